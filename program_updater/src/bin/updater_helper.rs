@@ -33,13 +33,34 @@ fn main() {
     println!("发现主程序更新文件: {}", source_file.display());
     println!("目标位置: {}", program_updater::config::MAIN_EXE_PATH);
     
-    //短暂延迟确保主程序已退出
+    //等待主程序进程退出（运行中的可执行文件在Windows下无法被覆盖）
     println!("等待主程序退出...");
-    std::thread::sleep(std::time::Duration::from_secs(2));
+    let timeout = std::time::Duration::from_secs(program_updater::config::WAIT_EXIT_TIMEOUT_SECS);
+    if !wait_for_process_exit(program_updater::config::MAIN_EXE_PATH, timeout) {
+        eprintln!("错误：等待主程序退出超时（{}秒）", program_updater::config::WAIT_EXIT_TIMEOUT_SECS);
+        std::process::exit(1);
+    }
+    
+    //读取并校验签名（与主程序使用同一公钥，未配置时拒绝替换）
+    let public_key = match program_updater::updater::load_public_key(program_updater::config::PUBLIC_KEY_PEM) {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("错误：{}", e);
+            std::process::exit(1);
+        }
+    };
+    let verified = match program_updater::updater::verify_file(&public_key, &source_file) {
+        Ok(verified) => verified,
+        Err(e) => {
+            eprintln!("错误：签名校验失败，放弃替换: {}", e);
+            std::process::exit(1);
+        }
+    };
+    println!("签名校验通过");
     
-    //复制源文件到目标位置
+    //复制已校验的文件到目标位置
     println!("正在替换主程序...");
-    if let Err(e) = copy_file(&source_file, program_updater::config::MAIN_EXE_PATH) {
+    if let Err(e) = program_updater::updater::copy_verified_with_progress(verified, program_updater::config::MAIN_EXE_PATH, |_, _| {}) {
         eprintln!("错误：替换失败: {}", e);
         std::process::exit(1);
     }
@@ -63,31 +84,39 @@ fn main() {
     println!("更新完成，助手退出");
 }
 
-//复制文件
-fn copy_file(source: &std::path::Path, target: &str) -> std::io::Result<()> {
-    //确保目标目录存在
-    if let std::option::Option::Some(parent) = std::path::Path::new(target).parent() {
-        if !parent.exists() {
-            std::fs::create_dir_all(parent)?;
-        }
-    }
-    
-    //复制文件
-    std::fs::copy(source, target)?;
-    
-    //在Linux上设置可执行权限
-    #[cfg(not(target_os = "windows"))]
-    {
-        let mut perms = std::fs::metadata(target)?.permissions();
-        std::os::unix::fs::PermissionsExt::set_mode(&mut perms, 0o755);
-        std::fs::set_permissions(target, perms)?;
-    }
-    
-    Ok(())
-}
-
 //启动程序
 fn launch_program(path: &str) -> std::io::Result<()> {
     std::process::Command::new(path).spawn()?;
     Ok(())
 }
+
+//判断是否有进程正在运行指定路径的可执行文件（按可执行文件完整路径匹配，不依赖tasklist/kill）
+fn is_executable_running(system: &sysinfo::System, exe_path: &str) -> bool {
+    let target = normalize_path(std::path::Path::new(exe_path));
+    let self_pid = std::process::id();
+    system.processes().values().any(|process| {
+        process.pid().as_u32() != self_pid
+            && process.exe().map(normalize_path).as_ref() == std::option::Option::Some(&target)
+    })
+}
+
+//等待指定可执行文件的所有进程退出，超时返回false
+fn wait_for_process_exit(exe_path: &str, timeout: std::time::Duration) -> bool {
+    let mut system = sysinfo::System::new();
+    let start = std::time::Instant::now();
+    loop {
+        system.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+        if !is_executable_running(&system, exe_path) {
+            return true;
+        }
+        if start.elapsed() >= timeout {
+            return false;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(200));
+    }
+}
+
+//规范化路径用于比较（文件存在时解析符号链接和相对路径）
+fn normalize_path(path: &std::path::Path) -> std::path::PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
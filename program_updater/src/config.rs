@@ -13,6 +13,15 @@ pub const FILE_MAPPINGS: &[(&str, &str)] = &[
 //启动文件路径（所有文件替换完成后启动此程序）
 pub const STARTUP_FILE: &str = "";
 
+//签名校验公钥（PEM格式，PKCS#8 "BEGIN PUBLIC KEY"）
+//每个待替换文件都需要同目录下的同名.sig签名文件（RSA + SHA256），
+//任一文件校验失败则终止整批替换；未配置时拒绝升级
+//示例：
+//pub const PUBLIC_KEY_PEM: &str = "-----BEGIN PUBLIC KEY-----
+//MIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEA...
+//-----END PUBLIC KEY-----";
+pub const PUBLIC_KEY_PEM: &str = "";
+
 //主程序（program_updater）的完整路径
 //供updater_helper使用，用于替换主程序
 //示例：Windows下为"C:/path/to/program_updater.exe"
@@ -23,3 +32,8 @@ pub const MAIN_EXE_PATH: &str = "";
 //供updater_helper在SOURCE_DIR中查找
 #[allow(dead_code)]
 pub const MAIN_EXE_NAME: &str = "program_updater.exe";
+
+//等待主程序退出的最长时间（秒）
+//updater_helper按MAIN_EXE_PATH轮询进程，超时仍未退出则放弃替换
+#[allow(dead_code)]
+pub const WAIT_EXIT_TIMEOUT_SECS: u64 = 30;
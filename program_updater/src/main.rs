@@ -2,6 +2,7 @@
 
 mod config;
 mod updater;
+mod progress;
 
 fn main() {
     println!("程序升级器启动...");
@@ -28,6 +29,15 @@ fn main() {
         std::process::exit(1);
     }
 
+    //加载签名校验公钥（未配置或解析失败时拒绝升级）
+    let public_key = match updater::load_public_key(config::PUBLIC_KEY_PEM) {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("错误：{}", e);
+            std::process::exit(1);
+        }
+    };
+
     //步骤1：获取源目录中的所有文件
     println!("检查源目录: {}", config::SOURCE_DIR);
     let source_files = match updater::get_source_files(config::SOURCE_DIR) {
@@ -38,8 +48,8 @@ fn main() {
         }
     };
     
-    //步骤2：遍历源文件，与映射表对比并替换
-    let mut replaced_count = 0;
+    //步骤2：筛选出映射表中的文件
+    let mut matched_files = std::vec::Vec::new();
     for source_file in &source_files {
         //获取文件名
         let filename = match source_file.file_name() {
@@ -52,17 +62,50 @@ fn main() {
         
         //在映射表中查找目标路径
         if let std::option::Option::Some(target_path) = updater::find_target_path(filename, config::FILE_MAPPINGS) {
-            println!("发现匹配文件: {} -> {}", filename, target_path);
-            
-            //复制替换
-            match updater::copy_file(source_file, target_path) {
-                Ok(()) => {
-                    println!("替换成功: {}", target_path);
-                    replaced_count += 1;
-                }
-                Err(e) => {
-                    eprintln!("错误：替换失败 {}: {}", target_path, e);
-                }
+            matched_files.push((source_file, filename, target_path));
+        }
+    }
+
+    //步骤3：校验签名，任一文件失败则终止整批替换
+    //文件按块校验，只保留句柄和摘要；替换时从同一句柄重新读取，内容与校验时不一致则放弃
+    let mut verified_files = std::vec::Vec::new();
+    for (source_file, filename, target_path) in &matched_files {
+        match updater::verify_file(&public_key, source_file) {
+            Ok(verified) => {
+                println!("签名校验通过: {}", filename);
+                verified_files.push((*filename, *target_path, verified));
+            }
+            Err(e) => {
+                eprintln!("错误：签名校验失败，终止升级: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    //步骤4：替换文件
+    let mut replaced_count = 0;
+    for (filename, target_path, verified) in verified_files {
+        println!("发现匹配文件: {} -> {}", filename, target_path);
+        
+        //复制替换（显示以目标文件名标注的字节进度条）
+        let target_name = std::path::Path::new(target_path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(target_path);
+        let pb = progress::file_bar(0, target_name);
+        let on_progress = |copied, total| {
+            pb.set_length(total);
+            pb.set_position(copied);
+        };
+        match updater::copy_verified_with_progress(verified, target_path, on_progress) {
+            Ok(()) => {
+                pb.finish();
+                println!("替换成功: {}", target_path);
+                replaced_count += 1;
+            }
+            Err(e) => {
+                pb.abandon();
+                eprintln!("错误：替换失败 {}: {}", target_path, e);
             }
         }
     }
@@ -70,7 +113,7 @@ fn main() {
     
     println!("共替换 {} 个文件", replaced_count);
     
-    //步骤3：清空源目录
+    //步骤5：清空源目录
     println!("正在清空源目录...");
     if let Err(e) = updater::clear_source_dir(config::SOURCE_DIR) {
         eprintln!("警告：清空源目录失败: {}", e);
@@ -78,7 +121,7 @@ fn main() {
         println!("源目录已清空");
     }
     
-    //步骤4：启动预设启动文件
+    //步骤6：启动预设启动文件
     println!("正在启动程序: {}", config::STARTUP_FILE);
     match updater::launch_executable(config::STARTUP_FILE) {
         Ok(_) => {
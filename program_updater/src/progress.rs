@@ -0,0 +1,14 @@
+//进度显示模块：替换文件时显示字节进度条（基于indicatif）
+
+//文件复制进度条样式：目标文件名 + 字节数 + 速度 + 预估时间
+const FILE_TEMPLATE: &str = "{prefix:.cyan} [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}) ETA: {eta}";
+
+//创建以文件名标注的字节进度条
+pub fn file_bar(total: u64, name: &str) -> indicatif::ProgressBar {
+    let pb = indicatif::ProgressBar::new(total);
+    if let Ok(style) = indicatif::ProgressStyle::default_bar().template(FILE_TEMPLATE) {
+        pb.set_style(style.progress_chars("█▓░"));
+    }
+    pb.set_prefix(name.to_string());
+    pb
+}
@@ -28,15 +28,18 @@
 
 use std::process::{Command, Stdio, Child, ExitStatus};
 use std::io::{Read, Write};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::thread;
-use std::sync::mpsc;
+use std::sync::{mpsc, Arc, Mutex};
 
 //========================================
 //命令输出结构
 //========================================
 
-///命令执行结果
+///命令执行结果（文本）
+///
+///stdout/stderr 按 UTF-8 解码，无效字节会被替换为 U+FFFD。
+///适合文本输出；命令输出二进制数据（图片、压缩包等）时使用 run_bytes() 获取 OutputBytes
 #[derive(Debug, Clone)]
 pub struct Output {
     ///标准输出
@@ -47,16 +50,19 @@ pub struct Output {
     pub status: i32,
     ///是否成功（状态码为0）
     pub success: bool,
+    ///执行耗时（从启动到进程退出）
+    pub elapsed: Duration,
 }
 
 impl Output {
-    ///从 std::process::Output 创建
-    fn from_std(output: std::process::Output) -> Self {
+    ///从 std::process::Output 创建（started 为进程启动时刻）
+    fn from_std(output: std::process::Output, started: Instant) -> Self {
         Self {
             stdout: String::from_utf8_lossy(&output.stdout).to_string(),
             stderr: String::from_utf8_lossy(&output.stderr).to_string(),
             status: output.status.code().unwrap_or(-1),
             success: output.status.success(),
+            elapsed: started.elapsed(),
         }
     }
 
@@ -80,6 +86,51 @@ impl Output {
     pub fn stderr_trimmed(&self) -> &str {
         self.stderr.trim()
     }
+
+    ///按行拆分 stdout（兼容 \n 与 \r\n，不含末尾空行）
+    pub fn stdout_lines(&self) -> Vec<&str> {
+        self.stdout.lines().collect()
+    }
+
+    ///按行拆分 stderr（兼容 \n 与 \r\n，不含末尾空行）
+    pub fn stderr_lines(&self) -> Vec<&str> {
+        self.stderr.lines().collect()
+    }
+}
+
+///命令执行结果（原始字节）
+///
+///stdout/stderr 保留进程输出的原始字节，不做任何解码
+#[derive(Debug, Clone)]
+pub struct OutputBytes {
+    ///标准输出
+    pub stdout: Vec<u8>,
+    ///标准错误
+    pub stderr: Vec<u8>,
+    ///退出状态码
+    pub status: i32,
+    ///是否成功（状态码为0）
+    pub success: bool,
+    ///执行耗时（从启动到进程退出）
+    pub elapsed: Duration,
+}
+
+impl OutputBytes {
+    ///从 std::process::Output 创建（started 为进程启动时刻）
+    fn from_std(output: std::process::Output, started: Instant) -> Self {
+        Self {
+            stdout: output.stdout,
+            stderr: output.stderr,
+            status: output.status.code().unwrap_or(-1),
+            success: output.status.success(),
+            elapsed: started.elapsed(),
+        }
+    }
+
+    ///按 UTF-8 解码 stderr（无效字节替换为 U+FFFD），便于输出错误信息
+    pub fn stderr_lossy(&self) -> String {
+        String::from_utf8_lossy(&self.stderr).to_string()
+    }
 }
 
 //========================================
@@ -120,12 +171,51 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 ///执行命令并获取输出
 pub fn run(program: &str, args: &[&str]) -> Result<Output> {
+    let started = Instant::now();
     let output = Command::new(program)
         .args(args)
         .output()
         .map_err(Error::SpawnFailed)?;
 
-    Ok(Output::from_std(output))
+    Ok(Output::from_std(output, started))
+}
+
+///执行命令并获取原始字节输出（适合二进制输出）
+pub fn run_bytes(program: &str, args: &[&str]) -> Result<OutputBytes> {
+    let started = Instant::now();
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(Error::SpawnFailed)?;
+
+    Ok(OutputBytes::from_std(output, started))
+}
+
+///执行命令，传入二进制输入并获取原始字节输出
+///
+///输入在独立线程中写入，输入输出都很大时也不会因管道写满而互相阻塞
+pub fn run_bytes_with_input(program: &str, args: &[&str], input: &[u8]) -> Result<OutputBytes> {
+    let started = Instant::now();
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(Error::SpawnFailed)?;
+
+    let writer = child.stdin.take().map(|mut stdin| {
+        let input = input.to_vec();
+        thread::spawn(move || stdin.write_all(&input))
+    });
+
+    let output = child.wait_with_output().map_err(Error::WaitFailed)?;
+    if let Some(writer) = writer {
+        //子进程未读完输入就退出时写入会失败（BrokenPipe），不视为错误
+        let _ = writer.join();
+    }
+
+    Ok(OutputBytes::from_std(output, started))
 }
 
 ///执行命令，仅返回成功与否
@@ -192,29 +282,50 @@ pub fn shell_silent(cmd: &str) -> Result<bool> {
 //========================================
 
 ///执行命令，带超时控制
+///
+///超时后终止子进程并回收，再返回 Error::Timeout
 pub fn run_with_timeout(program: &str, args: &[&str], timeout: Duration) -> Result<Output> {
-    let mut child = Command::new(program)
+    let started = Instant::now();
+    let child = Command::new(program)
         .args(args)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
         .map_err(Error::SpawnFailed)?;
 
+    wait_with_timeout(child, timeout, started, false)
+}
+
+///等待子进程结束并收集输出，超时则终止并回收子进程
+///
+///process_group 为 true 时连同进程组一起终止
+fn wait_with_timeout(child: Child, timeout: Duration, started: Instant, process_group: bool) -> Result<Output> {
+    let child = Arc::new(Mutex::new(child));
     let (tx, rx) = mpsc::channel();
 
-    let handle = thread::spawn(move || {
-        let result = child.wait_with_output();
-        let _ = tx.send(result);
-    });
+    let collector = {
+        let child = Arc::clone(&child);
+        thread::spawn(move || {
+            let _ = tx.send(collect_output(&child));
+        })
+    };
 
     match rx.recv_timeout(timeout) {
         Ok(result) => {
-            let _ = handle.join();
+            let _ = collector.join();
             let output = result.map_err(Error::WaitFailed)?;
-            Ok(Output::from_std(output))
+            Ok(Output::from_std(output, started))
         }
         Err(_) => {
-            //超时，尝试终止进程（注意：这里无法直接访问 child）
+            //超时：终止并回收子进程，收集线程随管道关闭自行退出
+            let mut child = child.lock().unwrap_or_else(|e| e.into_inner());
+            if process_group {
+                if let Err(e) = kill_process_group(child.id()) {
+                    eprintln!("终止进程组失败: {}", e);
+                }
+            }
+            let _ = child.kill();
+            let _ = child.wait();
             Err(Error::Timeout)
         }
     }
@@ -237,34 +348,63 @@ pub fn shell_with_timeout(cmd: &str, timeout: Duration) -> Result<Output> {
 
 ///进程句柄
 pub struct ProcessHandle {
-    child: Child,
+    ///子进程（spawn_with_output 时与后台收集线程共享）
+    child: Arc<Mutex<Child>>,
+    ///是否在独立进程组中启动（终止时连同子进程一起终止）
+    process_group: bool,
+    ///启动时刻
+    started: Instant,
 }
 
 impl ProcessHandle {
+    ///创建进程句柄
+    fn new(child: Child, process_group: bool) -> Self {
+        Self {
+            child: Arc::new(Mutex::new(child)),
+            process_group,
+            started: Instant::now(),
+        }
+    }
+
+    ///锁定子进程
+    fn child(&self) -> std::sync::MutexGuard<'_, Child> {
+        self.child.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     ///检查进程是否仍在运行
     pub fn is_running(&mut self) -> bool {
-        matches!(self.child.try_wait(), Ok(None))
+        matches!(self.child().try_wait(), Ok(None))
     }
 
     ///等待进程结束
-    pub fn wait(mut self) -> Result<Output> {
-        let output = self.child.wait_with_output().map_err(Error::WaitFailed)?;
-        Ok(Output::from_std(output))
+    pub fn wait(self) -> Result<Output> {
+        let output = collect_output(&self.child).map_err(Error::WaitFailed)?;
+        Ok(Output::from_std(output, self.started))
     }
 
     ///终止进程
+    ///
+    ///若通过 CommandBuilder::new_process_group(true) 启动，会终止整个进程组（含子孙进程）
     pub fn kill(&mut self) -> Result<()> {
-        self.child.kill().map_err(Error::IoError)
+        let mut child = self.child();
+        //组内其他进程终止失败时仍终止主进程，再返回进程组的错误
+        let group = if self.process_group {
+            kill_process_group(child.id())
+        } else {
+            Ok(())
+        };
+        child.kill().map_err(Error::IoError)?;
+        group.map_err(Error::IoError)
     }
 
     ///获取进程 ID
     pub fn pid(&self) -> u32 {
-        self.child.id()
+        self.child().id()
     }
 
     ///尝试获取退出状态（非阻塞）
     pub fn try_wait(&mut self) -> Result<Option<i32>> {
-        match self.child.try_wait() {
+        match self.child().try_wait() {
             Ok(Some(status)) => Ok(Some(status.code().unwrap_or(-1))),
             Ok(None) => Ok(None),
             Err(e) => Err(Error::WaitFailed(e)),
@@ -272,6 +412,82 @@ impl ProcessHandle {
     }
 }
 
+///读取子进程的全部输出并等待其退出
+///
+///子进程可能被其他线程共享（需要随时 kill），因此轮询退出状态而不是长期持有锁阻塞等待
+fn collect_output(child: &Mutex<Child>) -> std::io::Result<std::process::Output> {
+    let (stdout, stderr) = {
+        let mut child = child.lock().unwrap_or_else(|e| e.into_inner());
+        (child.stdout.take(), child.stderr.take())
+    };
+
+    //stderr 在独立线程中读取，避免任一管道写满导致子进程阻塞
+    let stderr_reader = thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut stderr) = stderr {
+            let _ = stderr.read_to_end(&mut buf);
+        }
+        buf
+    });
+
+    let mut stdout_buf = Vec::new();
+    if let Some(mut stdout) = stdout {
+        stdout.read_to_end(&mut stdout_buf)?;
+    }
+    let stderr_buf = stderr_reader.join().unwrap_or_default();
+
+    let status = loop {
+        if let Some(status) = child.lock().unwrap_or_else(|e| e.into_inner()).try_wait()? {
+            break status;
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    Ok(std::process::Output { status, stdout: stdout_buf, stderr: stderr_buf })
+}
+
+///终止进程组（进程组 ID 即组长进程 ID）
+///
+///进程组已不存在（组内进程均已退出）视为成功
+#[cfg(unix)]
+fn kill_process_group(pid: u32) -> std::io::Result<()> {
+    //标准库在 Unix 上已链接 libc，直接声明 killpg 以保持无第三方依赖
+    unsafe extern "C" {
+        fn killpg(pgrp: i32, sig: i32) -> i32;
+    }
+    const SIGKILL: i32 = 9;
+    const ESRCH: i32 = 3;
+
+    let pgrp = i32::try_from(pid)
+        .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, "进程 ID 超出范围"))?;
+    if unsafe { killpg(pgrp, SIGKILL) } == 0 {
+        return Ok(());
+    }
+    let err = std::io::Error::last_os_error();
+    if err.raw_os_error() == Some(ESRCH) {
+        Ok(())
+    } else {
+        Err(err)
+    }
+}
+
+///终止进程树（taskkill /T 按父子关系终止整棵进程树）
+#[cfg(windows)]
+fn kill_process_group(pid: u32) -> std::io::Result<()> {
+    let output = Command::new("taskkill")
+        .args(["/T", "/F", "/PID", &pid.to_string()])
+        .stdin(Stdio::null())
+        .output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!(
+            "taskkill 失败: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
 ///后台启动进程
 pub fn spawn(program: &str, args: &[&str]) -> Result<ProcessHandle> {
     let child = Command::new(program)
@@ -281,7 +497,7 @@ pub fn spawn(program: &str, args: &[&str]) -> Result<ProcessHandle> {
         .spawn()
         .map_err(Error::SpawnFailed)?;
 
-    Ok(ProcessHandle { child })
+    Ok(ProcessHandle::new(child, false))
 }
 
 ///后台启动 Shell 命令
@@ -301,6 +517,7 @@ pub fn spawn_shell(cmd: &str) -> Result<ProcessHandle> {
 
 ///执行命令并传递输入
 pub fn run_with_input(program: &str, args: &[&str], input: &str) -> Result<Output> {
+    let started = Instant::now();
     let mut child = Command::new(program)
         .args(args)
         .stdin(Stdio::piped())
@@ -315,7 +532,7 @@ pub fn run_with_input(program: &str, args: &[&str], input: &str) -> Result<Outpu
     }
 
     let output = child.wait_with_output().map_err(Error::WaitFailed)?;
-    Ok(Output::from_std(output))
+    Ok(Output::from_std(output, started))
 }
 
 ///通过 Shell 执行命令并传递输入
@@ -333,6 +550,28 @@ pub fn shell_with_input(cmd: &str, input: &str) -> Result<Output> {
 //命令构建器
 //========================================
 
+///输出重定向目标
+#[derive(Debug, Clone)]
+enum Redirect {
+    ///丢弃输出
+    Null,
+    ///写入文件（覆盖）
+    File(String),
+}
+
+impl Redirect {
+    ///转换为 Stdio
+    fn to_stdio(&self) -> Result<Stdio> {
+        match self {
+            Redirect::Null => Ok(Stdio::null()),
+            Redirect::File(path) => {
+                let file = std::fs::File::create(path).map_err(Error::IoError)?;
+                Ok(Stdio::from(file))
+            }
+        }
+    }
+}
+
 ///命令构建器
 pub struct CommandBuilder {
     program: String,
@@ -342,6 +581,9 @@ pub struct CommandBuilder {
     env_clear: bool,
     stdin_data: Option<String>,
     timeout: Option<Duration>,
+    new_process_group: bool,
+    stdout_redirect: Option<Redirect>,
+    stderr_redirect: Option<Redirect>,
 }
 
 impl CommandBuilder {
@@ -355,6 +597,9 @@ impl CommandBuilder {
             env_clear: false,
             stdin_data: None,
             timeout: None,
+            new_process_group: false,
+            stdout_redirect: None,
+            stderr_redirect: None,
         }
     }
 
@@ -393,12 +638,21 @@ impl CommandBuilder {
         self
     }
 
-    ///清除所有环境变量
+    ///清除所有环境变量（等同于 inherit_env(false)）
     pub fn env_clear(mut self) -> Self {
         self.env_clear = true;
         self
     }
 
+    ///设置是否继承当前进程的环境变量（默认继承）
+    ///
+    ///不继承时子进程只能看到通过 env() 设置的变量。
+    ///注意 PATH 也不会继承，程序名应使用绝对路径（可先用 which() 解析）
+    pub fn inherit_env(mut self, inherit: bool) -> Self {
+        self.env_clear = !inherit;
+        self
+    }
+
     ///设置标准输入
     pub fn stdin(mut self, data: &str) -> Self {
         self.stdin_data = Some(data.to_string());
@@ -411,6 +665,42 @@ impl CommandBuilder {
         self
     }
 
+    ///将标准输出重定向到文件（覆盖写入）
+    ///
+    ///适合长时间运行的后台进程，避免管道缓冲区写满导致子进程阻塞
+    pub fn stdout_file(mut self, path: &str) -> Self {
+        self.stdout_redirect = Some(Redirect::File(path.to_string()));
+        self
+    }
+
+    ///将标准错误重定向到文件（覆盖写入）
+    pub fn stderr_file(mut self, path: &str) -> Self {
+        self.stderr_redirect = Some(Redirect::File(path.to_string()));
+        self
+    }
+
+    ///丢弃标准输出
+    pub fn stdout_null(mut self) -> Self {
+        self.stdout_redirect = Some(Redirect::Null);
+        self
+    }
+
+    ///丢弃标准错误
+    pub fn stderr_null(mut self) -> Self {
+        self.stderr_redirect = Some(Redirect::Null);
+        self
+    }
+
+    ///设置是否在新进程组中启动
+    ///
+    ///启用后 ProcessHandle::kill 会终止整个进程组，避免 Shell 派生的子进程残留
+    ///- Unix：子进程调用 setpgid 成为新进程组组长
+    ///- Windows：使用 CREATE_NEW_PROCESS_GROUP 标志
+    pub fn new_process_group(mut self, enable: bool) -> Self {
+        self.new_process_group = enable;
+        self
+    }
+
     ///构建 Command 对象
     fn build(&self) -> Command {
         let mut cmd = Command::new(&self.program);
@@ -428,6 +718,21 @@ impl CommandBuilder {
             cmd.env(key, value);
         }
 
+        if self.new_process_group {
+            #[cfg(unix)]
+            {
+                use std::os::unix::process::CommandExt;
+                cmd.process_group(0);
+            }
+
+            #[cfg(windows)]
+            {
+                use std::os::windows::process::CommandExt;
+                const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+                cmd.creation_flags(CREATE_NEW_PROCESS_GROUP);
+            }
+        }
+
         cmd
     }
 
@@ -437,23 +742,27 @@ impl CommandBuilder {
             return self.run_complex();
         }
 
-        let output = self.build()
+        let mut cmd = self.build();
+        self.apply_redirects(&mut cmd, Stdio::piped, Stdio::piped)?;
+
+        let started = Instant::now();
+        let output = cmd
             .output()
             .map_err(Error::SpawnFailed)?;
 
-        Ok(Output::from_std(output))
+        Ok(Output::from_std(output, started))
     }
 
     ///复杂执行（带输入或超时）
     fn run_complex(self) -> Result<Output> {
         let mut cmd = self.build();
-        cmd.stdout(Stdio::piped());
-        cmd.stderr(Stdio::piped());
+        self.apply_redirects(&mut cmd, Stdio::piped, Stdio::piped)?;
 
         if self.stdin_data.is_some() {
             cmd.stdin(Stdio::piped());
         }
 
+        let started = Instant::now();
         let mut child = cmd.spawn().map_err(Error::SpawnFailed)?;
 
         //写入输入
@@ -463,34 +772,19 @@ impl CommandBuilder {
             }
         }
 
-        //带超时等待
+        //带超时等待，超时后终止子进程（启用 new_process_group 时终止整个进程组）
         if let Some(timeout) = self.timeout {
-            let (tx, rx) = mpsc::channel();
-
-            let handle = thread::spawn(move || {
-                let result = child.wait_with_output();
-                let _ = tx.send(result);
-            });
-
-            match rx.recv_timeout(timeout) {
-                Ok(result) => {
-                    let _ = handle.join();
-                    let output = result.map_err(Error::WaitFailed)?;
-                    Ok(Output::from_std(output))
-                }
-                Err(_) => Err(Error::Timeout),
-            }
+            wait_with_timeout(child, timeout, started, self.new_process_group)
         } else {
             let output = child.wait_with_output().map_err(Error::WaitFailed)?;
-            Ok(Output::from_std(output))
+            Ok(Output::from_std(output, started))
         }
     }
 
     ///后台启动
     pub fn spawn(self) -> Result<ProcessHandle> {
         let mut cmd = self.build();
-        cmd.stdout(Stdio::piped());
-        cmd.stderr(Stdio::piped());
+        self.apply_redirects(&mut cmd, Stdio::piped, Stdio::piped)?;
 
         if self.stdin_data.is_some() {
             cmd.stdin(Stdio::piped());
@@ -505,17 +799,70 @@ impl CommandBuilder {
             }
         }
 
-        Ok(ProcessHandle { child })
+        Ok(ProcessHandle::new(child, self.new_process_group))
+    }
+
+    ///后台执行，结果通过通道返回（不阻塞调用线程）
+    ///
+    ///命令在独立线程中执行，完成后将 Output（含 elapsed 耗时）发送到返回的通道
+    pub fn run_async(self) -> mpsc::Receiver<Result<Output>> {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(self.run());
+        });
+        rx
+    }
+
+    ///后台启动，并在进程退出后通过通道返回输出
+    ///
+    ///返回的 ProcessHandle 仍可用于查询状态或 kill；
+    ///输出由后台线程收集，不要再对该句柄调用 wait()
+    pub fn spawn_with_output(self) -> Result<(ProcessHandle, mpsc::Receiver<Output>)> {
+        let handle = self.spawn()?;
+        let child = Arc::clone(&handle.child);
+        let started = handle.started;
+
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            if let Ok(output) = collect_output(&child) {
+                let _ = tx.send(Output::from_std(output, started));
+            }
+        });
+
+        Ok((handle, rx))
     }
 
     ///仅返回成功与否
     pub fn status(self) -> Result<bool> {
-        let status = self.build()
+        let mut cmd = self.build();
+        self.apply_redirects(&mut cmd, Stdio::inherit, Stdio::inherit)?;
+
+        let status = cmd
             .status()
             .map_err(Error::SpawnFailed)?;
 
         Ok(status.success())
     }
+
+    ///设置标准输出/错误（未配置重定向时使用默认值）
+    fn apply_redirects(
+        &self,
+        cmd: &mut Command,
+        default_stdout: fn() -> Stdio,
+        default_stderr: fn() -> Stdio,
+    ) -> Result<()> {
+        let stdout = match self.stdout_redirect {
+            Some(ref r) => r.to_stdio()?,
+            None => default_stdout(),
+        };
+        let stderr = match self.stderr_redirect {
+            Some(ref r) => r.to_stdio()?,
+            None => default_stderr(),
+        };
+        cmd.stdout(stdout);
+        cmd.stderr(stderr);
+        Ok(())
+    }
 }
 
 //========================================
@@ -534,15 +881,65 @@ pub fn shell_output(cmd: &str) -> Result<String> {
     Ok(output.stdout.trim().to_string())
 }
 
-///检查命令是否存在
+///检查命令是否存在（在 PATH 中查找，不启动子进程）
 pub fn exists(program: &str) -> bool {
-    let check_cmd = if cfg!(target_os = "windows") {
-        format!("where {}", program)
+    which(program).is_some()
+}
+
+///查找可执行文件的完整路径（类似 which/where 命令）
+///
+///- 包含路径分隔符时（如 "./run.sh"）直接检查该路径
+///- 否则按 PATH 顺序查找，返回第一个匹配
+///- Windows 下按 PATHEXT（默认 .COM;.EXE;.BAT;.CMD）补全扩展名
+pub fn which(program: &str) -> Option<std::path::PathBuf> {
+    let path = std::path::Path::new(program);
+    if path.components().count() > 1 {
+        return find_executable(path);
+    }
+
+    let dirs = std::env::var_os("PATH")?;
+    std::env::split_paths(&dirs).find_map(|dir| find_executable(&dir.join(program)))
+}
+
+///检查候选路径（Windows 下依次尝试 PATHEXT 扩展名）
+fn find_executable(path: &std::path::Path) -> Option<std::path::PathBuf> {
+    if cfg!(target_os = "windows") {
+        if path.extension().is_some() && is_executable(path) {
+            return Some(path.to_path_buf());
+        }
+        let exts = std::env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string());
+        exts.split(';')
+            .filter(|ext| !ext.is_empty())
+            .map(|ext| {
+                let mut name = path.as_os_str().to_os_string();
+                name.push(ext);
+                std::path::PathBuf::from(name)
+            })
+            .find(|candidate| is_executable(candidate))
+    } else if is_executable(path) {
+        Some(path.to_path_buf())
     } else {
-        format!("which {}", program)
-    };
+        None
+    }
+}
 
-    shell_status(&check_cmd).unwrap_or(false)
+///判断路径是否为可执行文件（Unix 下检查执行权限位）
+fn is_executable(path: &std::path::Path) -> bool {
+    let Ok(meta) = std::fs::metadata(path) else {
+        return false;
+    };
+    if !meta.is_file() {
+        return false;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        meta.permissions().mode() & 0o111 != 0
+    }
+    #[cfg(not(unix))]
+    {
+        true
+    }
 }
 
 ///获取当前 Shell
@@ -553,3 +950,141 @@ pub fn current_shell() -> Option<String> {
         std::env::var("SHELL").ok()
     }
 }
+
+//========================================
+//测试
+//========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("command_{}_{}", std::process::id(), name))
+    }
+
+    ///读取 Shell 写入文件的进程 ID 列表
+    fn read_pids(path: &std::path::Path) -> Vec<u32> {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| line.trim().parse().unwrap())
+            .collect()
+    }
+
+    ///进程是否仍在运行（僵尸进程视为已退出）
+    #[cfg(target_os = "linux")]
+    fn is_alive(pid: u32) -> bool {
+        match std::fs::read_to_string(format!("/proc/{}/stat", pid)) {
+            //格式：pid (comm) state ...，comm 可能含空格，因此从最后一个 ')' 之后取状态
+            Ok(stat) => stat
+                .rsplit_once(')')
+                .map(|(_, rest)| !rest.trim_start().starts_with('Z'))
+                .unwrap_or(false),
+            Err(_) => false,
+        }
+    }
+
+    ///轮询等待进程退出（SIGKILL 投递与回收存在短暂延迟）
+    #[cfg(target_os = "linux")]
+    fn wait_gone(pid: u32) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while Instant::now() < deadline {
+            if !is_alive(pid) {
+                return true;
+            }
+            thread::sleep(Duration::from_millis(20));
+        }
+        false
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn timeout_kills_whole_process_group() {
+        let pid_file = temp_path("group_pids");
+        let script = format!(
+            "echo $$ > {0}; sleep 30 & echo $! >> {0}; sleep 30",
+            pid_file.display()
+        );
+
+        let result = CommandBuilder::new("sh")
+            .args(&["-c", &script])
+            .new_process_group(true)
+            .timeout(Duration::from_millis(300))
+            .run();
+        assert!(matches!(result, Err(Error::Timeout)));
+
+        let pids = read_pids(&pid_file);
+        let _ = std::fs::remove_file(&pid_file);
+        assert_eq!(pids.len(), 2);
+        for pid in pids {
+            assert!(wait_gone(pid), "进程 {} 未被终止", pid);
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn timeout_returns_promptly_and_reaps_child() {
+        let pid_file = temp_path("timeout_pid");
+        //exec 让 sleep 替换 Shell，写入的进程 ID 即被超时终止的子进程
+        let script = format!("echo $$ > {}; exec sleep 10", pid_file.display());
+
+        let started = Instant::now();
+        let result = CommandBuilder::new("sh")
+            .args(&["-c", &script])
+            .timeout(Duration::from_millis(100))
+            .run();
+        let elapsed = started.elapsed();
+        assert!(matches!(result, Err(Error::Timeout)));
+        assert!(elapsed < Duration::from_secs(2), "超时返回过慢: {:?}", elapsed);
+
+        let pids = read_pids(&pid_file);
+        let _ = std::fs::remove_file(&pid_file);
+        assert_eq!(pids.len(), 1);
+        //子进程已被 wait 回收，连僵尸进程也不应残留
+        assert!(!std::path::Path::new(&format!("/proc/{}", pids[0])).exists());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn run_bytes_keeps_non_utf8_stdout() {
+        //八进制转义 0xff 0xfe（\x 转义不是 POSIX printf 的标准写法）
+        let output = run_bytes("printf", &["\\377\\376"]).unwrap();
+        assert!(output.success);
+        assert_eq!(output.stdout, vec![0xff, 0xfe]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn inherit_env_false_hides_parent_vars() {
+        //以父进程必然存在的 PATH 作为观察对象，避免测试修改进程级环境变量
+        assert!(std::env::var_os("PATH").is_some());
+        //不继承环境时 PATH 也被清空，需使用绝对路径
+        let env_path = which("env").expect("找不到 env");
+        let env_path = env_path.to_str().unwrap();
+
+        let inherited = CommandBuilder::new(env_path).run().unwrap();
+        assert!(inherited.stdout.lines().any(|line| line.starts_with("PATH=")));
+
+        let isolated = CommandBuilder::new(env_path)
+            .inherit_env(false)
+            .env("ONLY_CHILD", "1")
+            .run()
+            .unwrap();
+        assert!(!isolated.stdout.lines().any(|line| line.starts_with("PATH=")));
+        assert_eq!(isolated.stdout.trim(), "ONLY_CHILD=1");
+    }
+
+    #[test]
+    fn which_missing_binary_is_none() {
+        assert!(which("definitely-not-a-binary").is_none());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn which_resolves_sh() {
+        let path = which("sh").expect("找不到 sh");
+        assert!(path.is_absolute());
+        assert!(path.ends_with("sh"));
+    }
+}
@@ -6,11 +6,14 @@
 //!- aes-gcm（使用时查询最新版本：https://crates.io/crates/aes-gcm）
 //!- aes（使用时查询最新版本：https://crates.io/crates/aes）
 //!- cbc（使用时查询最新版本：https://crates.io/crates/cbc）
+//!- ctr（使用时查询最新版本：https://crates.io/crates/ctr）
 //!- rand（使用时查询最新版本：https://crates.io/crates/rand）
+//!- zeroize（使用时查询最新版本：https://crates.io/crates/zeroize，SecretKey 使用）
 //!
 //!# AES-GCM vs AES-CBC
 //!- AES-GCM：带认证的加密，能检测数据篡改，推荐使用
 //!- AES-CBC：传统模式，需要自行处理数据完整性校验
+//!- AES-CTR：流模式，无填充、可随机访问，不带认证（需配合 HMAC 使用）
 //!
 //!# 示例
 //!```rust
@@ -21,13 +24,18 @@
 //!let nonce = aes::generate_nonce();
 //!let encrypted = aes::gcm_encrypt(&key, &nonce, b"hello").unwrap();
 //!let decrypted = aes::gcm_decrypt(&key, &nonce, &encrypted).unwrap();
+//!
+//!//大文件分块加密（内存占用与文件大小无关）
+//!aes::encrypt_file(&key, "data.bin", "data.bin.enc").unwrap();
+//!aes::decrypt_file(&key, "data.bin.enc", "data.bin").unwrap();
 //!```
 
 use aes_gcm::{
     Aes256Gcm,
-    aead::{Aead, KeyInit},
+    aead::{Aead, KeyInit, Payload},
 };
 use rand::RngCore;
+use zeroize::Zeroize;
 
 //========================================
 //密钥和随机数生成
@@ -40,6 +48,15 @@ pub fn generate_key() -> [u8; super::config::AES_KEY_SIZE] {
     key
 }
 
+///生成 AES-256 密钥，存放在离开作用域时自动清零的 SecretKey 中
+///
+///随机数直接写入 SecretKey 内部，不会在栈上留下副本
+pub fn generate_secret_key() -> SecretKey {
+    let mut key = SecretKey([0u8; super::config::AES_KEY_SIZE]);
+    rand::thread_rng().fill_bytes(&mut key.0);
+    key
+}
+
 ///生成 AES-GCM Nonce（12字节）
 pub fn generate_nonce() -> [u8; super::config::AES_GCM_NONCE_SIZE] {
     let mut nonce = [0u8; super::config::AES_GCM_NONCE_SIZE];
@@ -54,6 +71,65 @@ pub fn generate_iv() -> [u8; super::config::AES_CBC_IV_SIZE] {
     iv
 }
 
+//========================================
+//密钥安全存储
+//========================================
+
+///AES-256 密钥，Drop 时使用 zeroize 清零内存
+///
+///实现了 `Deref<Target = [u8; 32]>`，`&SecretKey` 可直接传给本模块所有接受 `&[u8; 32]` 的函数
+///
+///# 示例
+///```rust
+///let key = aes::generate_secret_key();
+///let data = aes::encrypt_simple(&key, b"hello").unwrap();
+///let plain = aes::decrypt_simple(&key, &data).unwrap();
+/////key 离开作用域时内存被清零
+///```
+pub struct SecretKey([u8; super::config::AES_KEY_SIZE]);
+
+impl SecretKey {
+    ///从字节数组创建（数组按值复制，调用方持有的原数组需自行清零）
+    pub fn from_bytes(bytes: [u8; super::config::AES_KEY_SIZE]) -> Self {
+        Self(bytes)
+    }
+
+    ///从切片创建（长度必须为 32 字节）
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.len() != super::config::AES_KEY_SIZE {
+            return Err(format!(
+                "密钥长度错误: 需要 {} 字节，实际 {} 字节",
+                super::config::AES_KEY_SIZE,
+                bytes.len()
+            ));
+        }
+        let mut key = Self([0u8; super::config::AES_KEY_SIZE]);
+        key.0.copy_from_slice(bytes);
+        Ok(key)
+    }
+}
+
+impl std::ops::Deref for SecretKey {
+    type Target = [u8; super::config::AES_KEY_SIZE];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+///调试输出不显示密钥内容
+impl std::fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SecretKey(***)")
+    }
+}
+
 //========================================
 //AES-GCM 加密（推荐）
 //带认证的加密，能检测数据篡改
@@ -137,6 +213,52 @@ pub fn cbc_decrypt(key: &[u8; 32], iv: &[u8; 16], ciphertext: &[u8]) -> Result<V
         .map_err(|e| format!("解密失败: {:?}", e))
 }
 
+//========================================
+//AES-CTR 加密
+//流模式，无填充，支持随机访问，不带认证
+//========================================
+
+use aes::cipher::{StreamCipher, StreamCipherSeek};
+
+type Aes256Ctr = ctr::Ctr128BE<aes::Aes256>;
+
+///AES-256-CTR 加密
+///
+///# 参数
+///- key: 32字节密钥
+///- iv: 16字节初始计数器（同一密钥下每次加密必须不同）
+///- data: 明文数据
+///
+///# 返回
+///密文（长度与明文相同）
+///
+///# 注意
+///CTR 模式不提供完整性保护，密文被篡改无法察觉，应配合 HMAC 使用
+pub fn ctr_encrypt(key: &[u8; 32], iv: &[u8; 16], data: &[u8]) -> Vec<u8> {
+    let mut cipher = Aes256Ctr::new(key.into(), iv.into());
+    let mut buf = data.to_vec();
+    cipher.apply_keystream(&mut buf);
+    buf
+}
+
+///AES-256-CTR 解密（与加密是相同的运算）
+pub fn ctr_decrypt(key: &[u8; 32], iv: &[u8; 16], data: &[u8]) -> Vec<u8> {
+    ctr_encrypt(key, iv, data)
+}
+
+///AES-256-CTR 从指定偏移处解密（随机访问）
+///
+///# 参数
+///- offset: data 在完整密文中的起始字节偏移
+///- data: 从 offset 开始的一段密文
+pub fn ctr_decrypt_at(key: &[u8; 32], iv: &[u8; 16], offset: u64, data: &[u8]) -> Vec<u8> {
+    let mut cipher = Aes256Ctr::new(key.into(), iv.into());
+    cipher.seek(offset);
+    let mut buf = data.to_vec();
+    cipher.apply_keystream(&mut buf);
+    buf
+}
+
 //========================================
 //便捷函数
 //========================================
@@ -164,3 +286,250 @@ pub fn decrypt_simple(key: &[u8; 32], data: &[u8]) -> Result<Vec<u8>, String> {
 
     gcm_decrypt(key, &nonce, ciphertext)
 }
+
+//========================================
+//文件分块加密（AES-GCM）
+//自定义容器格式，不是任何标准格式，只能由本模块解密
+//
+//文件头：[魔数:4字节][基础 nonce:12字节][块大小:4字节]
+//数据块：[密文:N字节][认证标签:16字节]，最后一块的明文可以不足块大小（可以为空）
+//
+//第 i 块的 nonce = 基础 nonce 的后 8 字节与 i（大端）异或，
+//附加认证数据 = 文件头 + 是否最后一块（1字节），
+//因此篡改、调换、删除数据块或截断文件都会在解密时被发现
+//========================================
+
+///文件头大小（字节）
+const FILE_HEADER_SIZE: usize = 4 + super::config::AES_GCM_NONCE_SIZE + 4;
+
+///分块加密文件，内存占用只与块大小有关
+///
+///# 参数
+///- key: 32字节密钥
+///- in_path: 明文文件路径
+///- out_path: 密文输出路径（已存在则覆盖）
+pub fn encrypt_file(key: &[u8; 32], in_path: &str, out_path: &str) -> Result<(), String> {
+    use std::io::Write;
+
+    let cipher = Aes256Gcm::new_from_slice(key)
+        .map_err(|e| format!("创建加密器失败: {}", e))?;
+    let mut input = std::fs::File::open(in_path)
+        .map_err(|e| format!("打开文件失败: {}", e))?;
+    let mut output = std::io::BufWriter::new(
+        std::fs::File::create(out_path).map_err(|e| format!("创建文件失败: {}", e))?,
+    );
+
+    let chunk_size = super::config::AES_FILE_CHUNK_SIZE;
+    let header = file_header(&generate_nonce(), chunk_size as u32);
+
+    let result = (|| {
+        output.write_all(&header)
+            .map_err(|e| format!("写入文件失败: {}", e))?;
+
+        //预读下一块，以便知道当前块是否为最后一块
+        let mut current = vec![0u8; chunk_size];
+        let mut next = vec![0u8; chunk_size];
+        let mut current_len = read_full(&mut input, &mut current)?;
+        let mut index = 0u64;
+        loop {
+            let next_len = if current_len == chunk_size {
+                read_full(&mut input, &mut next)?
+            } else {
+                0
+            };
+            let last = next_len == 0;
+
+            let nonce = chunk_nonce(&header, index);
+            let aad = chunk_aad(&header, last);
+            let encrypted = cipher
+                .encrypt(aes_gcm::Nonce::from_slice(&nonce), Payload { msg: &current[..current_len], aad: &aad })
+                .map_err(|e| format!("加密失败: {}", e))?;
+            output.write_all(&encrypted)
+                .map_err(|e| format!("写入文件失败: {}", e))?;
+
+            if last {
+                break;
+            }
+            std::mem::swap(&mut current, &mut next);
+            current_len = next_len;
+            index += 1;
+        }
+
+        output.flush().map_err(|e| format!("写入文件失败: {}", e))
+    })();
+
+    if result.is_err() {
+        drop(output);
+        let _ = std::fs::remove_file(out_path);
+    }
+    result
+}
+
+///解密 encrypt_file 生成的文件
+///
+///任一数据块校验失败都会返回错误，并删除已写出的不完整明文文件
+pub fn decrypt_file(key: &[u8; 32], in_path: &str, out_path: &str) -> Result<(), String> {
+    use std::io::{Read, Write};
+
+    let cipher = Aes256Gcm::new_from_slice(key)
+        .map_err(|e| format!("创建解密器失败: {}", e))?;
+    let mut input = std::fs::File::open(in_path)
+        .map_err(|e| format!("打开文件失败: {}", e))?;
+
+    //解析文件头
+    let mut header = [0u8; FILE_HEADER_SIZE];
+    input.read_exact(&mut header)
+        .map_err(|_| "文件格式错误: 文件头不完整".to_string())?;
+    if header[..4] != super::config::AES_FILE_MAGIC {
+        return Err("文件格式错误: 魔数不匹配".to_string());
+    }
+    let chunk_size = u32::from_be_bytes(header[FILE_HEADER_SIZE - 4..].try_into().unwrap()) as usize;
+    if chunk_size == 0 || chunk_size > super::config::AES_FILE_MAX_CHUNK_SIZE {
+        return Err(format!("文件格式错误: 块大小 {} 无效", chunk_size));
+    }
+
+    let mut output = std::io::BufWriter::new(
+        std::fs::File::create(out_path).map_err(|e| format!("创建文件失败: {}", e))?,
+    );
+
+    let result = (|| {
+        let block_size = chunk_size + super::config::AES_GCM_TAG_SIZE;
+        let mut current = vec![0u8; block_size];
+        let mut next = vec![0u8; block_size];
+        let mut current_len = read_full(&mut input, &mut current)?;
+        let mut index = 0u64;
+        loop {
+            let next_len = if current_len == block_size {
+                read_full(&mut input, &mut next)?
+            } else {
+                0
+            };
+            let last = next_len == 0;
+
+            let nonce = chunk_nonce(&header, index);
+            let aad = chunk_aad(&header, last);
+            let decrypted = cipher
+                .decrypt(aes_gcm::Nonce::from_slice(&nonce), Payload { msg: &current[..current_len], aad: &aad })
+                .map_err(|_| format!("解密失败（第 {} 块数据可能被篡改或文件被截断）", index))?;
+            output.write_all(&decrypted)
+                .map_err(|e| format!("写入文件失败: {}", e))?;
+
+            if last {
+                break;
+            }
+            std::mem::swap(&mut current, &mut next);
+            current_len = next_len;
+            index += 1;
+        }
+
+        output.flush().map_err(|e| format!("写入文件失败: {}", e))
+    })();
+
+    if result.is_err() {
+        drop(output);
+        let _ = std::fs::remove_file(out_path);
+    }
+    result
+}
+
+///组装文件头
+fn file_header(nonce: &[u8; 12], chunk_size: u32) -> [u8; FILE_HEADER_SIZE] {
+    let mut header = [0u8; FILE_HEADER_SIZE];
+    header[..4].copy_from_slice(&super::config::AES_FILE_MAGIC);
+    header[4..16].copy_from_slice(nonce);
+    header[16..].copy_from_slice(&chunk_size.to_be_bytes());
+    header
+}
+
+///计算第 index 块的 nonce（基础 nonce 后 8 字节与块序号异或）
+fn chunk_nonce(header: &[u8; FILE_HEADER_SIZE], index: u64) -> [u8; 12] {
+    let mut nonce: [u8; 12] = header[4..16].try_into().unwrap();
+    for (byte, counter) in nonce[4..].iter_mut().zip(index.to_be_bytes()) {
+        *byte ^= counter;
+    }
+    nonce
+}
+
+///计算数据块的附加认证数据（文件头 + 最后一块标记）
+fn chunk_aad(header: &[u8; FILE_HEADER_SIZE], last: bool) -> [u8; FILE_HEADER_SIZE + 1] {
+    let mut aad = [0u8; FILE_HEADER_SIZE + 1];
+    aad[..FILE_HEADER_SIZE].copy_from_slice(header);
+    aad[FILE_HEADER_SIZE] = last as u8;
+    aad
+}
+
+///尽量读满缓冲区，返回实际读取的字节数（小于缓冲区长度说明已到文件末尾）
+fn read_full(reader: &mut impl std::io::Read, buf: &mut [u8]) -> Result<usize, String> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(format!("读取文件失败: {}", e)),
+        }
+    }
+    Ok(filled)
+}
+
+//========================================
+//测试
+//========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("aes_{}_{}", std::process::id(), name))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i * 31 % 251) as u8).collect()
+    }
+
+    #[test]
+    fn file_round_trip_across_chunk_boundaries() {
+        let chunk = super::super::config::AES_FILE_CHUNK_SIZE;
+        let key = generate_key();
+        let (plain, encrypted, decrypted) = (temp_path("plain"), temp_path("enc"), temp_path("dec"));
+
+        for len in [0, 1, chunk - 1, chunk, chunk + 1, 3 * chunk + 12345] {
+            let content = data(len);
+            std::fs::write(&plain, &content).unwrap();
+            encrypt_file(&key, &plain, &encrypted).unwrap();
+            decrypt_file(&key, &encrypted, &decrypted).unwrap();
+            assert_eq!(std::fs::read(&decrypted).unwrap(), content, "长度 {}", len);
+        }
+
+        for path in [&plain, &encrypted, &decrypted] {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
+    #[test]
+    fn tampered_chunk_fails_and_removes_output() {
+        let chunk = super::super::config::AES_FILE_CHUNK_SIZE;
+        let key = generate_key();
+        let (plain, encrypted, decrypted) = (temp_path("t_plain"), temp_path("t_enc"), temp_path("t_dec"));
+        std::fs::write(&plain, data(2 * chunk + 100)).unwrap();
+        encrypt_file(&key, &plain, &encrypted).unwrap();
+
+        //翻转第 2 块（序号 1）中的一个字节
+        let mut bytes = std::fs::read(&encrypted).unwrap();
+        let block_size = chunk + super::super::config::AES_GCM_TAG_SIZE;
+        bytes[FILE_HEADER_SIZE + block_size + 10] ^= 0x01;
+        std::fs::write(&encrypted, &bytes).unwrap();
+
+        let err = decrypt_file(&key, &encrypted, &decrypted).unwrap_err();
+        assert!(err.contains("第 1 块"), "{}", err);
+        assert!(!std::path::Path::new(&decrypted).exists());
+
+        for path in [&plain, &encrypted] {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
@@ -15,6 +15,9 @@ pub const RSA_MIN_BITS: usize = 1024;
 ///RSA 最大密钥长度（位）
 pub const RSA_MAX_BITS: usize = 4096;
 
+///RSA PKCS#1 v1.5 加密填充开销（字节）
+pub const RSA_PKCS1_PADDING_OVERHEAD: usize = 11;
+
 //========================================
 //AES 配置
 //========================================
@@ -28,9 +31,24 @@ pub const AES_GCM_NONCE_SIZE: usize = 12;
 ///AES-CBC IV 长度（字节）
 pub const AES_CBC_IV_SIZE: usize = 16;
 
+///AES-GCM 认证标签长度（字节）
+pub const AES_GCM_TAG_SIZE: usize = 16;
+
+///文件加密容器的魔数
+pub const AES_FILE_MAGIC: [u8; 4] = *b"AGF1";
+
+///文件加密时每块明文大小（字节）
+pub const AES_FILE_CHUNK_SIZE: usize = 64 * 1024;
+
+///文件解密时允许的最大块大小（字节），防止损坏的文件头导致超大内存分配
+pub const AES_FILE_MAX_CHUNK_SIZE: usize = 16 * 1024 * 1024;
+
 //========================================
 //哈希配置
 //========================================
 
 ///是否使用大写十六进制输出
 pub const HASH_UPPERCASE: bool = false;
+
+///文件哈希时每次读取的块大小（字节）
+pub const HASH_FILE_CHUNK_SIZE: usize = 64 * 1024;
@@ -14,6 +14,12 @@
 //!let md5_hash = hash::md5("hello");
 //!let sha256_hash = hash::sha256("hello");
 //!let sha512_hash = hash::sha512("hello");
+//!
+//!//增量计算（流式数据）
+//!let mut hasher = hash::Hasher::sha256();
+//!hasher.update(b"hel");
+//!hasher.update(b"lo");
+//!assert_eq!(hasher.finalize(), hash::sha256("hello"));
 //!```
 
 use sha2::Digest;
@@ -53,6 +59,43 @@ pub fn sha256_bytes(data: &[u8]) -> String {
     to_hex(&result)
 }
 
+///计算文件的 SHA256 哈希值（分块读取，适合大文件）
+pub fn sha256_file(path: &str) -> Result<String, String> {
+    sha256_file_with_progress(path, |_, _| {})
+}
+
+///计算文件的 SHA256 哈希值，每读取一块回调一次进度
+///
+///回调参数：(已处理字节数, 文件总大小)，可直接驱动 progress 模块的字节进度条
+pub fn sha256_file_with_progress<F>(path: &str, mut on_progress: F) -> Result<String, String>
+where
+    F: FnMut(u64, u64),
+{
+    use std::io::Read;
+
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| format!("打开文件失败: {}", e))?;
+    let total = file.metadata()
+        .map_err(|e| format!("读取文件信息失败: {}", e))?
+        .len();
+
+    let mut hasher = Hasher::sha256();
+    let mut buffer = vec![0u8; super::config::HASH_FILE_CHUNK_SIZE];
+    let mut processed = 0u64;
+    loop {
+        let n = file.read(&mut buffer)
+            .map_err(|e| format!("读取文件失败: {}", e))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+        processed += n as u64;
+        on_progress(processed, total);
+    }
+
+    Ok(hasher.finalize())
+}
+
 //========================================
 //SHA512 哈希
 //========================================
@@ -70,6 +113,66 @@ pub fn sha512_bytes(data: &[u8]) -> String {
     to_hex(&result)
 }
 
+//========================================
+//增量哈希
+//数据分多次到达时使用（大文件、网络流），无需先拼接完整数据
+//========================================
+
+///哈希算法状态
+enum HasherState {
+    Md5(md5::Md5),
+    Sha256(sha2::Sha256),
+    Sha512(sha2::Sha512),
+}
+
+///增量哈希计算器
+///
+///# 示例
+///```rust
+///let mut hasher = hash::Hasher::sha256();
+///hasher.update(b"hel");
+///hasher.update(b"lo");
+///let digest = hasher.finalize();  //与 hash::sha256("hello") 相同
+///```
+pub struct Hasher {
+    state: HasherState,
+}
+
+impl Hasher {
+    ///创建 MD5 计算器（MD5 已不安全，仅用于兼容旧系统）
+    pub fn md5() -> Self {
+        Self { state: HasherState::Md5(md5::Md5::new()) }
+    }
+
+    ///创建 SHA256 计算器
+    pub fn sha256() -> Self {
+        Self { state: HasherState::Sha256(sha2::Sha256::new()) }
+    }
+
+    ///创建 SHA512 计算器
+    pub fn sha512() -> Self {
+        Self { state: HasherState::Sha512(sha2::Sha512::new()) }
+    }
+
+    ///追加数据
+    pub fn update(&mut self, data: &[u8]) {
+        match &mut self.state {
+            HasherState::Md5(h) => h.update(data),
+            HasherState::Sha256(h) => h.update(data),
+            HasherState::Sha512(h) => h.update(data),
+        }
+    }
+
+    ///结束计算，返回十六进制哈希值
+    pub fn finalize(self) -> String {
+        match self.state {
+            HasherState::Md5(h) => to_hex(&h.finalize()),
+            HasherState::Sha256(h) => to_hex(&h.finalize()),
+            HasherState::Sha512(h) => to_hex(&h.finalize()),
+        }
+    }
+}
+
 //========================================
 //辅助函数
 //========================================
@@ -89,18 +192,32 @@ pub fn from_hex(hex_str: &str) -> Result<Vec<u8>, hex::FromHexError> {
 }
 
 //========================================
-//HMAC（可选，需要额外依赖 hmac 库）
+//测试
 //========================================
 
-//如需 HMAC 功能，添加依赖：
-//hmac = "0.12"  # https://crates.io/crates/hmac
-//
-//示例：
-//use hmac::{Hmac, Mac};
-//type HmacSha256 = Hmac<sha2::Sha256>;
-//
-//pub fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
-//    let mut mac = HmacSha256::new_from_slice(key).unwrap();
-//    mac.update(data);
-//    mac.finalize().into_bytes().to_vec()
-//}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///流式哈希构造函数与对应的一次性哈希函数
+    type Case = (fn() -> Hasher, fn(&[u8]) -> String);
+
+    #[test]
+    fn incremental_update_matches_one_shot() {
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i % 256) as u8).collect();
+        let cases: [Case; 3] = [
+            (Hasher::md5, md5_bytes),
+            (Hasher::sha256, sha256_bytes),
+            (Hasher::sha512, sha512_bytes),
+        ];
+
+        for (new_hasher, one_shot) in cases {
+            let mut hasher = new_hasher();
+            //拆分点不与块大小对齐，并包含空片段
+            for piece in [&data[..0], &data[..1], &data[1..63], &data[63..4097], &data[4097..]] {
+                hasher.update(piece);
+            }
+            assert_eq!(hasher.finalize(), one_shot(&data));
+        }
+    }
+}
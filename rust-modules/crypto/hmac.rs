@@ -0,0 +1,135 @@
+//!HMAC 消息认证模块
+//!
+//!提供 HMAC-SHA256 签名与校验，常用于 Webhook 签名、API 请求签名等场景。
+//!
+//!依赖：
+//!- hmac（使用时查询最新版本：https://crates.io/crates/hmac）
+//!- sha2（使用时查询最新版本：https://crates.io/crates/sha2）
+//!- hex（使用时查询最新版本：https://crates.io/crates/hex）
+//!
+//!# 示例
+//!```rust
+//!use crypto::hmac;
+//!
+//!//签名
+//!let signature = hmac::sha256(b"secret", b"payload");
+//!
+//!//校验（常量时间比较）
+//!let valid = hmac::verify(b"secret", b"payload", &signature);
+//!```
+
+use hmac::{Hmac, Mac};
+
+type HmacSha256 = Hmac<sha2::Sha256>;
+
+//========================================
+//HMAC-SHA256
+//========================================
+
+///计算 HMAC-SHA256，返回十六进制字符串
+///
+///# 参数
+///- key: 密钥（任意长度）
+///- data: 待签名数据
+pub fn sha256(key: &[u8], data: &[u8]) -> String {
+    hex::encode(sha256_bytes(key, data))
+}
+
+///计算 HMAC-SHA256，返回原始字节（32字节）
+pub fn sha256_bytes(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = new_sha256(key);
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+///校验 HMAC-SHA256 签名
+///
+///expected_hex 为十六进制签名（不区分大小写），格式错误时返回 false。
+///比较过程为常量时间，不会因签名前缀匹配的长短泄露耗时信息
+pub fn verify(key: &[u8], data: &[u8], expected_hex: &str) -> bool {
+    let expected = match hex::decode(expected_hex.trim()) {
+        Ok(bytes) => bytes,
+        Err(_) => return false,
+    };
+
+    let mut mac = new_sha256(key);
+    mac.update(data);
+    mac.verify_slice(&expected).is_ok()
+}
+
+///创建 HMAC-SHA256 计算器（HMAC 接受任意长度的密钥，不会失败）
+fn new_sha256(key: &[u8]) -> HmacSha256 {
+    <HmacSha256 as Mac>::new_from_slice(key).expect("HMAC 接受任意长度的密钥")
+}
+
+//========================================
+//测试
+//========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///RFC 4231 测试向量（第 5 组为截断输出，不适用）
+    fn rfc4231_cases() -> Vec<(Vec<u8>, Vec<u8>, &'static str)> {
+        vec![
+            (
+                vec![0x0b; 20],
+                b"Hi There".to_vec(),
+                "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+            ),
+            (
+                b"Jefe".to_vec(),
+                b"what do ya want for nothing?".to_vec(),
+                "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            ),
+            (
+                vec![0xaa; 20],
+                vec![0xdd; 50],
+                "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+            ),
+            (
+                (0x01..=0x19).collect(),
+                vec![0xcd; 50],
+                "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b",
+            ),
+            (
+                vec![0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec(),
+                "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+            ),
+            (
+                vec![0xaa; 131],
+                b"This is a test using a larger than block-size key and a larger than block-size data. \
+The key needs to be hashed before being used by the HMAC algorithm."
+                    .to_vec(),
+                "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+            ),
+        ]
+    }
+
+    #[test]
+    fn sha256_matches_rfc4231_vectors() {
+        for (key, data, expected) in rfc4231_cases() {
+            assert_eq!(sha256(&key, &data), expected);
+            assert_eq!(sha256_bytes(&key, &data), hex::decode(expected).unwrap());
+        }
+    }
+
+    #[test]
+    fn verify_accepts_rfc4231_vectors_in_any_case() {
+        for (key, data, expected) in rfc4231_cases() {
+            assert!(verify(&key, &data, expected));
+            assert!(verify(&key, &data, &expected.to_uppercase()));
+        }
+    }
+
+    #[test]
+    fn verify_rejects_wrong_key_data_or_malformed_hex() {
+        let signature = sha256(b"Jefe", b"what do ya want for nothing?");
+        assert!(!verify(b"jefe", b"what do ya want for nothing?", &signature));
+        assert!(!verify(b"Jefe", b"what do ya want for nothing!", &signature));
+        assert!(!verify(b"Jefe", b"what do ya want for nothing?", &signature[..62]));
+        assert!(!verify(b"Jefe", b"what do ya want for nothing?", "not hex"));
+    }
+}
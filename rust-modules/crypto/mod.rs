@@ -1,6 +1,6 @@
 //!加密工具模块
 //!
-//!提供常用的加密/解密功能，包括哈希算法、对称加密和非对称加密。
+//!提供常用的加密/解密功能，包括哈希算法、HMAC、对称加密和非对称加密。
 //!
 //!# 目录结构
 //!```
@@ -8,6 +8,7 @@
 //!├── mod.rs      # 模块入口（本文件）
 //!├── config.rs   # 配置项
 //!├── hash.rs     # 哈希算法（MD5/SHA256/SHA512）
+//!├── hmac.rs     # HMAC 消息认证
 //!├── aes.rs      # AES 对称加密
 //!└── rsa.rs      # RSA 非对称加密
 //!```
@@ -20,9 +21,12 @@
 //!aes-gcm = "0.10"   # https://crates.io/crates/aes-gcm
 //!aes = "0.8"        # https://crates.io/crates/aes
 //!cbc = "0.1"        # https://crates.io/crates/cbc
-//!rsa = "0.9"        # https://crates.io/crates/rsa
+//!ctr = "0.9"        # https://crates.io/crates/ctr
+//!rsa = { version = "0.9", features = ["pem"] }  # https://crates.io/crates/rsa
 //!rand = "0.8"       # https://crates.io/crates/rand
+//!hmac = "0.12"      # https://crates.io/crates/hmac
 //!hex = "0.4"        # https://crates.io/crates/hex
+//!zeroize = "1"      # https://crates.io/crates/zeroize
 //!```
 //!
 //!> 注：使用前请到 crates.io 查询依赖的最新版本
@@ -42,6 +46,16 @@
 //!}
 //!```
 //!
+//!## HMAC
+//!```rust
+//!mod crypto;
+//!
+//!fn main() {
+//!    let signature = crypto::hmac::sha256(b"secret", b"payload");
+//!    let valid = crypto::hmac::verify(b"secret", b"payload", &signature);
+//!}
+//!```
+//!
 //!## AES 加密
 //!```rust
 //!mod crypto;
@@ -75,9 +89,11 @@
 
 pub mod config;
 pub mod hash;
+pub mod hmac;
 pub mod aes;
 pub mod rsa;
 
 //重新导出常用类型
 pub use hash::{md5, sha256, sha512};
+pub use hmac::{sha256 as hmac_sha256, verify as hmac_verify};
 pub use aes::{gcm_encrypt, gcm_decrypt};
@@ -2,12 +2,17 @@
 //!
 //!提供日期时间处理、格式化、计算等常用功能。
 //!
-//!依赖：chrono（使用时查询最新版本：https://crates.io/crates/chrono）
+//!依赖：
+//!- chrono（使用时查询最新版本：https://crates.io/crates/chrono）
+//!- chrono-tz（使用时查询最新版本：https://crates.io/crates/chrono-tz，命名时区转换使用）
+//!- serde（使用时查询最新版本：https://crates.io/crates/serde）
 //!
 //!# Cargo.toml 配置示例
 //!```toml
 //![dependencies]
 //!chrono = "0.4"  # https://crates.io/crates/chrono
+//!chrono-tz = "0.10"  # https://crates.io/crates/chrono-tz
+//!serde = { version = "1", features = ["derive"] }  # https://crates.io/crates/serde
 //!```
 //!
 //!# 快速开始
@@ -157,10 +162,32 @@ pub fn parse(s: &str) -> Result<LocalDateTime, String> {
 }
 
 ///解析自定义格式的时间字符串
+///
+///# 夏令时处理
+///- 夏令时回拨造成的重复时间（如 01:30 出现两次）：取较早的一个
+///- 夏令时跳过、实际不存在的时间（如 02:30 被跳过）：返回错误
 pub fn parse_with_format(s: &str, fmt: &str) -> Result<LocalDateTime, String> {
-    NaiveDateTime::parse_from_str(s, fmt)
-        .map(|naive| Local.from_local_datetime(&naive).unwrap())
-        .map_err(|e| format!("解析失败: {}", e))
+    let naive = NaiveDateTime::parse_from_str(s, fmt)
+        .map_err(|e| format!("解析失败: {}", e))?;
+    local_from_naive(&naive, false)
+        .ok_or_else(|| format!("解析失败: 本地时间 {} 不存在（处于夏令时跳过的时间段）", naive))
+}
+
+///本地日期时间转换为 LocalDateTime
+///
+///重复时间按实际时刻取较早（latest 为 true 时取较晚）的一个，不存在的时间返回 None
+fn local_from_naive(naive: &NaiveDateTime, latest: bool) -> Option<LocalDateTime> {
+    from_naive_in(&Local, naive, latest)
+}
+
+///指定时区的本地日期时间转换为 DateTime（规则同 local_from_naive）
+fn from_naive_in<Tz: TimeZone>(tz: &Tz, naive: &NaiveDateTime, latest: bool) -> Option<DateTime<Tz>> {
+    match tz.from_local_datetime(naive) {
+        chrono::LocalResult::Single(dt) => Some(dt),
+        chrono::LocalResult::Ambiguous(a, b) if latest => Some(a.max(b)),
+        chrono::LocalResult::Ambiguous(a, b) => Some(a.min(b)),
+        chrono::LocalResult::None => None,
+    }
 }
 
 ///解析日期字符串
@@ -176,6 +203,70 @@ pub fn parse_iso(s: &str) -> Result<UtcDateTime, String> {
         .map_err(|e| format!("解析失败: {}", e))
 }
 
+///parse_any() 依次尝试的格式（RFC 3339 / RFC 2822 单独处理）
+const PARSE_ANY_FORMATS: &[&str] = &[
+    formats::LOG,
+    formats::DEFAULT,
+    formats::ISO8601,
+    "%Y-%m-%dT%H:%M:%S",
+    formats::FULL_CN,
+    formats::DATE,
+    formats::DATE_CN,
+    formats::COMPACT,
+];
+
+///自动识别格式解析时间字符串
+///
+///依次尝试 RFC 3339、formats 中的常用格式（LOG、DEFAULT、ISO8601、FULL_CN、DATE、DATE_CN、COMPACT）、
+///不带时区的 ISO 格式（2024-01-15T13:45:30）
+///及 RFC 2822，返回第一个成功的结果；带时区的输入会转换为本地时间，只有日期时取当天 00:00:00
+///
+///# 示例
+///```rust
+///let a = datetime::parse_any("2024-01-15").unwrap();
+///let b = datetime::parse_any("2024-01-15 13:45:30").unwrap();
+///let c = datetime::parse_any("2024-01-15T13:45:30+08:00").unwrap();
+///```
+pub fn parse_any(s: &str) -> Result<LocalDateTime, String> {
+    let s = s.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Ok(dt.with_timezone(&Local));
+    }
+    if let Some(dt) = PARSE_ANY_FORMATS.iter().find_map(|fmt| parse_one(s, fmt)) {
+        return Ok(dt);
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc2822(s) {
+        return Ok(dt.with_timezone(&Local));
+    }
+    Err(format!(
+        "无法识别的时间格式: {}（已尝试: RFC3339, {}, RFC2822）",
+        s,
+        PARSE_ANY_FORMATS.join(", ")
+    ))
+}
+
+///按给定格式列表依次尝试解析，返回第一个成功的结果
+///
+///格式可以包含时区（%z）、完整日期时间或只有日期
+pub fn parse_try(s: &str, formats: &[&str]) -> Result<LocalDateTime, String> {
+    let s = s.trim();
+    formats
+        .iter()
+        .find_map(|fmt| parse_one(s, fmt))
+        .ok_or_else(|| format!("无法识别的时间格式: {}（已尝试: {}）", s, formats.join(", ")))
+}
+
+///按单个格式解析（依次尝试带时区、日期时间、纯日期）
+fn parse_one(s: &str, fmt: &str) -> Option<LocalDateTime> {
+    if let Ok(dt) = DateTime::parse_from_str(s, fmt) {
+        return Some(dt.with_timezone(&Local));
+    }
+    let naive = NaiveDateTime::parse_from_str(s, fmt)
+        .ok()
+        .or_else(|| chrono::NaiveDate::parse_from_str(s, fmt).ok()?.and_hms_opt(0, 0, 0))?;
+    local_from_naive(&naive, false)
+}
+
 //========================================
 //时间戳转换
 //========================================
@@ -205,6 +296,29 @@ pub fn to_timestamp_millis<Tz: TimeZone>(dt: &DateTime<Tz>) -> i64 {
     dt.timestamp_millis()
 }
 
+//========================================
+//时区转换
+//使用 IANA 时区名称（如 Asia/Tokyo、America/New_York），自动处理夏令时
+//========================================
+
+///转换到指定时区
+///
+///# 示例
+///```rust
+///let tokyo = datetime::to_timezone(&datetime::now_utc(), "Asia/Tokyo").unwrap();
+///```
+pub fn to_timezone<Tz: TimeZone>(dt: &DateTime<Tz>, tz_name: &str) -> Result<DateTime<chrono_tz::Tz>, String> {
+    let tz: chrono_tz::Tz = tz_name
+        .parse()
+        .map_err(|_| format!("未知时区: {}（应为 IANA 时区名称，如 Asia/Shanghai）", tz_name))?;
+    Ok(dt.with_timezone(&tz))
+}
+
+///转换到指定时区后按格式输出
+pub fn format_in_tz<Tz: TimeZone>(dt: &DateTime<Tz>, tz_name: &str, fmt: &str) -> Result<String, String> {
+    Ok(to_timezone(dt, tz_name)?.format(fmt).to_string())
+}
+
 //========================================
 //时间计算
 //========================================
@@ -262,6 +376,137 @@ pub fn diff<Tz1: TimeZone, Tz2: TimeZone>(
     TimeDiff::from_seconds(secs)
 }
 
+//========================================
+//时间取整
+//========================================
+
+///向下取整到整分钟
+pub fn truncate_to_minute<Tz: TimeZone>(dt: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+    truncate_to(dt, Duration::minutes(1))
+}
+
+///向下取整到整小时
+pub fn truncate_to_hour<Tz: TimeZone>(dt: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+    truncate_to(dt, Duration::hours(1))
+}
+
+///向下取整到当天 00:00:00（按 dt 所在时区）
+pub fn truncate_to_day<Tz: TimeZone>(dt: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+    truncate_to(dt, Duration::days(1))
+}
+
+///向下取整到指定时长的整数倍（以 Unix 纪元为起点，按 dt 所在时区的本地时间计算）
+///
+///常用于时间序列分桶，如 truncate_to(&dt, Duration::minutes(5))
+///
+///先对本地时间（NaiveDateTime）取整，再换算回 dt 所在时区：
+///取整结果落在重复时间段（夏令时结束）内时，优先取与 dt 相同 UTC 偏移的时刻，否则取较早的时刻
+///
+///# 注意
+///duration 非正数，或取整结果落在夏令时跳过的时间段内（该本地时间不存在）时返回 None
+pub fn truncate_to<Tz: TimeZone>(dt: &DateTime<Tz>, duration: Duration) -> Option<DateTime<Tz>> {
+    use chrono::DurationRound;
+    let naive = dt.naive_local().duration_trunc(duration).ok()?;
+    resolve_rounded(dt, &naive)
+}
+
+///四舍五入到指定时长的整数倍（正好一半时向上取，规则同 truncate_to）
+///
+///# 注意
+///duration 非正数，或结果落在夏令时跳过的时间段内时返回 None
+pub fn round_to<Tz: TimeZone>(dt: &DateTime<Tz>, duration: Duration) -> Option<DateTime<Tz>> {
+    use chrono::DurationRound;
+    let naive = dt.naive_local().duration_round(duration).ok()?;
+    resolve_rounded(dt, &naive)
+}
+
+///把取整后的本地时间换算回 dt 所在时区
+///
+///重复时间段内优先取与 dt 偏移相同的时刻（如 01:30 EST 取整到小时得到 01:00 EST 而非 01:00 EDT），
+///否则取较早的时刻；跳过的时间段返回 None
+fn resolve_rounded<Tz: TimeZone>(dt: &DateTime<Tz>, naive: &NaiveDateTime) -> Option<DateTime<Tz>> {
+    use chrono::Offset;
+    match dt.timezone().from_local_datetime(naive) {
+        chrono::LocalResult::Single(t) => Some(t),
+        chrono::LocalResult::Ambiguous(a, b) => {
+            let offset = dt.offset().fix();
+            if b.offset().fix() == offset && a.offset().fix() != offset {
+                Some(b)
+            } else {
+                Some(a.min(b))
+            }
+        }
+        chrono::LocalResult::None => None,
+    }
+}
+
+//========================================
+//日历计算
+//以下函数按 dt 所在时区的本地日期计算；
+//结果落在夏令时跳过的时间段内时返回 None，落在重复时间段内时取较早的时刻
+//========================================
+
+///加减工作日（跳过周六、周日，不考虑法定节假日）
+///
+///从周末开始计算时，+1 个工作日为下周一；n 为 0 时原样返回。
+///按本地日期逐日推算，时分秒保持不变（跨越夏令时切换也不会偏移一小时）
+pub fn add_business_days<Tz: TimeZone>(dt: &DateTime<Tz>, n: i64) -> Option<DateTime<Tz>> {
+    if n == 0 {
+        return Some(dt.clone());
+    }
+    let naive = dt.naive_local();
+    let mut date = naive.date();
+    let mut remaining = n.unsigned_abs();
+    while remaining > 0 {
+        date = if n > 0 { date.succ_opt()? } else { date.pred_opt()? };
+        if date.weekday().number_from_monday() <= 5 {
+            remaining -= 1;
+        }
+    }
+    from_naive_in(&dt.timezone(), &date.and_time(naive.time()), false)
+}
+
+///加减月数（月末溢出时取目标月最后一天，如 1月31日 + 1个月 = 2月28日或29日）
+///
+///时分秒保持不变
+pub fn add_months<Tz: TimeZone>(dt: &DateTime<Tz>, months: i32) -> Option<DateTime<Tz>> {
+    let naive = dt.naive_local();
+    let shifted = if months >= 0 {
+        naive.checked_add_months(chrono::Months::new(months as u32))?
+    } else {
+        naive.checked_sub_months(chrono::Months::new(months.unsigned_abs()))?
+    };
+    from_naive_in(&dt.timezone(), &shifted, false)
+}
+
+///加减年数（2月29日在平年取2月28日）
+pub fn add_years<Tz: TimeZone>(dt: &DateTime<Tz>, years: i32) -> Option<DateTime<Tz>> {
+    add_months(dt, years.checked_mul(12)?)
+}
+
+///获取所在月份第一天的开始时间（1日 00:00:00）
+pub fn start_of_month<Tz: TimeZone>(dt: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+    let first = dt.date_naive().with_day(1)?;
+    from_naive_in(&dt.timezone(), &first.and_time(chrono::NaiveTime::MIN), false)
+}
+
+///获取所在月份最后一天的结束时间（23:59:59）
+pub fn end_of_month<Tz: TimeZone>(dt: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+    let first = dt.date_naive().with_day(1)?;
+    let last = first.checked_add_months(chrono::Months::new(1))?.pred_opt()?;
+    from_naive_in(&dt.timezone(), &last.and_hms_opt(23, 59, 59)?, true)
+}
+
+///获取所在周第一天的开始时间（00:00:00）
+///
+///first_day 为一周的第一天，中国/ISO 习惯为 chrono::Weekday::Mon，美国习惯为 chrono::Weekday::Sun
+pub fn start_of_week<Tz: TimeZone>(dt: &DateTime<Tz>, first_day: chrono::Weekday) -> Option<DateTime<Tz>> {
+    let date = dt.date_naive();
+    let offset = (7 + date.weekday().num_days_from_monday() - first_day.num_days_from_monday()) % 7;
+    let start = date - Duration::days(offset as i64);
+    from_naive_in(&dt.timezone(), &start.and_time(chrono::NaiveTime::MIN), false)
+}
+
 //========================================
 //时间差结构
 //========================================
@@ -328,6 +573,65 @@ impl TimeDiff {
     }
 }
 
+//========================================
+//日历差（年/月/日）
+//========================================
+
+///按日历计算的时间差（如 2 年 3 个月 5 天）
+///
+///与 TimeDiff 不同，月份长度和闰年按实际日历计算；
+///to 早于 from 时各字段均为负数（或零）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarDiff {
+    ///整年数
+    pub years: i32,
+    ///不足一年的整月数（0-11）
+    pub months: i32,
+    ///不足一月的天数
+    pub days: i32,
+}
+
+impl CalendarDiff {
+    ///人性化显示（如：2年3个月5天）
+    pub fn humanize(&self) -> String {
+        let sign = if self.years < 0 || self.months < 0 || self.days < 0 { "-" } else { "" };
+        format!("{}{}年{}个月{}天", sign, self.years.abs(), self.months.abs(), self.days.abs())
+    }
+}
+
+///计算两个时间之间的日历差（只比较日期部分，忽略时分秒）
+///
+///先取最大整月数，剩余部分计为天数；起始日在目标月不存在时按月末计，
+///如 1月31日 到 3月1日 为 1个月1天（1月31日 + 1个月 = 2月28日）
+pub fn calendar_diff(from: &LocalDateTime, to: &LocalDateTime) -> CalendarDiff {
+    let (start, end) = (from.date_naive(), to.date_naive());
+    if end < start {
+        let d = calendar_diff(to, from);
+        return CalendarDiff { years: -d.years, months: -d.months, days: -d.days };
+    }
+
+    let mut total_months = (end.year() - start.year()) * 12 + end.month() as i32 - start.month() as i32;
+    let add_months = |n: i32| start.checked_add_months(chrono::Months::new(n as u32));
+    //月末对齐后仍超过目标日期，说明最后一个月未满
+    if add_months(total_months).filter(|d| *d <= end).is_none() {
+        total_months -= 1;
+    }
+    let anchor = add_months(total_months).unwrap_or(start);
+
+    CalendarDiff {
+        years: total_months / 12,
+        months: total_months % 12,
+        days: (end - anchor).num_days() as i32,
+    }
+}
+
+///根据出生日期计算周岁
+///
+///2月29日出生者在平年的2月28日满周岁；出生日期晚于当前时间时返回 0
+pub fn age(birthdate: &LocalDateTime) -> u32 {
+    calendar_diff(birthdate, &now()).years.max(0) as u32
+}
+
 //========================================
 //时间比较
 //========================================
@@ -344,6 +648,12 @@ pub fn is_yesterday(dt: &LocalDateTime) -> bool {
     dt.date_naive() == yesterday.date_naive()
 }
 
+///判断是否是明天
+pub fn is_tomorrow(dt: &LocalDateTime) -> bool {
+    let tomorrow = now().date_naive().succ_opt();
+    Some(dt.date_naive()) == tomorrow
+}
+
 ///判断是否在指定时间之前
 pub fn is_before<Tz1: TimeZone, Tz2: TimeZone>(
     dt: &DateTime<Tz1>,
@@ -360,35 +670,78 @@ pub fn is_after<Tz1: TimeZone, Tz2: TimeZone>(
     dt.timestamp() > other.timestamp()
 }
 
+//========================================
+//日期信息
+//========================================
+
+///获取 ISO 周（ISO 年份, 周数）
+///
+///# 注意
+///年初/年末的日期可能属于相邻年份的周，
+///如 2024-12-30 返回 (2025, 1)，2021-01-03 返回 (2020, 53)
+pub fn iso_week<Tz: TimeZone>(dt: &DateTime<Tz>) -> (i32, u32) {
+    let week = dt.iso_week();
+    (week.year(), week.week())
+}
+
+///获取星期几（1-7，周一为 1）
+pub fn weekday<Tz: TimeZone>(dt: &DateTime<Tz>) -> u32 {
+    dt.weekday().number_from_monday()
+}
+
+///获取星期几的中文名称（如：星期一）
+pub fn weekday_name<Tz: TimeZone>(dt: &DateTime<Tz>) -> &'static str {
+    const NAMES: [&str; 7] = ["星期一", "星期二", "星期三", "星期四", "星期五", "星期六", "星期日"];
+    NAMES[dt.weekday().num_days_from_monday() as usize]
+}
+
+///获取一年中的第几天（1-366）
+pub fn day_of_year<Tz: TimeZone>(dt: &DateTime<Tz>) -> u32 {
+    dt.ordinal()
+}
+
+///判断是否是周末（周六或周日）
+pub fn is_weekend<Tz: TimeZone>(dt: &DateTime<Tz>) -> bool {
+    weekday(dt) >= 6
+}
+
 //========================================
 //便捷功能
 //========================================
 
 ///获取今天的开始时间（00:00:00）
+///
+///00:00 处于夏令时跳过的时间段时（部分时区在午夜切换），返回当天第一个存在的整分钟
 pub fn today_start() -> LocalDateTime {
-    let today = now();
-    Local.with_ymd_and_hms(
-        today.year(),
-        today.month(),
-        today.day(),
-        0, 0, 0
-    ).unwrap()
+    let start = now().date_naive().and_time(chrono::NaiveTime::MIN);
+    (0..=MAX_DST_GAP_MINUTES)
+        .find_map(|m| local_from_naive(&(start + Duration::minutes(m)), false))
+        .unwrap_or_else(now)
 }
 
 ///获取今天的结束时间（23:59:59）
+///
+///23:59:59 处于夏令时跳过的时间段时，返回当天最后一个存在的时间
 pub fn today_end() -> LocalDateTime {
-    let today = now();
-    Local.with_ymd_and_hms(
-        today.year(),
-        today.month(),
-        today.day(),
-        23, 59, 59
-    ).unwrap()
+    let end = now().date_naive().and_hms_opt(23, 59, 59).unwrap_or_default();
+    (0..=MAX_DST_GAP_MINUTES)
+        .find_map(|m| local_from_naive(&(end - Duration::minutes(m)), true))
+        .unwrap_or_else(now)
 }
 
-///人性化显示时间（如：刚刚、5分钟前、昨天）
+///夏令时跳过时间段的最大长度（分钟），用于查找最近的有效本地时间
+const MAX_DST_GAP_MINUTES: i64 = 24 * 60;
+
+///人性化显示时间（如：刚刚、5分钟前、昨天；未来时间显示为 5分钟后、明天）
 pub fn humanize(dt: &LocalDateTime) -> String {
     let now = now();
+
+    //按毫秒四舍五入到秒，避免调用耗时使“5分钟后”显示成“4分钟后”
+    let ahead_secs = (dt.timestamp_millis() - now.timestamp_millis() + 500).div_euclid(1000);
+    if ahead_secs > 0 {
+        return humanize_future(dt, TimeDiff::from_seconds(ahead_secs));
+    }
+
     let diff = diff(&now, dt);
 
     if diff.total_seconds < 60 {
@@ -406,5 +759,245 @@ pub fn humanize(dt: &LocalDateTime) -> String {
     }
 }
 
+///人性化显示未来时间
+fn humanize_future(dt: &LocalDateTime, diff: TimeDiff) -> String {
+    if diff.total_seconds < 60 {
+        "即将".to_string()
+    } else if diff.total_seconds < 3600 {
+        format!("{}分钟后", diff.total_minutes())
+    } else if diff.total_seconds < 86400 {
+        format!("{}小时后", diff.total_hours())
+    } else if is_tomorrow(dt) {
+        format!("明天 {}", format_time(dt))
+    } else if diff.days() < 7 {
+        format!("{}天后", diff.days())
+    } else {
+        format_date(dt)
+    }
+}
+
+//========================================
+//serde 序列化支持
+//========================================
+
+///时间戳（秒），序列化为整数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ::serde::Serialize, ::serde::Deserialize)]
+#[serde(transparent)]
+pub struct Timestamp(pub i64);
+
+impl Timestamp {
+    ///获取当前时间戳
+    pub fn now() -> Self {
+        Self(timestamp())
+    }
+
+    ///转换为本地时间
+    pub fn to_local(self) -> Option<LocalDateTime> {
+        from_timestamp(self.0)
+    }
+}
+
+impl<Tz: TimeZone> From<DateTime<Tz>> for Timestamp {
+    fn from(dt: DateTime<Tz>) -> Self {
+        Self(dt.timestamp())
+    }
+}
+
+///用于 `#[serde(with = "...")]` 的序列化辅助模块
+///
+///# 示例
+///```rust
+///#[derive(serde::Serialize, serde::Deserialize)]
+///struct Event {
+///    #[serde(with = "datetime::serde::ts_seconds")]
+///    created: datetime::LocalDateTime,
+///    #[serde(with = "datetime::serde::rfc3339")]
+///    updated: datetime::LocalDateTime,
+///}
+///```
+pub mod serde {
+    use ::serde::{Deserialize, Deserializer, Serializer};
+    use ::serde::de::Error;
+
+    ///以时间戳（秒）序列化 LocalDateTime
+    pub mod ts_seconds {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(dt: &super::super::LocalDateTime, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_i64(dt.timestamp())
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<super::super::LocalDateTime, D::Error> {
+            let ts = i64::deserialize(deserializer)?;
+            super::super::from_timestamp(ts).ok_or_else(|| D::Error::custom(format!("无效的时间戳: {}", ts)))
+        }
+    }
+
+    ///以 RFC 3339 字符串序列化 LocalDateTime
+    pub mod rfc3339 {
+        use super::*;
+
+        pub fn serialize<S: Serializer>(dt: &super::super::LocalDateTime, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&dt.to_rfc3339())
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<super::super::LocalDateTime, D::Error> {
+            let s = String::deserialize(deserializer)?;
+            chrono::DateTime::parse_from_rfc3339(&s)
+                .map(|dt| dt.with_timezone(&chrono::Local))
+                .map_err(|e| D::Error::custom(format!("解析时间失败: {}", e)))
+        }
+    }
+}
+
 //需要导入年月日方法
 use chrono::Datelike;
+
+//========================================
+//测试
+//========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::America::New_York;
+
+    fn utc(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    #[test]
+    fn truncate_to_buckets_utc() {
+        let dt = Utc.with_ymd_and_hms(2024, 5, 1, 12, 34, 56).unwrap();
+        assert_eq!(truncate_to(&dt, Duration::minutes(5)), Some(utc(2024, 5, 1, 12, 30)));
+        assert_eq!(round_to(&dt, Duration::minutes(15)), Some(utc(2024, 5, 1, 12, 30)));
+        assert_eq!(truncate_to(&dt, Duration::zero()), None);
+    }
+
+    #[test]
+    fn truncate_to_day_uses_local_midnight_across_dst_end() {
+        //2024-11-03 01:30 EST（第二次出现的 01:30，UTC 06:30），当天 00:00 仍为 EDT
+        let dt = utc(2024, 11, 3, 6, 30).with_timezone(&New_York);
+        let day = truncate_to_day(&dt).unwrap();
+        assert_eq!(day.with_timezone(&Utc), utc(2024, 11, 3, 4, 0));
+    }
+
+    #[test]
+    fn truncate_to_hour_keeps_offset_inside_fold() {
+        let est = utc(2024, 11, 3, 6, 30).with_timezone(&New_York);
+        assert_eq!(truncate_to_hour(&est).unwrap().with_timezone(&Utc), utc(2024, 11, 3, 6, 0));
+
+        let edt = utc(2024, 11, 3, 5, 30).with_timezone(&New_York);
+        assert_eq!(truncate_to_hour(&edt).unwrap().with_timezone(&Utc), utc(2024, 11, 3, 5, 0));
+    }
+
+    #[test]
+    fn add_business_days_skips_weekends() {
+        //2024-05-03 为周五
+        let friday = utc(2024, 5, 3, 9, 0);
+        assert_eq!(add_business_days(&friday, 1), Some(utc(2024, 5, 6, 9, 0)));
+        assert_eq!(add_business_days(&friday, 6), Some(utc(2024, 5, 13, 9, 0)));
+        assert_eq!(add_business_days(&friday, 0), Some(friday));
+
+        let saturday = utc(2024, 5, 4, 9, 0);
+        assert_eq!(add_business_days(&saturday, 1), Some(utc(2024, 5, 6, 9, 0)));
+        assert_eq!(add_business_days(&saturday, -1), Some(utc(2024, 5, 3, 9, 0)));
+
+        let monday = utc(2024, 5, 6, 9, 0);
+        assert_eq!(add_business_days(&monday, -1), Some(friday));
+    }
+
+    #[test]
+    fn add_business_days_keeps_wall_clock_across_dst() {
+        //2024-03-08 周五 09:00 EST，周末切换到夏令时后仍为周一 09:00 EDT
+        let friday = New_York.with_ymd_and_hms(2024, 3, 8, 9, 0, 0).unwrap();
+        let monday = add_business_days(&friday, 1).unwrap();
+        assert_eq!(monday.naive_local(), New_York.with_ymd_and_hms(2024, 3, 11, 9, 0, 0).unwrap().naive_local());
+        assert_eq!(monday.with_timezone(&Utc), utc(2024, 3, 11, 13, 0));
+    }
+
+    #[test]
+    fn truncate_into_dst_gap_returns_none() {
+        //2024-03-10 02:00-03:00 在纽约不存在，03:10 EDT 按 2 小时取整得到 02:00
+        let dt = utc(2024, 3, 10, 7, 10).with_timezone(&New_York);
+        assert_eq!(truncate_to(&dt, Duration::hours(2)), None);
+    }
+
+    #[test]
+    fn iso_week_and_day_info_at_year_boundaries() {
+        //2021-01-01 为周五，属于 2020 年第 53 周
+        let new_year = utc(2021, 1, 1, 12, 0);
+        assert_eq!(iso_week(&new_year), (2020, 53));
+        assert_eq!(weekday(&new_year), 5);
+        assert_eq!(day_of_year(&new_year), 1);
+        assert!(!is_weekend(&new_year));
+
+        let sunday = utc(2021, 1, 3, 12, 0);
+        assert_eq!(iso_week(&sunday), (2020, 53));
+        assert_eq!(weekday(&sunday), 7);
+        assert!(is_weekend(&sunday));
+        assert_eq!(iso_week(&utc(2021, 1, 4, 12, 0)), (2021, 1));
+
+        //闰年最后一天是第 366 天，且属于下一年第 1 周
+        let leap_end = utc(2024, 12, 31, 12, 0);
+        assert_eq!(day_of_year(&leap_end), 366);
+        assert_eq!(iso_week(&leap_end), (2025, 1));
+        assert!(is_weekend(&utc(2024, 12, 28, 12, 0)));
+    }
+
+    #[test]
+    fn from_naive_in_handles_dst_gap_and_fold() {
+        let naive = |m: u32, d: u32, h: u32, mi: u32| {
+            chrono::NaiveDate::from_ymd_opt(2021, m, d).unwrap().and_hms_opt(h, mi, 0).unwrap()
+        };
+
+        //2021-03-14 02:00-03:00 在纽约被跳过
+        assert_eq!(from_naive_in(&New_York, &naive(3, 14, 2, 30), false), None);
+        assert_eq!(from_naive_in(&New_York, &naive(3, 14, 2, 30), true), None);
+
+        //2021-11-07 01:30 出现两次：EDT（UTC 05:30）和 EST（UTC 06:30）
+        let earliest = from_naive_in(&New_York, &naive(11, 7, 1, 30), false).unwrap();
+        assert_eq!(earliest.with_timezone(&Utc), utc(2021, 11, 7, 5, 30));
+        let latest = from_naive_in(&New_York, &naive(11, 7, 1, 30), true).unwrap();
+        assert_eq!(latest.with_timezone(&Utc), utc(2021, 11, 7, 6, 30));
+    }
+
+    #[test]
+    fn add_months_clamps_to_month_end() {
+        assert_eq!(add_months(&utc(2023, 1, 31, 8, 0), 1), Some(utc(2023, 2, 28, 8, 0)));
+        assert_eq!(add_months(&utc(2024, 1, 31, 8, 0), 1), Some(utc(2024, 2, 29, 8, 0)));
+        assert_eq!(add_months(&utc(2024, 3, 31, 8, 0), -1), Some(utc(2024, 2, 29, 8, 0)));
+        assert_eq!(add_years(&utc(2024, 2, 29, 8, 0), 1), Some(utc(2025, 2, 28, 8, 0)));
+    }
+
+    #[test]
+    fn to_timezone_maps_utc_instant_to_wall_time() {
+        let instant = utc(2024, 7, 1, 12, 0);
+        let fmt = "%Y-%m-%d %H:%M %z";
+        assert_eq!(
+            to_timezone(&instant, "Asia/Shanghai").unwrap().format(fmt).to_string(),
+            "2024-07-01 20:00 +0800"
+        );
+        //7 月纽约为夏令时（UTC-4），1 月为标准时间（UTC-5）
+        assert_eq!(
+            format_in_tz(&instant, "America/New_York", fmt).unwrap(),
+            "2024-07-01 08:00 -0400"
+        );
+        assert_eq!(
+            format_in_tz(&utc(2024, 1, 1, 12, 0), "America/New_York", fmt).unwrap(),
+            "2024-01-01 07:00 -0500"
+        );
+
+        let err = to_timezone(&instant, "Mars/Olympus").unwrap_err();
+        assert!(err.contains("未知时区"), "{}", err);
+    }
+
+    #[test]
+    fn humanize_renders_future_and_past_minutes() {
+        let now = now();
+        assert_eq!(humanize(&add_minutes(&now, 5)), "5分钟后");
+        assert_eq!(humanize(&add_minutes(&now, -5)), "5分钟前");
+        assert_eq!(humanize(&add_hours(&now, 3)), "3小时后");
+        assert_eq!(humanize(&add_seconds(&now, 20)), "即将");
+    }
+}
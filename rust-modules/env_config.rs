@@ -2,12 +2,15 @@
 //!
 //!提供 .env 文件加载和环境变量读取功能。
 //!
-//!依赖：dotenvy（使用时查询最新版本：https://crates.io/crates/dotenvy）
+//!依赖：
+//!- dotenvy（使用时查询最新版本：https://crates.io/crates/dotenvy）
+//!- serde（使用时查询最新版本：https://crates.io/crates/serde，from_env() 反序列化为结构体使用）
 //!
 //!# Cargo.toml 配置示例
 //!```toml
 //![dependencies]
 //!dotenvy = "0.15"  # https://crates.io/crates/dotenvy
+//!serde = { version = "1", features = ["derive"] }  # https://crates.io/crates/serde
 //!```
 //!
 //!# 快速开始
@@ -137,10 +140,75 @@ pub fn get_float_or(key: &str, default: f64) -> f64 {
     get_float(key).unwrap_or(default)
 }
 
+///读取时长类型环境变量
+///
+///支持的单位：ms, s, m, h, d，可组合（如 "1h30m"）；纯数字按秒处理
+pub fn get_duration(key: &str) -> Option<std::time::Duration> {
+    let value = std::env::var(key).ok()?;
+    parse_duration(&value)
+}
+
+///读取必需的时长类型环境变量
+pub fn require_duration(key: &str) -> Result<std::time::Duration, String> {
+    let value = require(key)?;
+    parse_duration(&value)
+        .ok_or_else(|| format!("环境变量 {} 不是有效的时长: {}", key, value))
+}
+
+///读取时长类型环境变量，不存在返回默认值
+pub fn get_duration_or(key: &str, default: std::time::Duration) -> std::time::Duration {
+    get_duration(key).unwrap_or(default)
+}
+
+///列表默认分隔符
+pub const DEFAULT_LIST_DELIMITER: char = ',';
+
+///读取逗号分隔的列表（如 "a.com, b.com"）
+///
+///每个元素去除首尾空白，空元素被丢弃（" a , b ," → ["a", "b"]）
+pub fn get_list(key: &str) -> Option<Vec<String>> {
+    get_list_with(key, DEFAULT_LIST_DELIMITER)
+}
+
+///读取指定分隔符分隔的列表（规则同 get_list）
+pub fn get_list_with(key: &str, delimiter: char) -> Option<Vec<String>> {
+    let value = std::env::var(key).ok()?;
+    Some(split_list(&value, delimiter))
+}
+
+///读取逗号分隔的列表，不存在返回默认值
+pub fn get_list_or(key: &str, default: Vec<String>) -> Vec<String> {
+    get_list(key).unwrap_or(default)
+}
+
+///读取逗号分隔的整数列表
+///
+///任一元素不是有效整数时返回 None
+pub fn get_int_list(key: &str) -> Option<Vec<i64>> {
+    get_int_list_with(key, DEFAULT_LIST_DELIMITER)
+}
+
+///读取指定分隔符分隔的整数列表（规则同 get_int_list）
+pub fn get_int_list_with(key: &str, delimiter: char) -> Option<Vec<i64>> {
+    get_list_with(key, delimiter)?
+        .iter()
+        .map(|item| item.parse().ok())
+        .collect()
+}
+
 //========================================
 //辅助函数
 //========================================
 
+///拆分列表：去除元素首尾空白并丢弃空元素
+fn split_list(value: &str, delimiter: char) -> Vec<String> {
+    value.split(delimiter)
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 ///解析布尔值
 fn parse_bool(value: &str) -> Option<bool> {
     match value.to_lowercase().as_str() {
@@ -150,6 +218,43 @@ fn parse_bool(value: &str) -> Option<bool> {
     }
 }
 
+///解析时长（如 "30s"、"1500ms"、"1h30m"、"1.5h"，纯数字按秒处理）
+fn parse_duration(value: &str) -> Option<std::time::Duration> {
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+    if let Ok(secs) = value.parse::<f64>() {
+        return std::time::Duration::try_from_secs_f64(secs).ok();
+    }
+
+    let mut total = 0.0;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let num_end = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let num: f64 = rest[..num_end].parse().ok()?;
+        rest = &rest[num_end..];
+
+        let unit_end = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let factor = match rest[..unit_end].trim() {
+            "ms" => 0.001,
+            "s" => 1.0,
+            "m" => 60.0,
+            "h" => 3600.0,
+            "d" => 86400.0,
+            _ => return None,
+        };
+        rest = &rest[unit_end..];
+        total += num * factor;
+    }
+
+    std::time::Duration::try_from_secs_f64(total).ok()
+}
+
 ///设置环境变量
 pub fn set(key: &str, value: &str) {
     std::env::set_var(key, value);
@@ -165,6 +270,56 @@ pub fn exists(key: &str) -> bool {
     std::env::var(key).is_ok()
 }
 
+//========================================
+//临时修改（测试用）
+//========================================
+
+///临时设置/删除环境变量，离开作用域时恢复原值
+///
+///overrides 中 Some(值) 表示设置，None 表示删除
+///
+///# 注意
+///环境变量是进程级全局状态，并行运行的测试仍会互相影响，
+///修改同一变量的测试需串行执行（如 `cargo test -- --test-threads=1`）
+///
+///# 示例
+///```rust
+///{
+///    let _guard = env_config::guard(&[("PORT", Some("9000")), ("DEBUG", None)]);
+///    assert_eq!(env_config::get_int("PORT"), Some(9000));
+///}
+/////此处 PORT 和 DEBUG 已恢复为原值
+///```
+pub fn guard(overrides: &[(&str, Option<&str>)]) -> EnvGuard {
+    let mut saved = Vec::with_capacity(overrides.len());
+    for (key, value) in overrides {
+        saved.push((key.to_string(), std::env::var_os(key)));
+        match value {
+            Some(v) => set(key, v),
+            None => remove(key),
+        }
+    }
+    EnvGuard { saved }
+}
+
+///环境变量守卫（由 guard() 创建），Drop 时恢复修改前的值
+pub struct EnvGuard {
+    ///被修改变量的原值（None 表示原本不存在）
+    saved: Vec<(String, Option<std::ffi::OsString>)>,
+}
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        //逆序恢复，同一变量被多次修改时以最早的原值为准
+        for (key, value) in self.saved.iter().rev() {
+            match value {
+                Some(v) => std::env::set_var(key, v),
+                None => std::env::remove_var(key),
+            }
+        }
+    }
+}
+
 //========================================
 //EnvReader（带前缀支持）
 //========================================
@@ -173,6 +328,8 @@ pub fn exists(key: &str) -> bool {
 pub struct EnvReader {
     ///变量前缀
     prefix: String,
+    ///列表分隔符
+    list_delimiter: char,
 }
 
 impl EnvReader {
@@ -180,6 +337,7 @@ impl EnvReader {
     pub fn new() -> Self {
         Self {
             prefix: String::new(),
+            list_delimiter: DEFAULT_LIST_DELIMITER,
         }
     }
 
@@ -195,6 +353,12 @@ impl EnvReader {
         self
     }
 
+    ///设置列表分隔符（默认逗号）
+    pub fn list_delimiter(mut self, delimiter: char) -> Self {
+        self.list_delimiter = delimiter;
+        self
+    }
+
     ///加载 .env 文件
     pub fn load_dotenv(self) -> Self {
         load_optional();
@@ -257,10 +421,56 @@ impl EnvReader {
         get_float_or(&self.full_key(key), default)
     }
 
+    ///读取时长类型（如 "30s"、"1500ms"）
+    pub fn get_duration(&self, key: &str) -> Option<std::time::Duration> {
+        get_duration(&self.full_key(key))
+    }
+
+    ///读取必需的时长类型
+    pub fn require_duration(&self, key: &str) -> Result<std::time::Duration, String> {
+        require_duration(&self.full_key(key))
+    }
+
+    ///读取时长类型，不存在返回默认值
+    pub fn get_duration_or(&self, key: &str, default: std::time::Duration) -> std::time::Duration {
+        get_duration_or(&self.full_key(key), default)
+    }
+
+    ///读取列表（按 list_delimiter 拆分，规则同 get_list）
+    pub fn get_list(&self, key: &str) -> Option<Vec<String>> {
+        get_list_with(&self.full_key(key), self.list_delimiter)
+    }
+
+    ///读取列表，不存在返回默认值
+    pub fn get_list_or(&self, key: &str, default: Vec<String>) -> Vec<String> {
+        self.get_list(key).unwrap_or(default)
+    }
+
+    ///读取整数列表，任一元素不是有效整数时返回 None
+    pub fn get_int_list(&self, key: &str) -> Option<Vec<i64>> {
+        get_int_list_with(&self.full_key(key), self.list_delimiter)
+    }
+
     ///检查变量是否存在
     pub fn exists(&self, key: &str) -> bool {
         exists(&self.full_key(key))
     }
+
+    ///将带前缀的环境变量反序列化为结构体（规则同 from_env，如前缀 "APP_" 时 APP_PORT 对应 port 字段）
+    pub fn deserialize<T: serde::de::DeserializeOwned>(&self) -> Result<T, String> {
+        deserialize_vars(std::env::vars_os(), &self.prefix)
+    }
+
+    ///临时设置/删除带前缀的环境变量，返回的守卫 Drop 时恢复原值
+    pub fn scoped(&self, overrides: &[(&str, Option<&str>)]) -> EnvGuard {
+        let keys: Vec<String> = overrides.iter().map(|(k, _)| self.full_key(k)).collect();
+        let full: Vec<(&str, Option<&str>)> = keys
+            .iter()
+            .zip(overrides)
+            .map(|(k, (_, v))| (k.as_str(), *v))
+            .collect();
+        guard(&full)
+    }
 }
 
 impl Default for EnvReader {
@@ -284,3 +494,273 @@ pub fn get_all_with_prefix(prefix: &str) -> Vec<(String, String)> {
 pub fn get_all() -> Vec<(String, String)> {
     std::env::vars().collect()
 }
+
+//========================================
+//反序列化为结构体
+//========================================
+
+///将环境变量反序列化为结构体
+///
+///变量名转为小写后作为字段名（PORT → port），结构体中没有的变量会被忽略。
+///字段支持字符串、整数、浮点数、布尔值（规则同 get_bool）、Option 和单元枚举；
+///值不是有效 UTF-8 的变量仅在对应字段被读取时报错。需要按前缀读取时使用 EnvReader::deserialize()
+///
+///# 示例
+///```rust
+///#[derive(serde::Deserialize)]
+///struct Config {
+///    database_url: String,
+///    port: u16,
+///    debug: Option<bool>,
+///}
+///
+///let config: Config = env_config::from_env().unwrap();
+///```
+pub fn from_env<T: serde::de::DeserializeOwned>() -> Result<T, String> {
+    deserialize_vars(std::env::vars_os(), "")
+}
+
+///按前缀筛选变量并反序列化
+///
+///变量名不是有效 UTF-8 时无法对应字段，直接跳过；值不是有效 UTF-8 时只在对应字段被读取时报错
+fn deserialize_vars<T, I>(vars: I, prefix: &str) -> Result<T, String>
+where
+    T: serde::de::DeserializeOwned,
+    I: IntoIterator<Item = (std::ffi::OsString, std::ffi::OsString)>,
+{
+    let entries: Vec<(String, EnvValue)> = vars
+        .into_iter()
+        .filter_map(|(key, value)| {
+            let key = key.into_string().ok()?;
+            let field = key.strip_prefix(prefix)?.to_lowercase();
+            if field.is_empty() {
+                return None;
+            }
+            Some((field, EnvValue { key, value: value.into_string().ok() }))
+        })
+        .collect();
+
+    let deserializer = serde::de::value::MapDeserializer::new(entries.into_iter());
+    T::deserialize(deserializer).map_err(|e: serde::de::value::Error| {
+        //缺少字段时给出对应的环境变量名
+        let msg = e.to_string();
+        match msg.strip_prefix("missing field `").and_then(|rest| rest.strip_suffix('`')) {
+            Some(field) => format!("环境变量 {}{} 未设置（字段 {}）", prefix, field.to_uppercase(), field),
+            None => format!("环境变量反序列化失败: {}", msg),
+        }
+    })
+}
+
+///单个环境变量的值，按字段类型解析
+struct EnvValue {
+    ///完整变量名（用于错误信息）
+    key: String,
+    ///原始值（不是有效 UTF-8 时为 None）
+    value: Option<String>,
+}
+
+impl EnvValue {
+    ///获取值，不是有效 UTF-8 时返回带变量名的错误
+    fn text(&self) -> Result<&str, serde::de::value::Error> {
+        self.value.as_deref().ok_or_else(|| {
+            serde::de::Error::custom(format!("环境变量 {} 的值不是有效的 UTF-8", self.key))
+        })
+    }
+
+    ///获取值的所有权（规则同 text）
+    fn into_text(self) -> Result<String, serde::de::value::Error> {
+        self.text()?;
+        Ok(self.value.unwrap_or_default())
+    }
+
+    ///解析为指定类型，失败时返回带变量名的错误
+    fn parse<T: std::str::FromStr>(&self, kind: &str) -> Result<T, serde::de::value::Error> {
+        self.text()?.trim().parse().map_err(|_| self.invalid(kind))
+    }
+
+    ///构造类型不匹配错误
+    fn invalid(&self, kind: &str) -> serde::de::value::Error {
+        let value = self.value.as_deref().unwrap_or_default();
+        serde::de::Error::custom(format!("环境变量 {} 不是有效的{}: {}", self.key, kind, value))
+    }
+}
+
+impl<'de> serde::de::IntoDeserializer<'de, serde::de::value::Error> for EnvValue {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> serde::Deserializer<'de> for EnvValue {
+    type Error = serde::de::value::Error;
+
+    fn deserialize_any<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_string(self.into_text()?)
+    }
+
+    fn deserialize_bool<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let value = parse_bool(self.text()?.trim()).ok_or_else(|| self.invalid("布尔值"))?;
+        visitor.visit_bool(value)
+    }
+
+    fn deserialize_i8<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_i8(self.parse("整数")?)
+    }
+
+    fn deserialize_i16<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_i16(self.parse("整数")?)
+    }
+
+    fn deserialize_i32<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_i32(self.parse("整数")?)
+    }
+
+    fn deserialize_i64<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_i64(self.parse("整数")?)
+    }
+
+    fn deserialize_u8<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u8(self.parse("整数")?)
+    }
+
+    fn deserialize_u16<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u16(self.parse("整数")?)
+    }
+
+    fn deserialize_u32<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u32(self.parse("整数")?)
+    }
+
+    fn deserialize_u64<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_u64(self.parse("整数")?)
+    }
+
+    fn deserialize_f32<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_f32(self.parse("浮点数")?)
+    }
+
+    fn deserialize_f64<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_f64(self.parse("浮点数")?)
+    }
+
+    fn deserialize_char<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_char(self.parse("字符")?)
+    }
+
+    ///变量存在即为 Some，不存在的字段由 serde 处理为 None
+    fn deserialize_option<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: serde::de::Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    ///单元枚举按变体名匹配（如 LOG_LEVEL=Debug）
+    fn deserialize_enum<V: serde::de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        use serde::de::IntoDeserializer;
+        visitor.visit_enum(self.into_text()?.into_deserializer())
+    }
+
+    ///结构体中没有的变量不读取其值，避免无关变量的非 UTF-8 值导致失败
+    fn deserialize_ignored_any<V: serde::de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        i128 u128 str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier
+    }
+}
+
+//========================================
+//测试
+//========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, serde::Deserialize, PartialEq)]
+    struct AppConfig {
+        name: String,
+        port: u16,
+        debug: Option<bool>,
+    }
+
+    ///构造环境变量列表
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(std::ffi::OsString, std::ffi::OsString)> {
+        pairs.iter().map(|(k, v)| (k.into(), v.into())).collect()
+    }
+
+    #[test]
+    fn deserialize_vars_maps_prefixed_names_to_fields() {
+        let config: AppConfig = deserialize_vars(
+            vars(&[("APP_NAME", "demo"), ("APP_PORT", "8080"), ("PORT", "1"), ("OTHER_NAME", "x")]),
+            "APP_",
+        )
+        .unwrap();
+        assert_eq!(config, AppConfig { name: "demo".to_string(), port: 8080, debug: None });
+    }
+
+    #[test]
+    fn deserialize_vars_names_missing_and_invalid_variables() {
+        let err = deserialize_vars::<AppConfig, _>(vars(&[("APP_NAME", "demo")]), "APP_").unwrap_err();
+        assert!(err.contains("APP_PORT"), "{}", err);
+
+        let err = deserialize_vars::<AppConfig, _>(vars(&[("APP_NAME", "demo"), ("APP_PORT", "http")]), "APP_")
+            .unwrap_err();
+        assert!(err.contains("APP_PORT") && err.contains("http"), "{}", err);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn deserialize_vars_reports_non_utf8_only_when_used() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let invalid = std::ffi::OsString::from_vec(vec![0x66, 0xff]);
+        let mut env = vars(&[("APP_NAME", "demo"), ("APP_PORT", "8080")]);
+        env.push(("APP_UNUSED".into(), invalid.clone()));
+        env.push((invalid.clone(), "ignored".into()));
+        assert!(deserialize_vars::<AppConfig, _>(env, "APP_").is_ok());
+
+        let mut env = vars(&[("APP_PORT", "8080")]);
+        env.push(("APP_NAME".into(), invalid));
+        let err = deserialize_vars::<AppConfig, _>(env, "APP_").unwrap_err();
+        assert!(err.contains("APP_NAME") && err.contains("UTF-8"), "{}", err);
+    }
+
+    #[test]
+    fn split_list_trims_and_drops_empty_items() {
+        assert_eq!(split_list(" a , b ,", ','), ["a", "b"]);
+        assert_eq!(split_list("a;b; ;c", ';'), ["a", "b", "c"]);
+        assert!(split_list(" , ,", ',').is_empty());
+    }
+
+    #[test]
+    fn get_list_reads_environment() {
+        //变量名带测试前缀，避免与并行运行的其他测试冲突
+        let _guard = guard(&[
+            ("ENV_CONFIG_TEST_HOSTS", Some(" a , b ,")),
+            ("ENV_CONFIG_TEST_PORTS", Some("80, 443 ,")),
+            ("ENV_CONFIG_TEST_BAD_PORTS", Some("80,http")),
+            ("ENV_CONFIG_TEST_MISSING", None),
+        ]);
+
+        assert_eq!(get_list("ENV_CONFIG_TEST_HOSTS"), Some(vec!["a".to_string(), "b".to_string()]));
+        assert_eq!(get_list_or("ENV_CONFIG_TEST_MISSING", vec!["d".to_string()]), ["d"]);
+        assert_eq!(get_int_list("ENV_CONFIG_TEST_PORTS"), Some(vec![80, 443]));
+        assert_eq!(get_int_list("ENV_CONFIG_TEST_BAD_PORTS"), None);
+        assert_eq!(get_int_list("ENV_CONFIG_TEST_MISSING"), None);
+
+        let reader = EnvReader::new().prefix("ENV_CONFIG_TEST_").list_delimiter(' ');
+        assert_eq!(reader.get_list("PORTS"), Some(vec!["80,".to_string(), "443".to_string(), ",".to_string()]));
+    }
+}
@@ -8,7 +8,6 @@
 //!```toml
 //![dependencies]
 //!notify = "8"        # https://crates.io/crates/notify
-//!notify-debouncer-mini = "0.5"  # 可选，防抖动支持
 //!```
 //!
 //!# 快速开始
@@ -34,6 +33,7 @@
 //!            file_watcher::EventKind::Create => println!("创建: {:?}", event.path),
 //!            file_watcher::EventKind::Modify => println!("修改: {:?}", event.path),
 //!            file_watcher::EventKind::Delete => println!("删除: {:?}", event.path),
+//!            file_watcher::EventKind::Rename => println!("重命名: {:?} -> {:?}", event.from_path, event.path),
 //!            _ => {}
 //!        }
 //!    }).unwrap();
@@ -51,6 +51,7 @@
 //!        .recursive(true)
 //!        .debounce(Duration::from_millis(500))
 //!        .extensions(&["rs", "toml"])
+//!        .only_kinds(&[file_watcher::EventKind::Modify])
 //!        .on_event(|event| {
 //!            println!("{:?}", event);
 //!        })
@@ -195,6 +196,8 @@ where
     extensions: Option<Vec<String>>,
     ///文件名模式过滤
     pattern: Option<String>,
+    ///事件类型过滤
+    kinds: Option<Vec<EventKind>>,
     ///事件回调
     callback: Option<F>,
 }
@@ -211,6 +214,7 @@ where
             debounce: None,
             extensions: None,
             pattern: None,
+            kinds: None,
             callback: None,
         }
     }
@@ -236,6 +240,9 @@ where
     }
 
     ///设置防抖动延迟
+    ///
+    ///同一路径的事件在静默 duration 后才触发一次回调，期间的连续事件合并为一个：
+    ///创建后修改仍报告为创建，删除后重新创建（编辑器原子保存）报告为修改，其余以最后一次有效类型为准
     pub fn debounce(mut self, duration: std::time::Duration) -> Self {
         self.debounce = Some(duration);
         self
@@ -253,6 +260,12 @@ where
         self
     }
 
+    ///设置事件类型过滤（只处理指定类型的事件）
+    pub fn only_kinds(mut self, kinds: &[EventKind]) -> Self {
+        self.kinds = Some(kinds.to_vec());
+        self
+    }
+
     ///设置事件回调
     pub fn on_event(mut self, callback: F) -> Self {
         self.callback = Some(callback);
@@ -287,54 +300,64 @@ where
         }
 
         //事件处理循环
+        let mut dispatcher = EventDispatcher::new(
+            EventFilter {
+                extensions: self.extensions,
+                pattern: self.pattern,
+                kinds: self.kinds,
+            },
+            self.debounce,
+        );
         loop {
-            match rx.recv() {
-                Ok(event) => {
-                    for path in event.paths {
-                        //扩展名过滤
-                        if let Some(ref exts) = self.extensions {
-                            if let Some(ext) = path.extension() {
-                                let ext_str = ext.to_string_lossy().to_lowercase();
-                                if !exts.iter().any(|e| e.to_lowercase() == ext_str) {
-                                    continue;
-                                }
-                            } else {
-                                continue;
-                            }
-                        }
-
-                        //模式过滤
-                        if let Some(ref pattern) = self.pattern {
-                            if let Some(name) = path.file_name() {
-                                if !match_pattern(pattern, &name.to_string_lossy()) {
-                                    continue;
-                                }
-                            }
-                        }
-
-                        let kind = convert_event_kind(&event.kind);
-                        let file_event = FileEvent::new(kind, path);
-                        callback(file_event);
-                    }
-                }
-                Err(_) => break,
+            match rx.recv_timeout(dispatcher.poll_interval()) {
+                Ok(event) => dispatcher.handle(event, &callback),
+                Err(mpsc::RecvTimeoutError::Timeout) => {}
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
             }
+            dispatcher.flush_due(&callback);
         }
+        dispatcher.flush_all(&callback);
 
         Ok(())
     }
 
     ///启动监控（非阻塞，返回句柄）
-    pub fn watch_async(self) -> Result<WatchHandle, String> {
+    pub fn watch_async(mut self) -> Result<WatchHandle, String> {
+        let callback = self.callback.take().ok_or("未设置回调函数")?;
+        self.spawn(callback)
+    }
+
+    ///启动监控（非阻塞），事件通过通道返回而不是回调
+    ///
+    ///便于在自己的循环中与其他通道一起处理事件，回调上的 `Send + 'static` 约束也不再需要。
+    ///无需回调时可使用 ChannelWatcher 类型别名以省去泛型参数
+    pub fn watch_channel(self) -> Result<(WatchHandle, mpsc::Receiver<FileEvent>), String> {
+        let (event_tx, event_rx) = mpsc::channel();
+        let handle = self.spawn(move |event| {
+            let _ = event_tx.send(event);
+        })?;
+        Ok((handle, event_rx))
+    }
+
+    ///在后台线程中启动监控，过滤后的事件交给 callback 处理
+    fn spawn<C>(self, callback: C) -> Result<WatchHandle, String>
+    where
+        C: Fn(FileEvent) + Send + 'static,
+    {
         if self.paths.is_empty() {
             return Err("未指定监控路径".to_string());
         }
 
-        let callback = self.callback.ok_or("未设置回调函数")?;
-        let paths = self.paths.clone();
+        let paths = self.paths;
         let recursive = self.recursive;
-        let extensions = self.extensions.clone();
-        let pattern = self.pattern.clone();
+        let mut dispatcher = EventDispatcher::new(
+            EventFilter {
+                extensions: self.extensions,
+                pattern: self.pattern,
+                kinds: self.kinds,
+            },
+            self.debounce,
+        );
 
         let (stop_tx, stop_rx) = mpsc::channel();
 
@@ -369,39 +392,14 @@ where
                 }
 
                 //处理事件（带超时）
-                match rx.recv_timeout(std::time::Duration::from_millis(100)) {
-                    Ok(event) => {
-                        for path in event.paths {
-                            //扩展名过滤
-                            if let Some(ref exts) = extensions {
-                                if let Some(ext) = path.extension() {
-                                    let ext_str = ext.to_string_lossy().to_lowercase();
-                                    if !exts.iter().any(|e| e.to_lowercase() == ext_str) {
-                                        continue;
-                                    }
-                                } else {
-                                    continue;
-                                }
-                            }
-
-                            //模式过滤
-                            if let Some(ref pat) = pattern {
-                                if let Some(name) = path.file_name() {
-                                    if !match_pattern(pat, &name.to_string_lossy()) {
-                                        continue;
-                                    }
-                                }
-                            }
-
-                            let kind = convert_event_kind(&event.kind);
-                            let file_event = FileEvent::new(kind, path);
-                            callback(file_event);
-                        }
-                    }
-                    Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                match rx.recv_timeout(dispatcher.poll_interval()) {
+                    Ok(event) => dispatcher.handle(event, &callback),
+                    Err(mpsc::RecvTimeoutError::Timeout) => {}
                     Err(mpsc::RecvTimeoutError::Disconnected) => break,
                 }
+                dispatcher.flush_due(&callback);
             }
+            dispatcher.flush_all(&callback);
         });
 
         Ok(WatchHandle {
@@ -420,6 +418,20 @@ where
     }
 }
 
+///仅使用 watch_channel() 时的监控器类型（无需指定回调类型）
+///
+///# 示例
+///```rust
+///let (handle, rx) = file_watcher::ChannelWatcher::new()
+///    .path("./src")
+///    .watch_channel()
+///    .unwrap();
+///for event in rx {
+///    println!("{:?}", event);
+///}
+///```
+pub type ChannelWatcher = FileWatcher<fn(FileEvent)>;
+
 //========================================
 //监控句柄
 //========================================
@@ -455,6 +467,300 @@ impl Drop for WatchHandle {
 //辅助函数
 //========================================
 
+///事件轮询间隔
+const POLL_INTERVAL_MS: u64 = 100;
+
+///事件分发器：转换、过滤后直接回调，或经防抖合并后回调
+struct EventDispatcher {
+    ///事件过滤条件
+    filter: EventFilter,
+    ///重命名配对
+    renames: RenameTracker,
+    ///防抖动缓冲（未设置防抖时为 None）
+    debouncer: Option<Debouncer>,
+}
+
+impl EventDispatcher {
+    fn new(filter: EventFilter, debounce: Option<std::time::Duration>) -> Self {
+        Self {
+            filter,
+            renames: RenameTracker::new(),
+            debouncer: debounce.map(Debouncer::new),
+        }
+    }
+
+    ///处理一个 notify 事件
+    fn handle<C: Fn(FileEvent)>(&mut self, event: notify::Event, callback: &C) {
+        for file_event in self.renames.convert(event) {
+            self.emit(file_event, callback);
+        }
+    }
+
+    ///过滤事件，通过的交给防抖缓冲或直接回调
+    fn emit<C: Fn(FileEvent)>(&mut self, event: FileEvent, callback: &C) {
+        if !self.filter.matches(&event) {
+            return;
+        }
+        match self.debouncer {
+            Some(ref mut debouncer) => debouncer.push(event),
+            None => callback(event),
+        }
+    }
+
+    ///触发已到期的事件（未配对的重命名、已静默足够久的防抖事件）
+    fn flush_due<C: Fn(FileEvent)>(&mut self, callback: &C) {
+        let now = std::time::Instant::now();
+        if let Some(event) = self.renames.take_expired(now) {
+            self.emit(event, callback);
+        }
+        if let Some(ref mut debouncer) = self.debouncer {
+            for event in debouncer.take_due(now) {
+                callback(event);
+            }
+        }
+    }
+
+    ///触发所有缓冲中的事件（监控结束时调用）
+    fn flush_all<C: Fn(FileEvent)>(&mut self, callback: &C) {
+        if let Some(event) = self.renames.take_pending() {
+            self.emit(event, callback);
+        }
+        if let Some(ref mut debouncer) = self.debouncer {
+            for (event, _) in debouncer.pending.drain(..) {
+                callback(event);
+            }
+        }
+    }
+
+    ///下一次等待事件的超时时间（有待触发事件时缩短到其到期时刻）
+    fn poll_interval(&self) -> std::time::Duration {
+        let poll = std::time::Duration::from_millis(POLL_INTERVAL_MS);
+        let due = self.debouncer.as_ref()
+            .and_then(|d| d.next_due())
+            .into_iter()
+            .chain(self.renames.next_due())
+            .min();
+        match due {
+            Some(due) => due.saturating_duration_since(std::time::Instant::now()).min(poll),
+            None => poll,
+        }
+    }
+}
+
+///防抖动缓冲，按路径合并事件
+struct Debouncer {
+    ///静默时间
+    delay: std::time::Duration,
+    ///待触发事件及其最后一次变化时间（按首次出现顺序）
+    pending: Vec<(FileEvent, std::time::Instant)>,
+}
+
+impl Debouncer {
+    fn new(delay: std::time::Duration) -> Self {
+        Self {
+            delay,
+            pending: Vec::new(),
+        }
+    }
+
+    ///加入事件，同一路径已有待触发事件时合并
+    fn push(&mut self, event: FileEvent) {
+        let now = std::time::Instant::now();
+        match self.pending.iter_mut().find(|(e, _)| e.path == event.path) {
+            Some((pending, last)) => {
+                pending.kind = merge_kind(&pending.kind, event.kind);
+                if event.from_path.is_some() {
+                    pending.from_path = event.from_path;
+                }
+                *last = now;
+            }
+            None => self.pending.push((event, now)),
+        }
+    }
+
+    ///取出已静默 delay 的事件
+    fn take_due(&mut self, now: std::time::Instant) -> Vec<FileEvent> {
+        let mut due = Vec::new();
+        let mut i = 0;
+        while i < self.pending.len() {
+            if now.saturating_duration_since(self.pending[i].1) >= self.delay {
+                due.push(self.pending.remove(i).0);
+            } else {
+                i += 1;
+            }
+        }
+        due
+    }
+
+    ///最早的到期时刻
+    fn next_due(&self) -> Option<std::time::Instant> {
+        self.pending.iter().map(|(_, last)| *last + self.delay).min()
+    }
+}
+
+///合并同一路径的连续事件类型
+fn merge_kind(old: &EventKind, new: EventKind) -> EventKind {
+    match (old, new) {
+        (EventKind::Create, EventKind::Modify) => EventKind::Create,
+        (EventKind::Delete, EventKind::Create) => EventKind::Modify,
+        (old, EventKind::Other) => old.clone(),
+        (_, new) => new,
+    }
+}
+
+///事件过滤条件
+struct EventFilter {
+    ///文件扩展名过滤
+    extensions: Option<Vec<String>>,
+    ///文件名模式过滤
+    pattern: Option<String>,
+    ///事件类型过滤
+    kinds: Option<Vec<EventKind>>,
+}
+
+impl EventFilter {
+    ///判断事件是否满足过滤条件
+    fn matches(&self, event: &FileEvent) -> bool {
+        //事件类型过滤
+        if let Some(ref kinds) = self.kinds {
+            if !kinds.contains(&event.kind) {
+                return false;
+            }
+        }
+        //重命名时原路径或新路径任一满足即可（如 .tmp 重命名为 .rs）
+        self.matches_path(&event.path)
+            || event.from_path.as_deref().is_some_and(|from| self.matches_path(from))
+    }
+
+    ///判断路径是否满足扩展名和模式过滤
+    fn matches_path(&self, path: &std::path::Path) -> bool {
+        //扩展名过滤
+        if let Some(ref exts) = self.extensions {
+            match path.extension() {
+                Some(ext) => {
+                    let ext_str = ext.to_string_lossy().to_lowercase();
+                    if !exts.iter().any(|e| e.to_lowercase() == ext_str) {
+                        return false;
+                    }
+                }
+                None => return false,
+            }
+        }
+
+        //模式过滤
+        if let Some(ref pattern) = self.pattern {
+            if let Some(name) = path.file_name() {
+                if !match_pattern(pattern, &name.to_string_lossy()) {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+///重命名配对等待时间
+const RENAME_WINDOW_MS: u64 = 500;
+
+///重命名配对：部分平台把重命名拆成原路径和新路径两个事件报告，在此合并为一个 Rename 事件
+///
+///配对失败时，只有原路径的按删除处理（移出监控范围），只有新路径的按创建处理（移入监控范围）
+struct RenameTracker {
+    ///等待配对的原路径、tracker 及报告时间
+    pending_from: Option<(std::path::PathBuf, Option<usize>, std::time::Instant)>,
+    ///最近一次配对成功的重命名（inotify 随后还会报告一个 Both 事件，需忽略）
+    last_paired: Option<(std::path::PathBuf, std::path::PathBuf)>,
+}
+
+impl RenameTracker {
+    fn new() -> Self {
+        Self {
+            pending_from: None,
+            last_paired: None,
+        }
+    }
+
+    ///转换 notify 事件
+    fn convert(&mut self, event: notify::Event) -> Vec<FileEvent> {
+        use notify::event::{ModifyKind, RenameMode};
+
+        let tracker = event.attrs.tracker();
+        match event.kind {
+            notify::EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if event.paths.len() == 2 => {
+                let mut paths = event.paths.into_iter();
+                let (from, to) = (paths.next().unwrap_or_default(), paths.next().unwrap_or_default());
+                if self.last_paired.take() == Some((from.clone(), to.clone())) {
+                    return Vec::new();
+                }
+                vec![FileEvent::rename(from, to)]
+            }
+            notify::EventKind::Modify(ModifyKind::Name(RenameMode::From)) => event.paths.into_iter()
+                .flat_map(|path| self.start_from(path, tracker))
+                .collect(),
+            notify::EventKind::Modify(ModifyKind::Name(RenameMode::To)) => event.paths.into_iter()
+                .map(|path| self.pair_to(path, tracker))
+                .collect(),
+            //macOS 等平台不区分两侧，按路径是否仍存在判断
+            notify::EventKind::Modify(ModifyKind::Name(_)) => event.paths.into_iter()
+                .flat_map(|path| {
+                    if path.exists() {
+                        vec![self.pair_to(path, tracker)]
+                    } else {
+                        self.start_from(path, tracker).into_iter().collect()
+                    }
+                })
+                .collect(),
+            kind => {
+                let kind = convert_event_kind(&kind);
+                event.paths.into_iter().map(|path| FileEvent::new(kind.clone(), path)).collect()
+            }
+        }
+    }
+
+    ///记录原路径等待配对，之前未配对的原路径按删除返回
+    fn start_from(&mut self, path: std::path::PathBuf, tracker: Option<usize>) -> Option<FileEvent> {
+        let expired = self.take_pending();
+        self.pending_from = Some((path, tracker, std::time::Instant::now()));
+        expired
+    }
+
+    ///新路径与等待中的原路径配对
+    fn pair_to(&mut self, to: std::path::PathBuf, tracker: Option<usize>) -> FileEvent {
+        let window = std::time::Duration::from_millis(RENAME_WINDOW_MS);
+        match self.pending_from.take() {
+            Some((from, from_tracker, at)) if from_tracker == tracker && at.elapsed() <= window => {
+                self.last_paired = Some((from.clone(), to.clone()));
+                FileEvent::rename(from, to)
+            }
+            pending => {
+                self.pending_from = pending;
+                FileEvent::new(EventKind::Create, to)
+            }
+        }
+    }
+
+    ///取出超过等待时间仍未配对的原路径（按删除处理）
+    fn take_expired(&mut self, now: std::time::Instant) -> Option<FileEvent> {
+        let window = std::time::Duration::from_millis(RENAME_WINDOW_MS);
+        match self.pending_from {
+            Some((_, _, at)) if now.saturating_duration_since(at) > window => self.take_pending(),
+            _ => None,
+        }
+    }
+
+    ///取出等待中的原路径（按删除处理）
+    fn take_pending(&mut self) -> Option<FileEvent> {
+        self.pending_from.take().map(|(path, _, _)| FileEvent::new(EventKind::Delete, path))
+    }
+
+    ///等待中的原路径的到期时刻
+    fn next_due(&self) -> Option<std::time::Instant> {
+        self.pending_from.as_ref()
+            .map(|(_, _, at)| *at + std::time::Duration::from_millis(RENAME_WINDOW_MS))
+    }
+}
+
 ///转换事件类型
 fn convert_event_kind(kind: &notify::EventKind) -> EventKind {
     match kind {
@@ -467,48 +773,223 @@ fn convert_event_kind(kind: &notify::EventKind) -> EventKind {
 }
 
 ///简单模式匹配（支持 * 和 ?）
+///
+///双指针回溯实现，遇到 * 时只记录回退位置，时间复杂度 O(模式长度 × 文本长度)
 fn match_pattern(pattern: &str, text: &str) -> bool {
-    let mut pattern_chars = pattern.chars().peekable();
-    let mut text_chars = text.chars().peekable();
-
-    while let Some(p) = pattern_chars.next() {
-        match p {
-            '*' => {
-                //跳过连续的 *
-                while pattern_chars.peek() == Some(&'*') {
-                    pattern_chars.next();
-                }
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    //最近一个 * 的位置，以及它当前匹配到的文本位置
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            //回退：让 * 多匹配一个字符
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
 
-                //* 在末尾，匹配所有剩余
-                if pattern_chars.peek().is_none() {
-                    return true;
-                }
+    //剩余模式只能是 *
+    pattern[p..].iter().all(|&c| c == '*')
+}
 
-                //尝试匹配剩余部分
-                let remaining_pattern: String = pattern_chars.collect();
-                let mut remaining_text: String = text_chars.collect();
+//========================================
+//测试
+//========================================
 
-                while !remaining_text.is_empty() {
-                    if match_pattern(&remaining_pattern, &remaining_text) {
-                        return true;
-                    }
-                    remaining_text = remaining_text.chars().skip(1).collect();
-                }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-                return match_pattern(&remaining_pattern, "");
-            }
-            '?' => {
-                if text_chars.next().is_none() {
-                    return false;
-                }
-            }
-            c => {
-                if text_chars.next() != Some(c) {
-                    return false;
-                }
+    ///创建空的临时测试目录（返回规范化路径，与 notify 报告的路径一致）
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("file_watcher_{}_{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.canonicalize().unwrap()
+    }
+
+    ///收集 duration 内收到的全部事件
+    fn collect_events(rx: &mpsc::Receiver<FileEvent>, duration: std::time::Duration) -> Vec<FileEvent> {
+        let deadline = std::time::Instant::now() + duration;
+        let mut events = Vec::new();
+        while let Some(left) = deadline.checked_duration_since(std::time::Instant::now()) {
+            match rx.recv_timeout(left) {
+                Ok(event) => events.push(event),
+                Err(_) => break,
             }
         }
+        events
     }
 
-    text_chars.peek().is_none()
+    #[test]
+    fn debounce_coalesces_rapid_writes_into_one_event() {
+        let dir = temp_dir("debounce");
+        let file = dir.join("data.txt");
+        std::fs::write(&file, "0").unwrap();
+
+        let (handle, rx) = ChannelWatcher::new()
+            .path(&dir)
+            .debounce(std::time::Duration::from_millis(300))
+            .watch_channel()
+            .unwrap();
+        //等待后台线程注册监控
+        std::thread::sleep(std::time::Duration::from_millis(200));
+
+        for i in 1..=3 {
+            std::fs::write(&file, i.to_string()).unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(30));
+        }
+
+        let events: Vec<_> = collect_events(&rx, std::time::Duration::from_millis(1000))
+            .into_iter()
+            .filter(|e| e.path == file)
+            .collect();
+        handle.stop();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(events.len(), 1, "{:?}", events);
+        assert_eq!(events[0].kind, EventKind::Modify);
+    }
+
+    #[test]
+    fn debouncer_merges_by_path_and_waits_for_quiet_period() {
+        let delay = std::time::Duration::from_millis(50);
+        let mut debouncer = Debouncer::new(delay);
+        let a = std::path::PathBuf::from("a.txt");
+        let b = std::path::PathBuf::from("b.txt");
+
+        debouncer.push(FileEvent::new(EventKind::Create, a.clone()));
+        debouncer.push(FileEvent::new(EventKind::Modify, a.clone()));
+        debouncer.push(FileEvent::new(EventKind::Delete, b.clone()));
+        debouncer.push(FileEvent::new(EventKind::Create, b.clone()));
+        assert!(debouncer.take_due(std::time::Instant::now()).is_empty());
+
+        let due = debouncer.take_due(std::time::Instant::now() + delay);
+        let summary: Vec<_> = due.iter().map(|e| (e.path.clone(), e.kind.clone())).collect();
+        //创建后修改仍为创建，删除后重新创建视为修改
+        assert_eq!(summary, vec![(a, EventKind::Create), (b, EventKind::Modify)]);
+        assert!(debouncer.next_due().is_none());
+    }
+
+    #[test]
+    fn rename_reports_old_and_new_path() {
+        let dir = temp_dir("rename");
+        let from = dir.join("old.txt");
+        let to = dir.join("new.txt");
+        std::fs::write(&from, "data").unwrap();
+
+        let (handle, rx) = ChannelWatcher::new().path(&dir).watch_channel().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        std::fs::rename(&from, &to).unwrap();
+
+        let events = collect_events(&rx, std::time::Duration::from_millis(1000));
+        handle.stop();
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let renames: Vec<_> = events.iter().filter(|e| e.kind == EventKind::Rename).collect();
+        assert_eq!(renames.len(), 1, "{:?}", events);
+        assert_eq!(renames[0].from_path.as_deref(), Some(from.as_path()));
+        assert_eq!(renames[0].path, to);
+    }
+
+    ///构造带 tracker 的 notify 重命名事件
+    fn name_event(mode: notify::event::RenameMode, paths: &[&str], tracker: usize) -> notify::Event {
+        let mut event = notify::Event::new(notify::EventKind::Modify(notify::event::ModifyKind::Name(mode)))
+            .set_tracker(tracker);
+        for path in paths {
+            event = event.add_path(std::path::PathBuf::from(path));
+        }
+        event
+    }
+
+    #[test]
+    fn rename_tracker_pairs_from_and_to() {
+        use notify::event::RenameMode;
+
+        let mut tracker = RenameTracker::new();
+        assert!(tracker.convert(name_event(RenameMode::From, &["a.txt"], 1)).is_empty());
+        let events = tracker.convert(name_event(RenameMode::To, &["b.txt"], 1));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, EventKind::Rename);
+        assert_eq!(events[0].from_path, Some(std::path::PathBuf::from("a.txt")));
+        assert_eq!(events[0].path, std::path::PathBuf::from("b.txt"));
+
+        //inotify 随后报告的 Both 事件不重复触发
+        assert!(tracker.convert(name_event(RenameMode::Both, &["a.txt", "b.txt"], 1)).is_empty());
+    }
+
+    #[test]
+    fn rename_tracker_falls_back_to_delete_and_create() {
+        use notify::event::RenameMode;
+
+        let mut tracker = RenameTracker::new();
+        assert!(tracker.convert(name_event(RenameMode::From, &["gone.txt"], 1)).is_empty());
+        //tracker 不同，无法配对：新路径按创建处理
+        let events = tracker.convert(name_event(RenameMode::To, &["other.txt"], 2));
+        assert_eq!(events[0].kind, EventKind::Create);
+
+        //超过配对窗口的原路径按删除处理
+        let later = std::time::Instant::now() + std::time::Duration::from_millis(RENAME_WINDOW_MS + 1);
+        let expired = tracker.take_expired(later).unwrap();
+        assert_eq!(expired.kind, EventKind::Delete);
+        assert_eq!(expired.path, std::path::PathBuf::from("gone.txt"));
+    }
+
+    fn pattern_filter(pattern: &str) -> EventFilter {
+        EventFilter {
+            extensions: None,
+            pattern: Some(pattern.to_string()),
+            kinds: None,
+        }
+    }
+
+    #[test]
+    fn filters_match_rename_on_either_path() {
+        let rename = |from: &str, to: &str| FileEvent::rename(from.into(), to.into());
+
+        let filter = pattern_filter("*.rs");
+        assert!(filter.matches(&rename("main.tmp", "main.rs")));
+        assert!(filter.matches(&rename("main.rs", "main.rs.bak")));
+        assert!(!filter.matches(&rename("notes.tmp", "notes.txt")));
+
+        let filter = EventFilter {
+            extensions: Some(vec!["rs".to_string()]),
+            pattern: None,
+            kinds: Some(vec![EventKind::Rename]),
+        };
+        assert!(filter.matches(&rename("lib.tmp", "lib.RS")));
+        assert!(!filter.matches(&FileEvent::new(EventKind::Modify, "lib.rs".into())));
+    }
+
+    #[test]
+    fn match_pattern_wildcards() {
+        assert!(match_pattern("*.rs", "main.rs"));
+        assert!(!match_pattern("*.rs", "main.rsx"));
+        assert!(match_pattern("a?c", "abc"));
+        assert!(!match_pattern("a?c", "ac"));
+        assert!(match_pattern("test_*.log", "test_1.log"));
+        assert!(match_pattern("*", ""));
+        assert!(!match_pattern("?*", ""));
+    }
+
+    #[test]
+    fn match_pattern_is_not_exponential() {
+        let text = "a".repeat(10_000);
+        let started = std::time::Instant::now();
+        assert!(!match_pattern("a*a*a*a*b", &text));
+        assert!(match_pattern("a*a*a*a*b", &format!("{}b", text)));
+        assert!(started.elapsed() < std::time::Duration::from_secs(1));
+    }
 }
@@ -22,6 +22,8 @@ use super::config;
 pub struct Response {
     ///状态码
     pub status: u16,
+    ///响应头（名称, 值），同名多值的头按出现顺序分别保存
+    pub headers: Vec<(String, String)>,
     ///响应体
     body: String,
 }
@@ -41,6 +43,14 @@ impl Response {
     pub fn is_success(&self) -> bool {
         self.status >= 200 && self.status < 300
     }
+
+    ///获取响应头（名称不区分大小写，同名多值时返回第一个）
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
 }
 
 //========================================
@@ -51,13 +61,27 @@ impl Response {
 pub struct HttpClient {
     ///自定义请求头
     headers: Vec<(String, String)>,
+    ///Cookie 存储（None 表示未启用）
+    cookie_jar: Option<std::sync::Mutex<Vec<StoredCookie>>>,
+    ///单次请求超时
+    timeout: std::time::Duration,
+    ///失败后的最多重试次数（仅 GET/PUT/DELETE）
+    retries: u32,
+    ///首次重试前的等待时间，之后按 config::RETRY_MULTIPLIER 递增
+    retry_backoff: std::time::Duration,
 }
 
 impl HttpClient {
     ///创建新的 HTTP 客户端
+    ///
+    ///默认携带 `User-Agent: config::DEFAULT_USER_AGENT`，可通过 with_user_agent() 覆盖
     pub fn new() -> Self {
         Self {
-            headers: Vec::new(),
+            headers: vec![("User-Agent".to_string(), config::DEFAULT_USER_AGENT.to_string())],
+            cookie_jar: None,
+            timeout: std::time::Duration::from_secs(config::REQUEST_TIMEOUT_SECS),
+            retries: 0,
+            retry_backoff: std::time::Duration::from_millis(config::RETRY_INITIAL_MS),
         }
     }
 
@@ -67,117 +91,270 @@ impl HttpClient {
         self
     }
 
+    ///设置 User-Agent（替换默认值）
+    pub fn with_user_agent(mut self, ua: &str) -> Self {
+        self.headers.retain(|(key, _)| !key.eq_ignore_ascii_case("User-Agent"));
+        self.with_header("User-Agent", ua)
+    }
+
     ///添加 Bearer Token
     pub fn with_bearer_token(self, token: &str) -> Self {
         self.with_header("Authorization", &format!("Bearer {}", token))
     }
 
+    ///设置单次请求超时（默认 config::REQUEST_TIMEOUT_SECS）
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    ///设置失败重试
+    ///
+    ///只对幂等请求（GET/PUT/DELETE）在连接错误或 5xx 时重试，POST 不重试。
+    ///首次等待 backoff，之后按 config::RETRY_MULTIPLIER 递增，最长 config::RETRY_MAX_MS
+    pub fn with_retries(mut self, count: u32, backoff: std::time::Duration) -> Self {
+        self.retries = count;
+        self.retry_backoff = backoff;
+        self
+    }
+
+    ///启用/禁用 Cookie 存储
+    ///
+    ///启用后会保存响应中的 Set-Cookie，并在后续请求中自动携带 Cookie 头：
+    ///- 按名称 + 域名 + 路径保存，只发送给域名和路径匹配的请求
+    ///- 遵循 Expires / Max-Age，过期的 Cookie 不再发送
+    ///- Secure Cookie 只通过 https 发送
+    ///
+    ///（不校验公共后缀列表，不处理 SameSite）
+    pub fn with_cookies(mut self, enable: bool) -> Self {
+        self.cookie_jar = if enable {
+            Some(std::sync::Mutex::new(Vec::new()))
+        } else {
+            None
+        };
+        self
+    }
+
+    ///获取当前保存且未过期的 Cookie 列表（名称, 值），不区分域名
+    pub fn cookies(&self) -> Vec<(String, String)> {
+        let jar = match self.cookie_jar {
+            Some(ref jar) => jar,
+            None => return Vec::new(),
+        };
+        let now = std::time::SystemTime::now();
+        jar.lock()
+            .map(|cookies| {
+                cookies
+                    .iter()
+                    .filter(|c| !c.is_expired(now))
+                    .map(|c| (c.name.clone(), c.value.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     //========================================
-    //GET 请求
+    //请求辅助方法
     //========================================
 
-    ///发送 GET 请求
-    pub fn get(&self, url: &str) -> Result<Response, String> {
-        let mut request = ureq::get(url)
-            .timeout(std::time::Duration::from_secs(config::REQUEST_TIMEOUT_SECS));
-
+    ///应用自定义请求头和 Cookie
+    fn apply_headers(&self, mut request: ureq::Request) -> ureq::Request {
         for (key, value) in &self.headers {
             request = request.set(key, value);
         }
 
-        match request.call() {
+        if let Some(ref jar) = self.cookie_jar {
+            if let Ok(mut cookies) = jar.lock() {
+                let header = cookie_header(&mut cookies, request.url(), std::time::SystemTime::now());
+                if let Some(header) = header {
+                    request = request.set("Cookie", &header);
+                }
+            }
+        }
+
+        request
+    }
+
+    ///发送请求，幂等请求按重试配置在连接错误或 5xx 时重试
+    ///
+    ///send 返回装箱的 ureq::Error（该错误类型较大，装箱避免每次返回时复制）
+    fn execute<F>(&self, idempotent: bool, send: F) -> Result<Response, String>
+    where
+        F: Fn() -> Result<ureq::Response, Box<ureq::Error>>,
+    {
+        let retries = if idempotent { self.retries } else { 0 };
+        let mut delay = self.retry_backoff;
+        let mut attempt = 0;
+
+        loop {
+            let result = send();
+            let retryable = match result {
+                Err(ref e) => match **e {
+                    ureq::Error::Transport(_) => true,
+                    ureq::Error::Status(code, _) => code >= 500,
+                },
+                Ok(_) => false,
+            };
+            if !retryable || attempt >= retries {
+                return self.handle_result(result.map_err(|e| *e));
+            }
+
+            attempt += 1;
+            std::thread::sleep(delay);
+            delay = delay
+                .mul_f64(config::RETRY_MULTIPLIER)
+                .min(std::time::Duration::from_millis(config::RETRY_MAX_MS));
+        }
+    }
+
+    ///处理请求结果（非 2xx 状态码也作为正常响应返回）
+    fn handle_result(&self, result: Result<ureq::Response, ureq::Error>) -> Result<Response, String> {
+        match result {
             Ok(resp) => {
+                self.store_cookies(&resp);
                 let status = resp.status();
+                let headers = Self::collect_headers(&resp);
                 let body = resp.into_string().unwrap_or_default();
-                Ok(Response { status, body })
+                Ok(Response { status, headers, body })
             }
             Err(ureq::Error::Status(code, resp)) => {
+                self.store_cookies(&resp);
+                let headers = Self::collect_headers(&resp);
                 let body = resp.into_string().unwrap_or_default();
-                Ok(Response { status: code, body })
+                Ok(Response { status: code, headers, body })
             }
             Err(e) => Err(format!("请求失败: {}", e)),
         }
     }
 
+    ///收集响应头（名称为小写）
+    fn collect_headers(resp: &ureq::Response) -> Vec<(String, String)> {
+        let names = resp.headers_names();
+        let mut headers = Vec::new();
+        for (i, name) in names.iter().enumerate() {
+            //同名头不一定相邻，只在第一次出现时收集
+            if names[..i].contains(name) {
+                continue;
+            }
+            for value in resp.all(name) {
+                headers.push((name.clone(), value.to_string()));
+            }
+        }
+        headers
+    }
+
+    ///保存响应中的 Set-Cookie
+    fn store_cookies(&self, resp: &ureq::Response) {
+        let jar = match self.cookie_jar {
+            Some(ref jar) => jar,
+            None => return,
+        };
+        let mut cookies = match jar.lock() {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+
+        let now = std::time::SystemTime::now();
+        for set_cookie in resp.all("set-cookie") {
+            store_set_cookie(&mut cookies, resp.get_url(), set_cookie, now);
+        }
+    }
+
     //========================================
-    //POST 请求
+    //GET 请求
     //========================================
 
-    ///发送 POST 请求（JSON 数据）
-    pub fn post_json<T: serde::Serialize>(&self, url: &str, data: &T) -> Result<Response, String> {
-        let mut request = ureq::post(url)
-            .timeout(std::time::Duration::from_secs(config::REQUEST_TIMEOUT_SECS))
-            .set("Content-Type", "application/json");
+    ///发送 GET 请求
+    pub fn get(&self, url: &str) -> Result<Response, String> {
+        self.execute(true, || {
+            let request = ureq::get(url).timeout(self.timeout);
+            self.apply_headers(request).call().map_err(Box::new)
+        })
+    }
 
-        for (key, value) in &self.headers {
-            request = request.set(key, value);
-        }
+    ///发送带查询参数的 GET 请求（参数自动进行百分号编码）
+    ///
+    ///# 示例
+    ///```rust
+    ///let params = [("q", "rust & go"), ("page", "2")];
+    ///client.get_with_query("https://api.example.com/search", &params); //?q=rust%20%26%20go&page=2
+    ///```
+    pub fn get_with_query(&self, url: &str, params: &[(&str, &str)]) -> Result<Response, String> {
+        self.get(&super::url::with_query(url, params))
+    }
 
-        match request.send_json(data) {
+    ///发送 GET 请求，返回响应体的流式读取器（不缓冲整个响应体）
+    ///
+    ///适用于大文件、超大 JSON 数组或持续输出的日志流，
+    ///可配合 serde_json::Deserializer::from_reader 或 BufRead::lines 增量处理
+    ///
+    ///# 注意
+    ///- 连接保持打开，直到读取器读完或被丢弃
+    ///- 不设置整体超时（with_timeout() 的超时会截断长时间的流），也不重试
+    ///- 非 2xx 状态码返回 Err
+    ///
+    ///# 示例
+    ///```rust
+    ///use std::io::BufRead;
+    ///let reader = client.get_reader("https://example.com/logs")?;
+    ///for line in std::io::BufReader::new(reader).lines() {
+    ///    println!("{}", line.unwrap());
+    ///}
+    ///```
+    pub fn get_reader(&self, url: &str) -> Result<Box<dyn std::io::Read + Send>, String> {
+        let request = self.apply_headers(ureq::get(url));
+
+        match request.call() {
             Ok(resp) => {
-                let status = resp.status();
-                let body = resp.into_string().unwrap_or_default();
-                Ok(Response { status, body })
+                self.store_cookies(&resp);
+                Ok(Box::new(resp.into_reader()))
             }
             Err(ureq::Error::Status(code, resp)) => {
-                let body = resp.into_string().unwrap_or_default();
-                Ok(Response { status: code, body })
+                self.store_cookies(&resp);
+                Err(format!("请求失败: HTTP {}", code))
             }
             Err(e) => Err(format!("请求失败: {}", e)),
         }
     }
 
+    //========================================
+    //POST 请求
+    //========================================
+
+    ///发送 POST 请求（JSON 数据）
+    pub fn post_json<T: serde::Serialize>(&self, url: &str, data: &T) -> Result<Response, String> {
+        let request = ureq::post(url)
+            .timeout(self.timeout)
+            .set("Content-Type", "application/json");
+
+        let request = self.apply_headers(request);
+
+        self.handle_result(request.send_json(data))
+    }
+
     ///发送 POST 请求（表单数据）
+    ///
+    ///键和值均做百分号编码（空格编码为 %20），值中含 `&`、`=`、`+` 或中文时不会破坏表单结构
     pub fn post_form(&self, url: &str, data: &[(&str, &str)]) -> Result<Response, String> {
-        let mut request = ureq::post(url)
-            .timeout(std::time::Duration::from_secs(config::REQUEST_TIMEOUT_SECS))
+        let request = ureq::post(url)
+            .timeout(self.timeout)
             .set("Content-Type", "application/x-www-form-urlencoded");
 
-        for (key, value) in &self.headers {
-            request = request.set(key, value);
-        }
+        let request = self.apply_headers(request);
 
-        let body: String = data
-            .iter()
-            .map(|(k, v)| format!("{}={}", k, v))
-            .collect::<Vec<_>>()
-            .join("&");
+        let body = super::url::build_query(data);
 
-        match request.send_string(&body) {
-            Ok(resp) => {
-                let status = resp.status();
-                let body = resp.into_string().unwrap_or_default();
-                Ok(Response { status, body })
-            }
-            Err(ureq::Error::Status(code, resp)) => {
-                let body = resp.into_string().unwrap_or_default();
-                Ok(Response { status: code, body })
-            }
-            Err(e) => Err(format!("请求失败: {}", e)),
-        }
+        self.handle_result(request.send_string(&body))
     }
 
     ///发送 POST 请求（原始字符串）
     pub fn post_string(&self, url: &str, body: &str) -> Result<Response, String> {
-        let mut request = ureq::post(url)
-            .timeout(std::time::Duration::from_secs(config::REQUEST_TIMEOUT_SECS));
+        let request = ureq::post(url)
+            .timeout(self.timeout);
 
-        for (key, value) in &self.headers {
-            request = request.set(key, value);
-        }
+        let request = self.apply_headers(request);
 
-        match request.send_string(body) {
-            Ok(resp) => {
-                let status = resp.status();
-                let body = resp.into_string().unwrap_or_default();
-                Ok(Response { status, body })
-            }
-            Err(ureq::Error::Status(code, resp)) => {
-                let body = resp.into_string().unwrap_or_default();
-                Ok(Response { status: code, body })
-            }
-            Err(e) => Err(format!("请求失败: {}", e)),
-        }
+        self.handle_result(request.send_string(body))
     }
 
     //========================================
@@ -186,26 +363,12 @@ impl HttpClient {
 
     ///发送 PUT 请求（JSON 数据）
     pub fn put_json<T: serde::Serialize>(&self, url: &str, data: &T) -> Result<Response, String> {
-        let mut request = ureq::put(url)
-            .timeout(std::time::Duration::from_secs(config::REQUEST_TIMEOUT_SECS))
-            .set("Content-Type", "application/json");
-
-        for (key, value) in &self.headers {
-            request = request.set(key, value);
-        }
-
-        match request.send_json(data) {
-            Ok(resp) => {
-                let status = resp.status();
-                let body = resp.into_string().unwrap_or_default();
-                Ok(Response { status, body })
-            }
-            Err(ureq::Error::Status(code, resp)) => {
-                let body = resp.into_string().unwrap_or_default();
-                Ok(Response { status: code, body })
-            }
-            Err(e) => Err(format!("请求失败: {}", e)),
-        }
+        self.execute(true, || {
+            let request = ureq::put(url)
+                .timeout(self.timeout)
+                .set("Content-Type", "application/json");
+            self.apply_headers(request).send_json(data).map_err(Box::new)
+        })
     }
 
     //========================================
@@ -214,32 +377,246 @@ impl HttpClient {
 
     ///发送 DELETE 请求
     pub fn delete(&self, url: &str) -> Result<Response, String> {
-        let mut request = ureq::delete(url)
-            .timeout(std::time::Duration::from_secs(config::REQUEST_TIMEOUT_SECS));
+        self.execute(true, || {
+            let request = ureq::delete(url).timeout(self.timeout);
+            self.apply_headers(request).call().map_err(Box::new)
+        })
+    }
+}
 
-        for (key, value) in &self.headers {
-            request = request.set(key, value);
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//========================================
+//Cookie 存储
+//========================================
+
+///Cookie 存储中的一条 Cookie
+#[derive(Clone, Debug)]
+struct StoredCookie {
+    name: String,
+    value: String,
+    ///域名（小写，不含开头的点）
+    domain: String,
+    ///未指定 Domain 属性时只发送给完全相同的主机
+    host_only: bool,
+    path: String,
+    ///只通过 https 发送
+    secure: bool,
+    ///过期时间（None 表示会话 Cookie）
+    expires: Option<std::time::SystemTime>,
+}
+
+impl StoredCookie {
+    fn is_expired(&self, now: std::time::SystemTime) -> bool {
+        self.expires.is_some_and(|t| t <= now)
+    }
+
+    ///是否应随发往 (secure, host, path) 的请求发送
+    fn matches(&self, secure: bool, host: &str, path: &str) -> bool {
+        if self.secure && !secure {
+            return false;
         }
+        let domain_ok = if self.host_only {
+            host == self.domain
+        } else {
+            domain_match(host, &self.domain)
+        };
+        domain_ok && path_match(path, &self.path)
+    }
+}
 
-        match request.call() {
-            Ok(resp) => {
-                let status = resp.status();
-                let body = resp.into_string().unwrap_or_default();
-                Ok(Response { status, body })
+///解析 Set-Cookie 并保存到 jar（url 为返回该响应的请求地址）
+///
+///Domain 与请求主机不匹配的 Cookie 被忽略；同名同域同路径的 Cookie 被替换；
+///Max-Age <= 0 或 Expires 已过的 Cookie 会删除已保存的同名 Cookie
+fn store_set_cookie(jar: &mut Vec<StoredCookie>, url: &str, set_cookie: &str, now: std::time::SystemTime) {
+    let (_, host, request_path) = match split_url(url) {
+        Some(parts) => parts,
+        None => return,
+    };
+
+    let mut parts = set_cookie.split(';');
+    let (name, value) = match parts.next().and_then(|pair| pair.split_once('=')) {
+        Some((name, value)) => (name.trim(), value.trim()),
+        None => return,
+    };
+    if name.is_empty() {
+        return;
+    }
+
+    let mut cookie = StoredCookie {
+        name: name.to_string(),
+        value: value.trim_matches('"').to_string(),
+        domain: host.clone(),
+        host_only: true,
+        path: default_cookie_path(&request_path),
+        secure: false,
+        expires: None,
+    };
+    let mut max_age: Option<i64> = None;
+
+    for attr in parts {
+        let (key, val) = match attr.split_once('=') {
+            Some((k, v)) => (k.trim(), v.trim()),
+            None => (attr.trim(), ""),
+        };
+        if key.eq_ignore_ascii_case("domain") {
+            let domain = val.trim_start_matches('.').to_ascii_lowercase();
+            if domain.is_empty() {
+                continue;
             }
-            Err(ureq::Error::Status(code, resp)) => {
-                let body = resp.into_string().unwrap_or_default();
-                Ok(Response { status: code, body })
+            if !domain_match(&host, &domain) {
+                return;
+            }
+            cookie.domain = domain;
+            cookie.host_only = false;
+        } else if key.eq_ignore_ascii_case("path") {
+            if val.starts_with('/') {
+                cookie.path = val.to_string();
+            }
+        } else if key.eq_ignore_ascii_case("secure") {
+            cookie.secure = true;
+        } else if key.eq_ignore_ascii_case("max-age") {
+            if let Ok(secs) = val.parse::<i64>() {
+                max_age = Some(secs);
+            }
+        } else if key.eq_ignore_ascii_case("expires") {
+            if let Some(t) = parse_http_date(val) {
+                cookie.expires = Some(t);
             }
-            Err(e) => Err(format!("请求失败: {}", e)),
         }
     }
+
+    //Max-Age 优先于 Expires
+    if let Some(secs) = max_age {
+        cookie.expires = Some(if secs <= 0 {
+            std::time::UNIX_EPOCH
+        } else {
+            now + std::time::Duration::from_secs(secs as u64)
+        });
+    }
+
+    jar.retain(|c| !(c.name == cookie.name && c.domain == cookie.domain && c.path == cookie.path));
+    if !cookie.is_expired(now) {
+        jar.push(cookie);
+    }
 }
 
-impl Default for HttpClient {
-    fn default() -> Self {
-        Self::new()
+///生成发往 url 的 Cookie 请求头（没有匹配的 Cookie 时返回 None），同时清理过期 Cookie
+///
+///路径更长（更具体）的 Cookie 排在前面
+fn cookie_header(jar: &mut Vec<StoredCookie>, url: &str, now: std::time::SystemTime) -> Option<String> {
+    jar.retain(|c| !c.is_expired(now));
+    let (secure, host, path) = split_url(url)?;
+
+    let mut matched: Vec<&StoredCookie> = jar.iter().filter(|c| c.matches(secure, &host, &path)).collect();
+    if matched.is_empty() {
+        return None;
     }
+    matched.sort_by_key(|c| std::cmp::Reverse(c.path.len()));
+    Some(
+        matched
+            .iter()
+            .map(|c| format!("{}={}", c.name, c.value))
+            .collect::<Vec<_>>()
+            .join("; "),
+    )
+}
+
+///拆分 URL 为 (是否 https, 小写主机名, 路径)
+fn split_url(url: &str) -> Option<(bool, String, String)> {
+    let (scheme, rest) = url.split_once("://")?;
+    let end = rest.find(['/', '?', '#']).unwrap_or(rest.len());
+    let authority = &rest[..end];
+    //去掉 userinfo
+    let host_port = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+    let host = if let Some(stripped) = host_port.strip_prefix('[') {
+        stripped.split(']').next().unwrap_or("")
+    } else {
+        host_port.split(':').next().unwrap_or("")
+    };
+    if host.is_empty() {
+        return None;
+    }
+
+    let rest = &rest[end..];
+    let path_end = rest.find(['?', '#']).unwrap_or(rest.len());
+    let path = if rest.starts_with('/') { &rest[..path_end] } else { "/" };
+    Some((scheme.eq_ignore_ascii_case("https"), host.to_ascii_lowercase(), path.to_string()))
+}
+
+///域名匹配（RFC 6265 5.1.3）：相同，或 host 是 domain 的子域名
+fn domain_match(host: &str, domain: &str) -> bool {
+    host == domain
+        || (host.len() > domain.len()
+            && host.ends_with(domain)
+            && host.as_bytes()[host.len() - domain.len() - 1] == b'.')
+}
+
+///路径匹配（RFC 6265 5.1.4）
+fn path_match(request_path: &str, cookie_path: &str) -> bool {
+    request_path == cookie_path
+        || (request_path.starts_with(cookie_path)
+            && (cookie_path.ends_with('/') || request_path.as_bytes()[cookie_path.len()] == b'/'))
+}
+
+///未指定 Path 属性时的默认路径：请求路径去掉最后一段（RFC 6265 5.1.4）
+fn default_cookie_path(request_path: &str) -> String {
+    match request_path.rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(i) => request_path[..i].to_string(),
+    }
+}
+
+///解析 Expires 中的 HTTP 日期（如 `Wed, 21 Oct 2015 07:28:00 GMT`，也接受 `21-Oct-15` 写法）
+fn parse_http_date(s: &str) -> Option<std::time::SystemTime> {
+    const MONTHS: [&str; 12] = ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"];
+
+    let (mut day, mut month, mut year, mut time) = (None, None, None, None);
+    for token in s.split([' ', ',', '-']).filter(|t| !t.is_empty()) {
+        if time.is_none() && token.contains(':') {
+            let mut hms = token.split(':').map(|v| v.parse::<u64>().ok());
+            if let (Some(Some(h)), Some(Some(m)), Some(Some(sec))) = (hms.next(), hms.next(), hms.next()) {
+                time = Some((h, m, sec));
+            }
+        } else if let Ok(n) = token.parse::<i64>() {
+            if day.is_none() && token.len() <= 2 {
+                day = Some(n);
+            } else if year.is_none() {
+                year = Some(n);
+            }
+        } else if month.is_none() {
+            let lower = token.to_ascii_lowercase();
+            month = MONTHS.iter().position(|m| lower.starts_with(m)).map(|i| i as i64 + 1);
+        }
+    }
+
+    let (day, month, mut year, (h, m, sec)) = (day?, month?, year?, time?);
+    //两位年份：70-99 为 19xx，00-69 为 20xx
+    if year < 70 {
+        year += 2000;
+    } else if year < 100 {
+        year += 1900;
+    }
+    if !(1..=31).contains(&day) || h > 23 || m > 59 || sec > 60 || year < 1970 {
+        return None;
+    }
+
+    //公历日期转 Unix 天数
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468;
+
+    let secs = days as u64 * 86400 + h * 3600 + m * 60 + sec;
+    Some(std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs))
 }
 
 //========================================
@@ -255,3 +632,176 @@ pub fn get(url: &str) -> Result<Response, String> {
 pub fn post_json<T: serde::Serialize>(url: &str, data: &T) -> Result<Response, String> {
     HttpClient::new().post_json(url, data)
 }
+
+//========================================
+//测试
+//========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header_for(jar: &mut Vec<StoredCookie>, url: &str) -> Option<String> {
+        cookie_header(jar, url, std::time::SystemTime::now())
+    }
+
+    #[test]
+    fn cookies_are_scoped_to_their_origin() {
+        let now = std::time::SystemTime::now();
+        let mut jar = Vec::new();
+        store_set_cookie(&mut jar, "http://a.example.com/login", "sid=1; Path=/", now);
+        store_set_cookie(&mut jar, "http://other.test/", "sid=2", now);
+
+        assert_eq!(header_for(&mut jar, "http://a.example.com/api").as_deref(), Some("sid=1"));
+        assert_eq!(header_for(&mut jar, "http://other.test/x").as_deref(), Some("sid=2"));
+        //未指定 Domain 时不发送给子域名
+        assert_eq!(header_for(&mut jar, "http://b.a.example.com/"), None);
+    }
+
+    #[test]
+    fn domain_attribute_covers_subdomains_but_not_foreign_hosts() {
+        let now = std::time::SystemTime::now();
+        let mut jar = Vec::new();
+        store_set_cookie(&mut jar, "https://www.example.com/", "a=1; Domain=.example.com", now);
+        //不能为无关域名设置 Cookie
+        store_set_cookie(&mut jar, "https://www.example.com/", "b=2; Domain=evil.test", now);
+
+        assert_eq!(header_for(&mut jar, "https://api.example.com/").as_deref(), Some("a=1"));
+        assert_eq!(header_for(&mut jar, "https://notexample.com/"), None);
+        assert_eq!(jar.len(), 1);
+    }
+
+    #[test]
+    fn path_and_secure_are_honoured() {
+        let now = std::time::SystemTime::now();
+        let mut jar = Vec::new();
+        store_set_cookie(&mut jar, "https://example.com/", "token=t; Secure", now);
+        store_set_cookie(&mut jar, "https://example.com/", "admin=1; Path=/admin", now);
+        //未指定 Path 时取请求路径的目录
+        store_set_cookie(&mut jar, "https://example.com/docs/page", "doc=1", now);
+
+        assert_eq!(header_for(&mut jar, "http://example.com/").as_deref(), None);
+        assert_eq!(header_for(&mut jar, "https://example.com/").as_deref(), Some("token=t"));
+        assert_eq!(
+            header_for(&mut jar, "https://example.com/admin/users").as_deref(),
+            Some("admin=1; token=t")
+        );
+        assert_eq!(header_for(&mut jar, "https://example.com/administrator").as_deref(), Some("token=t"));
+        assert_eq!(header_for(&mut jar, "https://example.com/docs/other").as_deref(), Some("doc=1; token=t"));
+    }
+
+    #[test]
+    fn expired_cookies_are_dropped() {
+        let now = std::time::SystemTime::now();
+        let mut jar = Vec::new();
+        store_set_cookie(&mut jar, "http://example.com/", "a=1", now);
+        store_set_cookie(&mut jar, "http://example.com/", "b=2; Max-Age=60", now);
+        store_set_cookie(&mut jar, "http://example.com/", "c=3; Expires=Wed, 21 Oct 2015 07:28:00 GMT", now);
+        assert_eq!(header_for(&mut jar, "http://example.com/").as_deref(), Some("a=1; b=2"));
+
+        //Max-Age=0 删除已保存的 Cookie
+        store_set_cookie(&mut jar, "http://example.com/", "a=; Max-Age=0", now);
+        assert_eq!(header_for(&mut jar, "http://example.com/").as_deref(), Some("b=2"));
+
+        //Max-Age 到期后不再发送
+        let later = now + std::time::Duration::from_secs(61);
+        assert_eq!(cookie_header(&mut jar, "http://example.com/", later), None);
+        assert!(jar.is_empty());
+    }
+
+    #[test]
+    fn post_form_sends_encoded_body() {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+        let handle = std::thread::spawn(move || {
+            let mut req = server.recv().unwrap();
+            let mut body = String::new();
+            std::io::Read::read_to_string(req.as_reader(), &mut body).unwrap();
+            req.respond(tiny_http::Response::from_string("ok")).unwrap();
+            body
+        });
+
+        let url = format!("http://127.0.0.1:{}/form", port);
+        let resp = HttpClient::new().post_form(&url, &[("q", "a&b=c"), ("name", "张 三")]).unwrap();
+        assert_eq!(resp.status, 200);
+        assert_eq!(handle.join().unwrap(), "q=a%26b%3Dc&name=%E5%BC%A0%20%E4%B8%89");
+    }
+
+    #[test]
+    fn parses_http_dates() {
+        let expected = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1445412480);
+        assert_eq!(parse_http_date("Wed, 21 Oct 2015 07:28:00 GMT"), Some(expected));
+        assert_eq!(parse_http_date("Wednesday, 21-Oct-15 07:28:00 GMT"), Some(expected));
+        assert_eq!(parse_http_date("not a date"), None);
+    }
+
+    ///本地测试服务器：依次以 statuses 中的状态码响应，返回实际收到的请求方法
+    ///
+    ///在 500ms 内没有新请求即结束，用于确认客户端没有多余的重试
+    fn serve_statuses(statuses: Vec<u16>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+        let handle = std::thread::spawn(move || {
+            let mut methods = Vec::new();
+            for status in statuses {
+                let req = match server.recv_timeout(std::time::Duration::from_millis(500)) {
+                    Ok(Some(req)) => req,
+                    _ => break,
+                };
+                methods.push(req.method().to_string());
+                req.respond(tiny_http::Response::from_string("body").with_status_code(status)).unwrap();
+            }
+            methods
+        });
+        (format!("http://127.0.0.1:{}/", port), handle)
+    }
+
+    #[test]
+    fn get_is_retried_after_server_errors() {
+        let (url, handle) = serve_statuses(vec![503, 500, 200]);
+        let client = HttpClient::new().with_retries(2, std::time::Duration::from_millis(10));
+
+        let resp = client.get(&url).unwrap();
+        assert_eq!(resp.status, 200);
+        assert_eq!(handle.join().unwrap(), vec!["GET", "GET", "GET"]);
+    }
+
+    #[test]
+    fn post_is_not_retried() {
+        let (url, handle) = serve_statuses(vec![503, 200]);
+        let client = HttpClient::new().with_retries(2, std::time::Duration::from_millis(10));
+
+        //非幂等请求不重试，5xx 作为普通响应返回
+        let resp = client.post_string(&url, "data").unwrap();
+        assert_eq!(resp.status, 503);
+        assert_eq!(handle.join().unwrap(), vec!["POST"]);
+    }
+
+    #[test]
+    fn repeated_headers_are_kept_with_lowercase_names() {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+        let handle = std::thread::spawn(move || {
+            let req = server.recv().unwrap();
+            let header = |raw: &str| raw.parse::<tiny_http::Header>().unwrap();
+            let resp = tiny_http::Response::from_string("ok")
+                .with_header(header("Set-Cookie: a=1"))
+                .with_header(header("X-Trace-Id: abc"))
+                .with_header(header("Set-Cookie: b=2"));
+            req.respond(resp).unwrap();
+        });
+
+        let resp = HttpClient::new().get(&format!("http://127.0.0.1:{}/", port)).unwrap();
+        handle.join().unwrap();
+
+        let cookies: Vec<&str> = resp
+            .headers
+            .iter()
+            .filter(|(name, _)| name == "set-cookie")
+            .map(|(_, value)| value.as_str())
+            .collect();
+        assert_eq!(cookies, vec!["a=1", "b=2"]);
+        assert!(resp.headers.iter().any(|(name, value)| name == "x-trace-id" && value == "abc"));
+        assert!(resp.headers.iter().all(|(name, _)| *name == name.to_lowercase()));
+    }
+}
@@ -19,6 +19,19 @@ pub const DEFAULT_USER_AGENT: &str = "rust-http-client/1.0";
 ///默认 Content-Type
 pub const DEFAULT_CONTENT_TYPE: &str = "application/json";
 
+//========================================
+//重试配置
+//========================================
+
+///默认首次重试间隔（毫秒），可通过 with_retries() 覆盖
+pub const RETRY_INITIAL_MS: u64 = 500;
+
+///重试间隔倍数（指数退避）
+pub const RETRY_MULTIPLIER: f64 = 1.5;
+
+///最大重试间隔（毫秒）
+pub const RETRY_MAX_MS: u64 = 30000;
+
 //========================================
 //服务端配置
 //========================================
@@ -32,6 +45,19 @@ pub const SERVER_DEFAULT_ADDR: &str = "0.0.0.0";
 ///工作线程数（0 表示使用 CPU 核心数）
 pub const WORKER_THREADS: usize = 4;
 
+//========================================
+//CORS 配置
+//========================================
+
+///预检请求允许的方法
+pub const CORS_ALLOW_METHODS: &str = "GET, POST, PUT, DELETE, OPTIONS";
+
+///预检请求允许的请求头（客户端未声明 Access-Control-Request-Headers 时使用）
+pub const CORS_ALLOW_HEADERS: &str = "Content-Type, Authorization";
+
+///预检结果缓存时间（秒）
+pub const CORS_MAX_AGE_SECS: u64 = 86400;
+
 //========================================
 //响应配置
 //========================================
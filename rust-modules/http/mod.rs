@@ -20,6 +20,7 @@
 //!- `config` - 配置项（超时、端口等）
 //!- `client` - HTTP 客户端
 //!- `server` - HTTP 服务端
+//!- `url` - URL 编码与查询字符串构造
 //!
 //!# 快速开始
 //!
@@ -61,10 +62,11 @@
 pub mod config;
 pub mod client;
 pub mod server;
+pub mod url;
 
 //========================================
 //便捷重导出
 //========================================
 
 pub use client::{HttpClient, Response, get, post_json};
-pub use server::{HttpServer, Request};
+pub use server::{HttpServer, Request, Completed};
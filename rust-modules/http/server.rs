@@ -27,8 +27,14 @@ pub struct Request {
     pub query: Option<String>,
     ///请求体
     pub body: String,
+    ///路径参数（路由中 `:name` 段匹配到的值）
+    params: std::collections::HashMap<String, String>,
     ///内部请求对象
     inner: tiny_http::Request,
+    ///响应时附加的响应头（如 CORS）
+    extra_headers: Vec<tiny_http::Header>,
+    ///已发送响应的状态码（0 表示尚未响应），供后置钩子读取
+    status: std::sync::Arc<std::sync::atomic::AtomicU16>,
 }
 
 impl Request {
@@ -50,10 +56,44 @@ impl Request {
             path,
             query,
             body,
+            params: std::collections::HashMap::new(),
             inner: req,
+            extra_headers: Vec::new(),
+            status: std::sync::Arc::new(std::sync::atomic::AtomicU16::new(0)),
         }
     }
 
+    ///读取请求头（不区分大小写）
+    pub fn header(&self, name: &str) -> Option<String> {
+        self.inner
+            .headers()
+            .iter()
+            .find(|h| h.field.as_str().as_str().eq_ignore_ascii_case(name))
+            .map(|h| h.value.as_str().to_string())
+    }
+
+    ///获取路径参数（如路由 `/users/:id` 匹配 `/users/42` 时 `param("id")` 为 "42"）
+    ///
+    ///参数值已做百分号解码
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(|v| v.as_str())
+    }
+
+    ///添加响应头（在后续 respond_* 调用时一并发送，常用于中间件中附加请求 ID 等）
+    pub fn add_response_header(&mut self, name: &str, value: &str) {
+        self.extra_headers.extend(make_header(name, value));
+    }
+
+    ///附加响应头并发送响应
+    fn send<R: std::io::Read>(self, mut response: tiny_http::Response<R>) {
+        let Self { inner, extra_headers, status, .. } = self;
+        for header in extra_headers {
+            response.add_header(header);
+        }
+        status.store(response.status_code().0, std::sync::atomic::Ordering::Relaxed);
+        let _ = inner.respond(response);
+    }
+
     ///解析 JSON 请求体
     pub fn json<T: serde::de::DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
         serde_json::from_str(&self.body)
@@ -66,7 +106,7 @@ impl Request {
             .with_header(
                 tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"text/plain; charset=utf-8"[..]).unwrap()
             );
-        let _ = self.inner.respond(response);
+        self.send(response);
     }
 
     ///响应请求（JSON）
@@ -77,7 +117,7 @@ impl Request {
             .with_header(
                 tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap()
             );
-        let _ = self.inner.respond(response);
+        self.send(response);
     }
 
     ///响应请求（HTML）
@@ -87,7 +127,111 @@ impl Request {
             .with_header(
                 tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"text/html; charset=utf-8"[..]).unwrap()
             );
-        let _ = self.inner.respond(response);
+        self.send(response);
+    }
+
+    ///响应请求（二进制数据，自定义 Content-Type）
+    pub fn respond_bytes(self, status: u16, content_type: &str, data: &[u8]) {
+        let mut response = tiny_http::Response::from_data(data.to_vec())
+            .with_status_code(status);
+        if let Ok(header) = tiny_http::Header::from_bytes(&b"Content-Type"[..], content_type.as_bytes()) {
+            response = response.with_header(header);
+        }
+        self.send(response);
+    }
+
+    ///响应请求（文件下载）
+    ///
+    ///根据扩展名推断 Content-Type，并附带 `Content-Disposition: attachment`
+    ///文件不存在或无法打开时响应 404
+    pub fn respond_file(self, path: &str) {
+        let file = match std::fs::File::open(path) {
+            Ok(f) => f,
+            Err(_) => {
+                self.respond_text(404, "Not Found");
+                return;
+            }
+        };
+
+        let file_path = std::path::Path::new(path);
+        let filename = file_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("download");
+        let content_type = guess_content_type(file_path);
+
+        //文件内容以流的方式发送
+        let mut response = tiny_http::Response::from_file(file)
+            .with_header(
+                tiny_http::Header::from_bytes(&b"Content-Type"[..], content_type.as_bytes()).unwrap()
+            );
+        let disposition = content_disposition(filename);
+        if let Ok(header) = tiny_http::Header::from_bytes(&b"Content-Disposition"[..], disposition.as_bytes()) {
+            response = response.with_header(header);
+        }
+        self.send(response);
+    }
+}
+
+///构造响应头（名称或值非法时返回 None）
+fn make_header(name: &str, value: &str) -> Option<tiny_http::Header> {
+    tiny_http::Header::from_bytes(name.as_bytes(), value.as_bytes()).ok()
+}
+
+///构造 `Content-Disposition: attachment` 头的值
+///
+///`filename` 为 ASCII 兜底名：转义 `"` 和 `\`，控制字符（含 CR/LF）和非 ASCII 字符替换为 `_`；
+///文件名含非 ASCII 字符时追加 RFC 5987 编码的 `filename*`
+fn content_disposition(filename: &str) -> String {
+    let mut fallback = String::with_capacity(filename.len());
+    for c in filename.chars() {
+        match c {
+            '"' | '\\' => {
+                fallback.push('\\');
+                fallback.push(c);
+            }
+            c if c.is_ascii() && !c.is_ascii_control() => fallback.push(c),
+            _ => fallback.push('_'),
+        }
+    }
+
+    if filename.is_ascii() {
+        format!("attachment; filename=\"{}\"", fallback)
+    } else {
+        format!(
+            "attachment; filename=\"{}\"; filename*=UTF-8''{}",
+            fallback,
+            super::url::urlencode(filename)
+        )
+    }
+}
+
+///根据文件扩展名推断 Content-Type
+fn guess_content_type(path: &std::path::Path) -> &'static str {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+
+    match ext.as_str() {
+        "html" | "htm" => "text/html; charset=utf-8",
+        "txt" => "text/plain; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" => "application/javascript",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "ico" => "image/x-icon",
+        "webp" => "image/webp",
+        "mp3" => "audio/mpeg",
+        "mp4" => "video/mp4",
+        _ => "application/octet-stream",
     }
 }
 
@@ -98,6 +242,27 @@ impl Request {
 ///路由处理器
 pub type Handler = Box<dyn Fn(Request) + Send + Sync>;
 
+///前置钩子（只读访问请求，如记录日志）
+pub type BeforeHook = Box<dyn Fn(&Request) + Send + Sync>;
+
+///后置钩子（请求处理完成后调用，如记录状态码和耗时）
+pub type AfterHook = Box<dyn Fn(&Completed) + Send + Sync>;
+
+///已处理完成的请求摘要，传给后置钩子
+pub struct Completed {
+    ///请求方法
+    pub method: String,
+    ///请求路径
+    pub path: String,
+    ///响应状态码（处理器未响应时为 500）
+    pub status: u16,
+    ///处理耗时
+    pub elapsed: std::time::Duration,
+}
+
+///中间件：返回 Some(请求) 继续处理，已自行响应时返回 None 中断后续处理
+pub type Middleware = Box<dyn Fn(Request) -> Option<Request> + Send + Sync>;
+
 ///路由条目
 struct Route {
     method: String,
@@ -115,6 +280,16 @@ pub struct HttpServer {
     routes: Vec<Route>,
     ///监听端口
     port: u16,
+    ///CORS 允许的来源（None 表示不启用 CORS）
+    cors_origins: Option<Vec<String>>,
+    ///前置钩子（按注册顺序执行）
+    before_hooks: Vec<BeforeHook>,
+    ///后置钩子（按注册顺序执行）
+    after_hooks: Vec<AfterHook>,
+    ///中间件（按注册顺序执行，在前置钩子之后）
+    middlewares: Vec<Middleware>,
+    ///未匹配任何路由时的处理器（None 表示使用默认的 404 文本响应）
+    not_found: Option<Handler>,
 }
 
 impl HttpServer {
@@ -123,6 +298,11 @@ impl HttpServer {
         Self {
             routes: Vec::new(),
             port,
+            cors_origins: None,
+            before_hooks: Vec::new(),
+            after_hooks: Vec::new(),
+            middlewares: Vec::new(),
+            not_found: None,
         }
     }
 
@@ -131,6 +311,84 @@ impl HttpServer {
         Self::bind(config::SERVER_DEFAULT_PORT)
     }
 
+    ///启用 CORS，仅允许指定来源（如 "http://localhost:3000"）
+    ///
+    ///匹配的路由响应会附加 Access-Control-Allow-* 响应头，
+    ///OPTIONS 预检请求自动响应 204
+    pub fn with_cors(mut self, origins: &[&str]) -> Self {
+        self.cors_origins = Some(origins.iter().map(|o| o.to_string()).collect());
+        self
+    }
+
+    ///启用 CORS，允许任意来源（`*`，适合开发环境）
+    pub fn cors_any(self) -> Self {
+        self.with_cors(&["*"])
+    }
+
+    ///注册前置钩子：每个请求在路由查找前都会调用（包括 404/405 和 CORS 预检请求），
+    ///如记录方法和路径；此时路径参数尚未解析，param() 返回 None
+    pub fn before<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Request) + Send + Sync + 'static,
+    {
+        self.before_hooks.push(Box::new(f));
+        self
+    }
+
+    ///注册后置钩子：每个请求处理完成后调用，可读取最终状态码和耗时（如访问日志）
+    ///
+    ///# 示例
+    ///```rust
+    ///server.after(|done| {
+    ///    println!("{} {} {} {:?}", done.method, done.path, done.status, done.elapsed);
+    ///})
+    ///```
+    pub fn after<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Completed) + Send + Sync + 'static,
+    {
+        self.after_hooks.push(Box::new(f));
+        self
+    }
+
+    ///注册中间件：可修改请求（如附加响应头）或直接响应以中断处理（如鉴权失败）
+    ///
+    ///返回 Some(req) 交给下一个中间件/路由处理器，返回 None 表示已响应
+    ///
+    ///# 示例
+    ///```rust
+    ///server.wrap(|req| {
+    ///    if req.path.starts_with("/admin") && req.header("Authorization").is_none() {
+    ///        req.respond_text(401, "Unauthorized");
+    ///        return None;
+    ///    }
+    ///    Some(req)
+    ///})
+    ///```
+    pub fn wrap<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Request) -> Option<Request> + Send + Sync + 'static,
+    {
+        self.middlewares.push(Box::new(f));
+        self
+    }
+
+    ///设置未匹配任何路由时的处理器（默认响应 404 "Not Found" 纯文本）
+    ///
+    ///# 示例
+    ///```rust
+    ///server.not_found(|req| {
+    ///    req.respond_json(404, &serde_json::json!({"error": "not found"}));
+    ///})
+    ///```
+    pub fn not_found<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(Request) + Send + Sync + 'static,
+    {
+        self.not_found = Some(Box::new(handler));
+        self
+    }
+
     ///注册 GET 路由
     pub fn get<F>(mut self, path: &str, handler: F) -> Self
     where
@@ -184,46 +442,381 @@ impl HttpServer {
     }
 
     ///启动服务端
+    ///
+    ///启动 config::WORKER_THREADS 个工作线程（0 表示 CPU 核心数）并发处理请求，
+    ///单个处理器耗时较长时不会阻塞其他请求；处理器 panic 时该请求响应 500，工作线程继续运行
     pub fn run(self) {
         let addr = format!("{}:{}", config::SERVER_DEFAULT_ADDR, self.port);
-        let server = tiny_http::Server::http(&addr).expect("启动 HTTP 服务端失败");
+        let server = std::sync::Arc::new(tiny_http::Server::http(&addr).expect("启动 HTTP 服务端失败"));
         println!("HTTP 服务端已启动，监听 http://{}", addr);
 
-        let routes = std::sync::Arc::new(self.routes);
+        let workers = if config::WORKER_THREADS == 0 {
+            std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+        } else {
+            config::WORKER_THREADS
+        };
 
-        for request in server.incoming_requests() {
-            let req = Request::from_tiny(request);
-            let method = req.method.clone();
-            let path = req.path.clone();
+        let this = std::sync::Arc::new(self);
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                let server = std::sync::Arc::clone(&server);
+                let this = std::sync::Arc::clone(&this);
+                std::thread::spawn(move || {
+                    while let Ok(request) = server.recv() {
+                        //处理器 panic 时请求在栈展开中被丢弃，tiny_http 自动响应 500
+                        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| this.handle(request)));
+                        if result.is_err() {
+                            eprintln!("请求处理器 panic，已响应 500");
+                        }
+                    }
+                })
+            })
+            .collect();
 
-            //查找匹配的路由
-            let mut found = false;
-            for route in routes.iter() {
-                if route.method == method && Self::match_path(&route.path, &path) {
-                    (route.handler)(req);
-                    found = true;
-                    break;
-                }
+        for handle in handles {
+            let _ = handle.join();
+        }
+    }
+
+    ///处理单个请求：前置钩子 -> 分发 -> 后置钩子
+    fn handle(&self, request: tiny_http::Request) {
+        let started = std::time::Instant::now();
+        let req = Request::from_tiny(request);
+        for hook in &self.before_hooks {
+            hook(&req);
+        }
+
+        let method = req.method.clone();
+        let path = req.path.clone();
+        let status = std::sync::Arc::clone(&req.status);
+        self.dispatch(req);
+
+        if !self.after_hooks.is_empty() {
+            //处理器未响应时 tiny_http 在丢弃请求时自动响应 500
+            let status = match status.load(std::sync::atomic::Ordering::Relaxed) {
+                0 => 500,
+                code => code,
+            };
+            let completed = Completed { method, path, status, elapsed: started.elapsed() };
+            for hook in &self.after_hooks {
+                hook(&completed);
+            }
+        }
+    }
+
+    ///分发请求：CORS 预检、路由匹配、405、404
+    fn dispatch(&self, mut req: Request) {
+        let method = req.method.clone();
+        let path = req.path.clone();
+
+        //CORS 处理
+        if let Some(ref origins) = self.cors_origins {
+            req.extra_headers = Self::cors_headers(origins, &req);
+            if method == "OPTIONS" {
+                Self::respond_preflight(req);
+                return;
+            }
+        }
+
+        //查找匹配的路由
+        let matched = self
+            .routes
+            .iter()
+            .filter(|r| r.method == method)
+            .find_map(|r| Self::match_path(&r.path, &path).map(|params| (r, params)));
+        if let Some((route, params)) = matched {
+            req.params = params;
+            let mut next = Some(req);
+            for middleware in &self.middlewares {
+                next = match next {
+                    Some(req) => middleware(req),
+                    None => break,
+                };
+            }
+            if let Some(req) = next {
+                (route.handler)(req);
             }
+            return;
+        }
+
+        //路径存在但方法不匹配：405
+        let allowed = Self::allowed_methods(&self.routes, &path);
+        if !allowed.is_empty() {
+            Self::respond_method_not_allowed(req, &allowed);
+            return;
+        }
+
+        //没有任何路由匹配：404
+        match self.not_found {
+            Some(ref handler) => handler(req),
+            None => req.respond_text(404, "Not Found"),
+        }
+    }
 
-            if !found {
-                //404 处理（请求已被消费，需要重新创建响应）
-                //由于 req 已经移动，这里无法响应 404
-                //实际使用中建议添加默认路由
+    ///获取指定路径上已注册的所有方法（去重，保持注册顺序）
+    fn allowed_methods(routes: &[Route], path: &str) -> Vec<String> {
+        let mut methods: Vec<String> = Vec::new();
+        for route in routes {
+            if Self::match_path(&route.path, path).is_some() && !methods.contains(&route.method) {
+                methods.push(route.method.clone());
             }
         }
+        methods
+    }
+
+    ///响应 405 Method Not Allowed，并通过 Allow 头列出支持的方法
+    fn respond_method_not_allowed(mut req: Request, allowed: &[String]) {
+        req.extra_headers.extend(make_header("Allow", &allowed.join(", ")));
+        req.respond_text(405, "Method Not Allowed");
     }
 
-    ///路径匹配
-    fn match_path(pattern: &str, path: &str) -> bool {
-        //简单匹配，支持 * 通配符
+    ///根据请求的 Origin 生成 CORS 响应头（来源不在允许列表时返回空）
+    fn cors_headers(origins: &[String], req: &Request) -> Vec<tiny_http::Header> {
+        let allow_origin = if origins.iter().any(|o| o == "*") {
+            "*".to_string()
+        } else {
+            match req.header("Origin") {
+                Some(origin) if origins.contains(&origin) => origin,
+                _ => return Vec::new(),
+            }
+        };
+
+        let allow_headers = req
+            .header("Access-Control-Request-Headers")
+            .unwrap_or_else(|| config::CORS_ALLOW_HEADERS.to_string());
+
+        let mut headers = vec![
+            make_header("Access-Control-Allow-Origin", &allow_origin),
+            make_header("Access-Control-Allow-Methods", config::CORS_ALLOW_METHODS),
+            make_header("Access-Control-Allow-Headers", &allow_headers),
+        ];
+        if allow_origin != "*" {
+            //响应随 Origin 变化，避免缓存混用
+            headers.push(make_header("Vary", "Origin"));
+        }
+        headers.into_iter().flatten().collect()
+    }
+
+    ///响应 OPTIONS 预检请求（204 No Content）
+    fn respond_preflight(mut req: Request) {
+        let max_age = config::CORS_MAX_AGE_SECS.to_string();
+        req.extra_headers.extend(make_header("Access-Control-Max-Age", &max_age));
+        let response = tiny_http::Response::empty(204);
+        req.send(response);
+    }
+
+    ///路径匹配，匹配成功时返回路径参数
+    ///
+    ///支持：
+    ///- 精确匹配：`/users`
+    ///- 通配符：`*` 匹配所有路径，`/static/*` 匹配前缀
+    ///- 路径参数：`/users/:id/posts/:pid`，按段比较，段数必须相同（以 `/*` 结尾时允许更多段）
+    fn match_path(pattern: &str, path: &str) -> Option<std::collections::HashMap<String, String>> {
+        let mut params = std::collections::HashMap::new();
         if pattern == "*" {
-            return true;
+            return Some(params);
         }
-        if pattern.ends_with("/*") {
-            let prefix = &pattern[..pattern.len() - 2];
-            return path.starts_with(prefix);
+
+        let (body, wildcard) = match pattern.strip_suffix("/*") {
+            Some(prefix) => (prefix, true),
+            None => (pattern, false),
+        };
+
+        //不含参数时保持原有的字符串匹配
+        if !body.contains("/:") {
+            let matched = if wildcard { path.starts_with(body) } else { pattern == path };
+            return matched.then_some(params);
+        }
+
+        let pattern_segments: Vec<&str> = body.split('/').collect();
+        let path_segments: Vec<&str> = path.split('/').collect();
+        let count_ok = if wildcard {
+            path_segments.len() >= pattern_segments.len()
+        } else {
+            path_segments.len() == pattern_segments.len()
+        };
+        if !count_ok {
+            return None;
+        }
+
+        for (pattern_segment, path_segment) in pattern_segments.iter().zip(&path_segments) {
+            match pattern_segment.strip_prefix(':') {
+                Some(name) => {
+                    if path_segment.is_empty() {
+                        return None;
+                    }
+                    //路径中的 + 不表示空格，先转义再解码
+                    let value = super::url::urldecode(&path_segment.replace('+', "%2B"))
+                        .unwrap_or_else(|_| path_segment.to_string());
+                    params.insert(name.to_string(), value);
+                }
+                None if pattern_segment == path_segment => {}
+                None => return None,
+            }
         }
-        pattern == path
+        Some(params)
+    }
+}
+
+//========================================
+//测试
+//========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///在空闲端口上后台启动服务端，等待可连接后返回端口
+    fn spawn_server(server: impl FnOnce(u16) -> HttpServer) -> u16 {
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let server = server(port);
+        std::thread::spawn(move || server.run());
+        for _ in 0..100 {
+            if std::net::TcpStream::connect(("127.0.0.1", port)).is_ok() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        port
+    }
+
+    fn params(pattern: &str, path: &str) -> Option<Vec<(String, String)>> {
+        HttpServer::match_path(pattern, path).map(|params| {
+            let mut params: Vec<_> = params.into_iter().collect();
+            params.sort();
+            params
+        })
+    }
+
+    #[test]
+    fn path_params_bind_by_segment() {
+        let matched = HttpServer::match_path("/users/:id", "/users/42").unwrap();
+        assert_eq!(matched.get("id").map(String::as_str), Some("42"));
+        assert_eq!(params("/users/:id", "/users/42/extra"), None);
+        assert_eq!(params("/users/:id", "/users/"), None);
+        assert_eq!(params("/users/:id", "/accounts/42"), None);
+
+        assert_eq!(
+            params("/users/:id/posts/:pid", "/users/7/posts/%E4%B8%AD+1"),
+            Some(vec![("id".to_string(), "7".to_string()), ("pid".to_string(), "中+1".to_string())])
+        );
+    }
+
+    #[test]
+    fn static_and_wildcard_patterns_still_match() {
+        assert_eq!(params("/users", "/users"), Some(vec![]));
+        assert_eq!(params("/users", "/users/1"), None);
+        assert_eq!(params("*", "/anything"), Some(vec![]));
+        assert_eq!(params("/static/*", "/static/css/app.css"), Some(vec![]));
+        assert_eq!(
+            params("/files/:bucket/*", "/files/b1/a/b.txt"),
+            Some(vec![("bucket".to_string(), "b1".to_string())])
+        );
+    }
+
+    #[test]
+    fn request_param_is_available_in_handler() {
+        let port = spawn_server(|port| {
+            HttpServer::bind(port).get("/users/:id", |req| {
+                let id = req.param("id").unwrap_or("").to_string();
+                req.respond_text(200, &id);
+            })
+        });
+        let client = super::super::client::HttpClient::new();
+        let base = format!("http://127.0.0.1:{}", port);
+        assert_eq!(client.get(&format!("{}/users/42", base)).unwrap().text(), "42");
+        assert_eq!(client.get(&format!("{}/users/42/extra", base)).unwrap().status, 404);
+    }
+
+    #[test]
+    fn slow_handlers_run_concurrently() {
+        let port = spawn_server(|port| {
+            HttpServer::bind(port).get("/slow", |req| {
+                std::thread::sleep(std::time::Duration::from_millis(200));
+                req.respond_text(200, "ok");
+            })
+        });
+        let url = format!("http://127.0.0.1:{}/slow", port);
+
+        let started = std::time::Instant::now();
+        let requests: Vec<_> = (0..4)
+            .map(|_| {
+                let url = url.clone();
+                std::thread::spawn(move || super::super::client::get(&url).unwrap().status)
+            })
+            .collect();
+        for request in requests {
+            assert_eq!(request.join().unwrap(), 200);
+        }
+        //串行处理需要 800ms
+        let elapsed = started.elapsed();
+        assert!(elapsed < std::time::Duration::from_millis(600), "{:?}", elapsed);
+    }
+
+    #[test]
+    fn panicking_handler_responds_500_and_keeps_workers() {
+        let port = spawn_server(|port| {
+            HttpServer::bind(port)
+                .get("/panic", |_req| panic!("handler failed"))
+                .get("/ok", |req| req.respond_text(200, "ok"))
+        });
+        let client = super::super::client::HttpClient::new();
+        let base = format!("http://127.0.0.1:{}", port);
+
+        //请求次数多于工作线程数，panic 若导致线程退出则后续请求无人处理
+        let workers = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1).max(config::WORKER_THREADS);
+        for _ in 0..workers + 2 {
+            assert_eq!(client.get(&format!("{}/panic", base)).unwrap().status, 500);
+        }
+        assert_eq!(client.get(&format!("{}/ok", base)).unwrap().status, 200);
+    }
+
+    #[test]
+    fn hooks_see_unmatched_requests_and_final_status() {
+        let log = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let before_log = std::sync::Arc::clone(&log);
+        let after_log = std::sync::Arc::clone(&log);
+        let port = spawn_server(move |port| {
+            HttpServer::bind(port)
+                .get("/ok", |req| req.respond_text(200, "ok"))
+                .before(move |req| before_log.lock().unwrap().push(format!("> {} {}", req.method, req.path)))
+                .after(move |done| after_log.lock().unwrap().push(format!("< {} {}", done.path, done.status)))
+        });
+
+        let client = super::super::client::HttpClient::new();
+        let base = format!("http://127.0.0.1:{}", port);
+        assert_eq!(client.get(&format!("{}/ok", base)).unwrap().status, 200);
+        assert_eq!(client.get(&format!("{}/missing", base)).unwrap().status, 404);
+        assert_eq!(client.delete(&format!("{}/ok", base)).unwrap().status, 405);
+
+        //后置钩子在响应发出后执行，稍等片刻；多个工作线程下各条记录的先后顺序不固定
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        let mut entries = log.lock().unwrap().clone();
+        entries.sort();
+        assert_eq!(
+            entries,
+            vec!["< /missing 404", "< /ok 200", "< /ok 405", "> DELETE /ok", "> GET /missing", "> GET /ok"]
+        );
+    }
+
+    #[test]
+    fn content_disposition_escapes_quotes_and_line_breaks() {
+        assert_eq!(content_disposition("report.pdf"), "attachment; filename=\"report.pdf\"");
+        assert_eq!(
+            content_disposition("a\"b\\c.txt"),
+            "attachment; filename=\"a\\\"b\\\\c.txt\""
+        );
+        assert_eq!(
+            content_disposition("x\r\nSet-Cookie: y"),
+            "attachment; filename=\"x__Set-Cookie: y\""
+        );
+    }
+
+    #[test]
+    fn content_disposition_adds_rfc5987_name_for_non_ascii() {
+        assert_eq!(
+            content_disposition("报告.pdf"),
+            "attachment; filename=\"__.pdf\"; filename*=UTF-8''%E6%8A%A5%E5%91%8A.pdf"
+        );
     }
 }
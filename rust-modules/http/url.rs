@@ -0,0 +1,147 @@
+//!URL 编码模块
+//!
+//!提供百分号编码/解码和查询字符串构造，纯标准库实现。
+
+//========================================
+//编码/解码
+//========================================
+
+///百分号编码（RFC 3986，保留 A-Z a-z 0-9 - _ . ~，其余按 UTF-8 字节编码为 %XX）
+pub fn urlencode(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                result.push(byte as char);
+            }
+            _ => result.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    result
+}
+
+///百分号解码（`+` 按空格处理，兼容表单编码）
+///
+///%XX 格式错误或解码结果不是合法 UTF-8 时返回错误
+pub fn urldecode(s: &str) -> Result<String, String> {
+    let bytes = s.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = bytes
+                    .get(i + 1..i + 3)
+                    .filter(|h| h.iter().all(u8::is_ascii_hexdigit))
+                    .and_then(|h| std::str::from_utf8(h).ok())
+                    .and_then(|h| u8::from_str_radix(h, 16).ok())
+                    .ok_or_else(|| format!("无效的百分号编码: {}", s))?;
+                result.push(hex);
+                i += 3;
+            }
+            b'+' => {
+                result.push(b' ');
+                i += 1;
+            }
+            b => {
+                result.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(result).map_err(|e| format!("解码结果不是有效的 UTF-8: {}", e))
+}
+
+//========================================
+//查询字符串
+//========================================
+
+///构造查询字符串（键和值均做百分号编码），如 "a=1&b=x%20y"
+pub fn build_query(params: &[(&str, &str)]) -> String {
+    params
+        .iter()
+        .map(|(k, v)| format!("{}={}", urlencode(k), urlencode(v)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+///在 URL 后追加查询参数
+///
+///URL 已含 `?` 时以 `&` 连接；含 `#` 片段时参数插入到片段之前
+pub fn with_query(url: &str, params: &[(&str, &str)]) -> String {
+    if params.is_empty() {
+        return url.to_string();
+    }
+
+    let (base, fragment) = match url.find('#') {
+        Some(pos) => (&url[..pos], &url[pos..]),
+        None => (url, ""),
+    };
+    let separator = if !base.contains('?') {
+        "?"
+    } else if base.ends_with('?') || base.ends_with('&') {
+        ""
+    } else {
+        "&"
+    };
+
+    format!("{}{}{}{}", base, separator, build_query(params), fragment)
+}
+
+///解析查询字符串为键值对列表（解码失败的项保留原文）
+pub fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (k, v) = pair.split_once('=').unwrap_or((pair, ""));
+            (
+                urldecode(k).unwrap_or_else(|_| k.to_string()),
+                urldecode(v).unwrap_or_else(|_| v.to_string()),
+            )
+        })
+        .collect()
+}
+
+//========================================
+//测试
+//========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_query_encodes_reserved_characters() {
+        assert_eq!(build_query(&[("q", "a&b=c")]), "q=a%26b%3Dc");
+        assert_eq!(build_query(&[("k y", "1+1 = 2")]), "k%20y=1%2B1%20%3D%202");
+        assert_eq!(build_query(&[("名字", "张三")]), "%E5%90%8D%E5%AD%97=%E5%BC%A0%E4%B8%89");
+    }
+
+    #[test]
+    fn injected_fields_survive_round_trip() {
+        let params = [("user", "bob&admin=true"), ("note", "x+y z")];
+        let query = build_query(&params);
+        assert_eq!(
+            parse_query(&query),
+            vec![
+                ("user".to_string(), "bob&admin=true".to_string()),
+                ("note".to_string(), "x+y z".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn with_query_respects_existing_query_and_fragment() {
+        assert_eq!(with_query("http://h/p", &[("a", "1")]), "http://h/p?a=1");
+        assert_eq!(with_query("http://h/p?x=0", &[("a", "b c")]), "http://h/p?x=0&a=b%20c");
+        assert_eq!(with_query("http://h/p#top", &[("a", "1")]), "http://h/p?a=1#top");
+    }
+
+    #[test]
+    fn urldecode_rejects_malformed_escapes() {
+        assert_eq!(urldecode("a%20b+c").unwrap(), "a b c");
+        assert!(urldecode("%zz").is_err());
+        assert!(urldecode("%E5%90").is_err());
+    }
+}
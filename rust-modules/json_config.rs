@@ -1,6 +1,6 @@
 //!JSON 配置模块
 //!
-//!提供 JSON 配置文件的读取、写入、修改功能。
+//!提供 JSON 配置文件的读取、写入、修改功能。保存时先写临时文件再重命名，写入中途崩溃不会损坏原文件。
 //!
 //!依赖：serde_json（使用时查询最新版本：https://crates.io/crates/serde_json）
 //!
@@ -26,6 +26,9 @@
 //!    #[derive(serde::Deserialize)]
 //!    struct Config { name: String, port: u16 }
 //!    let config: Config = json_config::load_as("config.json").unwrap();
+//!
+//!    //读取并替换 "${DB_PASS}" 等环境变量引用
+//!    let config = json_config::load_with_env("config.json").unwrap();
 //!}
 //!```
 //!
@@ -80,10 +83,12 @@ impl JsonConfig {
     //========================================
 
     ///获取指定路径的值（支持点分隔路径，如 "server.port"）
+    ///
+    ///数组节点下的数字段作为索引，如 "servers.0.port"；索引越界返回 None
     pub fn get(&self, path: &str) -> Option<&serde_json::Value> {
         let mut current = &self.data;
         for key in path.split('.') {
-            current = current.get(key)?;
+            current = child(current, key)?;
         }
         Some(current)
     }
@@ -118,39 +123,80 @@ impl JsonConfig {
     //========================================
 
     ///设置指定路径的值（支持点分隔路径）
+    ///
+    ///中间路径不存在时自动创建对象；中间路径已存在但不是对象（如字符串）时会被替换为新对象，
+    ///需要报错而不是覆盖时使用 set_strict()
+    ///
+    ///数组节点下的数字段作为索引：索引小于长度时替换该元素，等于长度时追加，大于长度时返回错误
     pub fn set<T: serde::Serialize>(&mut self, path: &str, value: T) -> Result<(), String> {
         let json_value = serde_json::to_value(value).map_err(|e| format!("序列化失败: {}", e))?;
         let keys: Vec<&str> = path.split('.').collect();
-        self.set_nested(&keys, json_value)
+        self.set_nested(&keys, json_value, false)
+    }
+
+    ///设置指定路径的值（严格模式）
+    ///
+    ///中间路径已存在但不是对象时返回错误，不会覆盖原有值
+    pub fn set_strict<T: serde::Serialize>(&mut self, path: &str, value: T) -> Result<(), String> {
+        let json_value = serde_json::to_value(value).map_err(|e| format!("序列化失败: {}", e))?;
+        let keys: Vec<&str> = path.split('.').collect();
+        self.set_nested(&keys, json_value, true)
     }
 
     ///设置嵌套值
-    fn set_nested(&mut self, keys: &[&str], value: serde_json::Value) -> Result<(), String> {
+    fn set_nested(&mut self, keys: &[&str], value: serde_json::Value, strict: bool) -> Result<(), String> {
         if keys.is_empty() {
             return Err("路径不能为空".to_string());
         }
 
         let mut current = &mut self.data;
         for (i, key) in keys.iter().enumerate() {
-            if i == keys.len() - 1 {
-                if let Some(obj) = current.as_object_mut() {
-                    obj.insert(key.to_string(), value);
+            let last = i == keys.len() - 1;
+
+            //数组节点：数字段作为索引
+            let index = if current.is_array() { key.parse::<usize>().ok() } else { None };
+            if let Some(index) = index {
+                let arr = current.as_array_mut().ok_or("路径无效".to_string())?;
+                if index > arr.len() {
+                    return Err(format!("数组索引越界: \"{}\"（长度 {}）", keys[..=i].join("."), arr.len()));
+                }
+                if last {
+                    if index == arr.len() {
+                        arr.push(value);
+                    } else {
+                        arr[index] = value;
+                    }
                     return Ok(());
                 }
-                return Err("父路径不是对象".to_string());
+                if index == arr.len() {
+                    arr.push(serde_json::json!({}));
+                }
+                current = &mut arr[index];
+                continue;
             }
 
-            if current.get(key).is_none() {
-                if let Some(obj) = current.as_object_mut() {
-                    obj.insert(key.to_string(), serde_json::json!({}));
+            //当前节点不是对象：严格模式报错，否则替换为空对象
+            if !current.is_object() {
+                if strict {
+                    return Err(match i {
+                        0 => "根节点不是对象".to_string(),
+                        _ => format!("路径 \"{}\" 不是对象", keys[..i].join(".")),
+                    });
                 }
+                *current = serde_json::json!({});
+            }
+            let obj = current.as_object_mut().ok_or("路径无效".to_string())?;
+
+            if last {
+                obj.insert(key.to_string(), value);
+                return Ok(());
             }
-            current = current.get_mut(key).ok_or("路径无效".to_string())?;
+            current = obj.entry(key.to_string()).or_insert_with(|| serde_json::json!({}));
         }
         Ok(())
     }
 
-    ///删除指定路径的值
+    ///删除指定路径的值（数组元素删除后，后续元素前移）
     pub fn remove(&mut self, path: &str) -> Option<serde_json::Value> {
         let keys: Vec<&str> = path.split('.').collect();
         if keys.is_empty() {
@@ -160,13 +206,43 @@ impl JsonConfig {
         let mut current = &mut self.data;
         for (i, key) in keys.iter().enumerate() {
             if i == keys.len() - 1 {
+                if let Some(arr) = current.as_array_mut() {
+                    let index = key.parse::<usize>().ok().filter(|&index| index < arr.len())?;
+                    return Some(arr.remove(index));
+                }
                 return current.as_object_mut()?.remove(*key);
             }
-            current = current.get_mut(key)?;
+            current = child_mut(current, key)?;
         }
         None
     }
 
+    //========================================
+    //合并
+    //========================================
+
+    ///合并另一个配置（other 中的值优先）
+    ///
+    ///对象递归合并：两边都有的键继续向下合并，只在 other 中的键直接加入；
+    ///数组和标量整体替换，不做追加（如 {"a":[1]} 合并 {"a":[2]} 得到 {"a":[2]}）
+    ///
+    ///# 示例
+    ///```rust
+    ///let mut base = json_config::from_str(r#"{"a":{"x":1}}"#).unwrap();
+    ///base.merge(&json_config::from_str(r#"{"a":{"y":2}}"#).unwrap());
+    ///assert_eq!(base.inner(), &serde_json::json!({"a": {"x": 1, "y": 2}}));
+    ///```
+    pub fn merge(&mut self, other: &JsonConfig) {
+        merge_value(&mut self.data, &other.data);
+    }
+
+    ///加载 JSON 文件并合并到当前配置（规则同 merge）
+    pub fn merge_file(&mut self, path: &str) -> std::io::Result<()> {
+        let other = load(path)?;
+        self.merge(&other);
+        Ok(())
+    }
+
     //========================================
     //文件操作
     //========================================
@@ -175,14 +251,19 @@ impl JsonConfig {
     pub fn save(&self, path: &str) -> std::io::Result<()> {
         let content = serde_json::to_string(&self.data)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
-        std::fs::write(path, content)
+        write_atomic(path, content.as_bytes())
     }
 
     ///保存到文件（美化格式）
     pub fn save_pretty(&self, path: &str) -> std::io::Result<()> {
         let content = serde_json::to_string_pretty(&self.data)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
-        std::fs::write(path, content)
+        write_atomic(path, content.as_bytes())
+    }
+
+    ///保存到文件（美化格式，自定义缩进，如 "\t" 或 "    "）
+    pub fn save_pretty_with(&self, path: &str, indent: &str) -> std::io::Result<()> {
+        save_pretty_with(path, &self.data, indent)
     }
 }
 
@@ -198,6 +279,32 @@ pub fn load(path: &str) -> std::io::Result<JsonConfig> {
     Ok(JsonConfig::new(data))
 }
 
+///加载 JSON 配置文件并替换环境变量引用
+///
+///所有字符串值中的 `${VAR}` 会替换为环境变量 VAR 的值，
+///未定义的变量保持原样（如需报错请使用 load_with_env_strict）
+///
+///# 示例
+///```json
+///{ "password": "${DB_PASS}" }
+///```
+pub fn load_with_env(path: &str) -> std::io::Result<JsonConfig> {
+    let mut config = load(path)?;
+    expand_env_value(&mut config.data, false)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    Ok(config)
+}
+
+///加载 JSON 配置文件并替换环境变量引用（严格模式）
+///
+///遇到未定义的环境变量时返回 InvalidData 错误
+pub fn load_with_env_strict(path: &str) -> std::io::Result<JsonConfig> {
+    let mut config = load(path)?;
+    expand_env_value(&mut config.data, true)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    Ok(config)
+}
+
 ///加载 JSON 配置文件为指定类型
 pub fn load_as<T: serde::de::DeserializeOwned>(path: &str) -> std::io::Result<T> {
     let content = std::fs::read_to_string(path)?;
@@ -209,14 +316,24 @@ pub fn load_as<T: serde::de::DeserializeOwned>(path: &str) -> std::io::Result<T>
 pub fn save<T: serde::Serialize>(path: &str, data: &T) -> std::io::Result<()> {
     let content = serde_json::to_string(data)
         .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
-    std::fs::write(path, content)
+    write_atomic(path, content.as_bytes())
 }
 
 ///保存数据到 JSON 文件（美化格式）
 pub fn save_pretty<T: serde::Serialize>(path: &str, data: &T) -> std::io::Result<()> {
     let content = serde_json::to_string_pretty(data)
         .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
-    std::fs::write(path, content)
+    write_atomic(path, content.as_bytes())
+}
+
+///保存数据到 JSON 文件（美化格式，自定义缩进，如 "\t" 或 "    "）
+pub fn save_pretty_with<T: serde::Serialize>(path: &str, data: &T, indent: &str) -> std::io::Result<()> {
+    let mut content = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut content, formatter);
+    data.serialize(&mut serializer)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    write_atomic(path, &content)
 }
 
 ///从字符串解析 JSON 配置
@@ -229,3 +346,256 @@ pub fn from_str(json: &str) -> Result<JsonConfig, serde_json::Error> {
 pub fn new() -> JsonConfig {
     JsonConfig::empty()
 }
+
+//========================================
+//辅助函数
+//========================================
+
+///原子写入文件：先写入同目录临时文件再重命名覆盖目标，写入中途崩溃不会留下空文件或半截文件
+///
+///目标已存在时沿用其权限；重命名失败时删除临时文件并返回错误，原文件保持不变
+fn write_atomic(path: &str, content: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    ///临时文件序号，与进程号一起保证同一文件并发保存时临时文件名不冲突
+    static TMP_SEQ: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+    //目标为符号链接时替换其指向的文件，保留链接本身
+    let target = std::fs::canonicalize(path).unwrap_or_else(|_| std::path::PathBuf::from(path));
+    let dir = match target.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => std::path::PathBuf::from("."),
+    };
+    let file_name = target.file_name()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "无效的文件路径"))?;
+    let seq = TMP_SEQ.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let tmp = dir.join(format!(".{}.{}.{}.tmp", file_name.to_string_lossy(), std::process::id(), seq));
+
+    //写入并刷盘，确保重命名后的文件内容完整
+    fn write_synced(tmp: &std::path::Path, content: &[u8], target: &std::path::Path) -> std::io::Result<()> {
+        let mut file = std::fs::OpenOptions::new().write(true).create_new(true).open(tmp)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+        if let Ok(meta) = std::fs::metadata(target) {
+            std::fs::set_permissions(tmp, meta.permissions())?;
+        }
+        Ok(())
+    }
+
+    if let Err(e) = write_synced(&tmp, content, &target) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e);
+    }
+
+    if let Err(e) = std::fs::rename(&tmp, &target) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e);
+    }
+    Ok(())
+}
+
+///获取子节点（数组节点按数字索引，其余按键名）
+fn child<'a>(value: &'a serde_json::Value, key: &str) -> Option<&'a serde_json::Value> {
+    match value {
+        serde_json::Value::Array(arr) => arr.get(key.parse::<usize>().ok()?),
+        _ => value.get(key),
+    }
+}
+
+///获取子节点的可变引用（规则同 child）
+fn child_mut<'a>(value: &'a mut serde_json::Value, key: &str) -> Option<&'a mut serde_json::Value> {
+    match value {
+        serde_json::Value::Array(arr) => arr.get_mut(key.parse::<usize>().ok()?),
+        _ => value.get_mut(key),
+    }
+}
+
+///递归合并 JSON 值：对象按键合并，其余类型由 source 整体替换
+fn merge_value(target: &mut serde_json::Value, source: &serde_json::Value) {
+    match (target, source) {
+        (serde_json::Value::Object(target), serde_json::Value::Object(source)) => {
+            for (key, value) in source {
+                match target.get_mut(key) {
+                    Some(existing) => merge_value(existing, value),
+                    None => {
+                        target.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (target, source) => *target = source.clone(),
+    }
+}
+
+///递归替换 JSON 值中所有字符串的环境变量引用
+fn expand_env_value(value: &mut serde_json::Value, strict: bool) -> Result<(), String> {
+    match value {
+        serde_json::Value::String(s) => {
+            *s = expand_env_str(s, strict)?;
+        }
+        serde_json::Value::Array(arr) => {
+            for item in arr {
+                expand_env_value(item, strict)?;
+            }
+        }
+        serde_json::Value::Object(obj) => {
+            for (_, item) in obj.iter_mut() {
+                expand_env_value(item, strict)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+///替换字符串中的 `${VAR}` 引用
+fn expand_env_str(s: &str, strict: bool) -> Result<String, String> {
+    let mut result = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+
+        //没有闭合的 }，剩余部分原样保留
+        let end = match after.find('}') {
+            Some(end) => end,
+            None => {
+                result.push_str(&rest[start..]);
+                return Ok(result);
+            }
+        };
+
+        let name = &after[..end];
+        match std::env::var(name) {
+            Ok(val) => result.push_str(&val),
+            Err(_) if strict => return Err(format!("环境变量未定义: {}", name)),
+            Err(_) => result.push_str(&rest[start..start + 2 + end + 1]),
+        }
+        rest = &after[end + 1..];
+    }
+
+    result.push_str(rest);
+    Ok(result)
+}
+
+//========================================
+//测试
+//========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_combines_nested_objects() {
+        let mut base = from_str(r#"{"a":{"x":1}}"#).unwrap();
+        base.merge(&from_str(r#"{"a":{"y":2}}"#).unwrap());
+        assert_eq!(base.inner(), &serde_json::json!({"a": {"x": 1, "y": 2}}));
+    }
+
+    #[test]
+    fn merge_replaces_arrays_and_scalars() {
+        let mut base = from_str(r#"{"list":[1,2],"name":"base","nested":{"keep":true,"port":80}}"#).unwrap();
+        base.merge(&from_str(r#"{"list":[3],"name":"override","nested":{"port":8080},"extra":null}"#).unwrap());
+        assert_eq!(
+            base.inner(),
+            &serde_json::json!({
+                "list": [3],
+                "name": "override",
+                "nested": {"keep": true, "port": 8080},
+                "extra": null
+            })
+        );
+    }
+
+    #[test]
+    fn merge_file_layers_overrides() {
+        let path = std::env::temp_dir().join(format!("json_config_merge_{}.json", std::process::id()));
+        std::fs::write(&path, r#"{"server":{"port":9000}}"#).unwrap();
+
+        let mut config = from_str(r#"{"server":{"host":"localhost","port":80}}"#).unwrap();
+        let result = config.merge_file(path.to_str().unwrap());
+        let _ = std::fs::remove_file(&path);
+
+        result.unwrap();
+        assert_eq!(config.get_str("server.host"), Some("localhost"));
+        assert_eq!(config.get_i64("server.port"), Some(9000));
+        assert!(config.merge_file("/nonexistent/config.json").is_err());
+    }
+
+    ///创建空的临时测试目录
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("json_config_{}_{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    ///序列化总是失败的值
+    struct Unserializable;
+
+    impl serde::Serialize for Unserializable {
+        fn serialize<S: serde::Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+            Err(serde::ser::Error::custom("不可序列化"))
+        }
+    }
+
+    ///目录中除 keep 以外的文件名（用于检查临时文件是否残留）
+    fn leftover_files(dir: &std::path::Path, keep: &str) -> Vec<String> {
+        std::fs::read_dir(dir).unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name != keep)
+            .collect()
+    }
+
+    #[test]
+    fn failed_serialization_leaves_original_file() {
+        let dir = temp_dir("bad_value");
+        let path = dir.join("config.json");
+        std::fs::write(&path, r#"{"keep":1}"#).unwrap();
+
+        assert!(save(path.to_str().unwrap(), &Unserializable).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), r#"{"keep":1}"#);
+        assert!(leftover_files(&dir, "config.json").is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn failed_rename_returns_error_and_cleans_up() {
+        let dir = temp_dir("rename_fail");
+        //目标是非空目录，重命名必然失败
+        let target = dir.join("config.json");
+        std::fs::create_dir(&target).unwrap();
+        std::fs::write(target.join("inner"), "x").unwrap();
+
+        assert!(save(target.to_str().unwrap(), &serde_json::json!({"a": 1})).is_err());
+        assert!(target.join("inner").exists());
+        assert!(leftover_files(&dir, "config.json").is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn concurrent_saves_use_distinct_temp_files() {
+        let dir = temp_dir("concurrent");
+        let path = dir.join("config.json");
+        let threads: Vec<_> = (0..8)
+            .map(|i| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..20 {
+                        save(path.to_str().unwrap(), &serde_json::json!({"writer": i})).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let config = load(path.to_str().unwrap()).unwrap();
+        assert!(config.get_i64("writer").is_some());
+        assert!(leftover_files(&dir, "config.json").is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
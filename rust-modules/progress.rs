@@ -37,6 +37,16 @@
 //!}
 //!```
 
+//========================================
+//配置常量
+//========================================
+
+///高频循环推荐的刷新频率（次/秒）
+pub const HIGH_FREQ_DRAW_RATE: u8 = 10;
+
+///自动降低刷新频率的总量阈值（total 达到此值时自动使用 HIGH_FREQ_DRAW_RATE）
+pub const AUTO_THROTTLE_THRESHOLD: u64 = 1_000_000;
+
 //========================================
 //进度条
 //========================================
@@ -51,12 +61,31 @@ impl ProgressBar {
     ///
     ///# 参数
     ///- total: 总量
+    ///
+    ///total 达到 AUTO_THROTTLE_THRESHOLD 时自动限制刷新频率
     pub fn new(total: u64) -> Self {
         let pb = indicatif::ProgressBar::new(total);
         pb.set_style(default_progress_style());
+        if total >= AUTO_THROTTLE_THRESHOLD {
+            pb.set_draw_target(indicatif::ProgressDrawTarget::stderr_with_hz(HIGH_FREQ_DRAW_RATE));
+        }
         Self { inner: pb }
     }
 
+    ///设置最大刷新频率（次/秒）
+    ///
+    ///在每秒调用上百万次 inc() 的循环中降低终端重绘开销，
+    ///推荐使用 HIGH_FREQ_DRAW_RATE（10）；传入 0 按 1 处理
+    ///
+    ///# 注意
+    ///会替换绘制目标，不要用于 MultiProgress 添加的进度条
+    pub fn with_draw_rate(self, per_sec: u8) -> Self {
+        //indicatif 在刷新频率为 0 时会 panic
+        let per_sec = per_sec.max(1);
+        self.inner.set_draw_target(indicatif::ProgressDrawTarget::stderr_with_hz(per_sec));
+        self
+    }
+
     ///创建带消息的进度条
     pub fn new_with_message(total: u64, msg: &str) -> Self {
         let pb = Self::new(total);
@@ -99,6 +128,23 @@ impl ProgressBar {
         self.inner.finish_and_clear();
     }
 
+    ///完成并显示统计摘要，如 "处理 10000 项，用时 1m20s (125/s)"
+    ///
+    ///项数取当前进度（而非 total），速率为整个过程的平均值
+    pub fn finish_with_summary(&self) {
+        let count = self.inner.position();
+        let elapsed = self.inner.elapsed();
+        let secs = elapsed.as_secs_f64();
+        let rate = if secs > 0.0 { count as f64 / secs } else { 0.0 };
+        let msg = format!(
+            "处理 {} 项，用时 {} ({}/s)",
+            count,
+            format_elapsed(elapsed),
+            format_rate(rate)
+        );
+        self.inner.finish_with_message(msg);
+    }
+
     ///放弃进度条（显示失败状态）
     pub fn abandon(&self) {
         self.inner.abandon();
@@ -310,6 +356,35 @@ pub fn multi() -> MultiProgress {
     MultiProgress::new()
 }
 
+//========================================
+//摘要格式化
+//========================================
+
+///格式化耗时（如 "850ms"、"42s"、"1m20s"、"2h5m"）
+fn format_elapsed(d: std::time::Duration) -> String {
+    let secs = d.as_secs();
+    if secs == 0 {
+        return format!("{}ms", d.as_millis());
+    }
+    let (h, m, s) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    if h > 0 {
+        format!("{}h{}m", h, m)
+    } else if m > 0 {
+        format!("{}m{}s", m, s)
+    } else {
+        format!("{}s", s)
+    }
+}
+
+///格式化速率（小于 10 时保留一位小数）
+fn format_rate(rate: f64) -> String {
+    if rate < 10.0 {
+        format!("{:.1}", rate)
+    } else {
+        format!("{:.0}", rate)
+    }
+}
+
 //========================================
 //默认样式
 //========================================
@@ -18,7 +18,7 @@
 //!
 //!fn main() {
 //!    for port in serial::list_ports().unwrap() {
-//!        println!("串口: {}", port);
+//!        println!("串口: {} {:?}", port, port.product);
 //!    }
 //!}
 //!```
@@ -51,11 +51,28 @@
 //!        .data_bits(serial::DataBits::Eight)
 //!        .stop_bits(serial::StopBits::One)
 //!        .parity(serial::Parity::None)
+//!        .flow_control(serial::FlowControl::Hardware)
 //!        .timeout(std::time::Duration::from_secs(1))
 //!        .open()
 //!        .unwrap();
 //!}
 //!```
+//!
+//!## 后台读取（设备主动上报数据）
+//!```rust
+//!mod serial;
+//!
+//!fn main() {
+//!    let port = serial::SerialPort::open("/dev/ttyUSB0", 115200).unwrap();
+//!
+//!    //后台线程按行读取，通过 channel 接收
+//!    let (reader, rx) = port.into_line_reader().unwrap();
+//!    for line in rx.iter().take(10) {
+//!        println!("收到: {}", String::from_utf8_lossy(&line));
+//!    }
+//!    reader.stop();
+//!}
+//!```
 
 use serial2::SerialPort as Serial2Port;
 
@@ -69,6 +86,12 @@ pub const DEFAULT_TIMEOUT_MS: u64 = 1000;
 ///默认读取缓冲区大小
 pub const DEFAULT_BUFFER_SIZE: usize = 1024;
 
+///默认行结束符
+pub const DEFAULT_LINE_TERMINATOR: u8 = b'\n';
+
+///后台读取线程的读取超时（毫秒），决定停止信号的响应速度
+pub const READER_POLL_INTERVAL_MS: u64 = 100;
+
 //========================================
 //配置枚举
 //========================================
@@ -130,15 +153,47 @@ impl From<Parity> for serial2::Parity {
     }
 }
 
+///流控制
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FlowControl {
+    ///无流控制
+    None,
+    ///硬件流控制（RTS/CTS）
+    Hardware,
+    ///软件流控制（XON/XOFF）
+    Software,
+}
+
+impl From<FlowControl> for serial2::FlowControl {
+    fn from(flow: FlowControl) -> Self {
+        match flow {
+            FlowControl::None => serial2::FlowControl::None,
+            FlowControl::Hardware => serial2::FlowControl::RtsCts,
+            FlowControl::Software => serial2::FlowControl::XonXoff,
+        }
+    }
+}
+
 //========================================
 //串口信息
 //========================================
 
 ///串口信息
-#[derive(Debug, Clone)]
+///
+///USB 描述信息目前仅在 Linux 上读取（来自 /sys/class/tty），
+///其他平台或非 USB 串口这些字段为 None
+#[derive(Debug, Clone, Default)]
 pub struct PortInfo {
     ///串口名称（如 /dev/ttyUSB0 或 COM1）
     pub name: String,
+    ///USB 厂商 ID
+    pub vid: Option<u16>,
+    ///USB 产品 ID
+    pub pid: Option<u16>,
+    ///厂商名称
+    pub manufacturer: Option<String>,
+    ///产品名称
+    pub product: Option<String>,
 }
 
 impl std::fmt::Display for PortInfo {
@@ -151,24 +206,83 @@ impl std::fmt::Display for PortInfo {
 //便捷函数
 //========================================
 
-///列出所有可用串口
+///列出所有可用串口（含 USB 厂商/产品信息，平台不支持时为 None）
 pub fn list_ports() -> Result<Vec<PortInfo>, String> {
     serial2::SerialPort::available_ports()
         .map(|ports| {
             ports.into_iter()
-                .map(|p| PortInfo {
-                    name: p.to_string_lossy().to_string(),
-                })
+                .map(|p| port_info(&p))
                 .collect()
         })
         .map_err(|e| format!("枚举串口失败: {}", e))
 }
 
+///按 USB 厂商 ID 和产品 ID 查找串口（返回第一个匹配项）
+///
+///# 示例
+///```rust
+/////查找 CH340 USB 转串口
+///if let Some(port) = serial::find_port_by_vid_pid(0x1a86, 0x7523).unwrap() {
+///    println!("找到: {}", port.name);
+///}
+///```
+pub fn find_port_by_vid_pid(vid: u16, pid: u16) -> Result<Option<PortInfo>, String> {
+    Ok(list_ports()?
+        .into_iter()
+        .find(|p| p.vid == Some(vid) && p.pid == Some(pid)))
+}
+
 ///快速打开串口
 pub fn open(port: &str, baud_rate: u32) -> Result<SerialPort, String> {
     SerialPort::open(port, baud_rate)
 }
 
+//========================================
+//USB 描述信息
+//========================================
+
+///根据串口路径构造串口信息
+fn port_info(path: &std::path::Path) -> PortInfo {
+    let mut info = PortInfo {
+        name: path.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+
+    #[cfg(target_os = "linux")]
+    {
+        //tty 设备的 device 链接（如 /sys/class/tty/ttyUSB0/device）指向 USB 接口目录，向上查找含 idVendor 的设备目录
+        if let Some(file_name) = path.file_name() {
+            let device = std::path::Path::new("/sys/class/tty").join(file_name).join("device");
+            if let Ok(device) = std::fs::canonicalize(device) {
+                if let Some(usb_dir) = device.ancestors().find(|dir| dir.join("idVendor").is_file()) {
+                    read_usb_info(usb_dir, &mut info);
+                }
+            }
+        }
+    }
+
+    info
+}
+
+///从 sysfs USB 设备目录读取厂商/产品信息，缺失的字段保持 None
+#[cfg(target_os = "linux")]
+fn read_usb_info(usb_dir: &std::path::Path, info: &mut PortInfo) {
+    let read = |name: &str| -> Option<String> {
+        let text = std::fs::read_to_string(usb_dir.join(name)).ok()?;
+        let text = text.trim();
+        if text.is_empty() {
+            None
+        } else {
+            Some(text.to_string())
+        }
+    };
+
+    info.vid = read("idVendor").and_then(|v| u16::from_str_radix(&v, 16).ok());
+    info.pid = read("idProduct").and_then(|v| u16::from_str_radix(&v, 16).ok());
+    info.manufacturer = read("manufacturer");
+    info.product = read("product");
+}
+
 //========================================
 //SerialPort
 //========================================
@@ -177,6 +291,12 @@ pub fn open(port: &str, baud_rate: u32) -> Result<SerialPort, String> {
 pub struct SerialPort {
     inner: Serial2Port,
     timeout: std::time::Duration,
+    ///串口名称（用于重连）
+    port_name: String,
+    ///当前配置（用于重连后恢复）
+    settings: serial2::Settings,
+    ///行结束符（read_line 和 into_line_reader 使用）
+    line_terminator: u8,
 }
 
 impl SerialPort {
@@ -189,12 +309,70 @@ impl SerialPort {
         let inner = Serial2Port::open(port, baud_rate)
             .map_err(|e| format!("打开串口失败: {}", e))?;
 
+        let settings = inner.get_configuration()
+            .map_err(|e| format!("获取配置失败: {}", e))?;
+
         Ok(Self {
             inner,
             timeout: std::time::Duration::from_millis(DEFAULT_TIMEOUT_MS),
+            port_name: port.to_string(),
+            settings,
+            line_terminator: DEFAULT_LINE_TERMINATOR,
         })
     }
 
+    ///打开串口，失败时按间隔重试
+    ///
+    ///适用于设备刚上电、USB 转串口尚未枚举完成等场景
+    ///
+    ///# 参数
+    ///- attempts: 最多尝试次数（至少 1 次）
+    ///- delay: 两次尝试之间的等待时间
+    ///
+    ///# 示例
+    ///```rust
+    ///let port = SerialPort::open_retry("/dev/ttyUSB0", 115200, 5, Duration::from_secs(1))?;
+    ///```
+    pub fn open_retry(port: &str, baud_rate: u32, attempts: u32, delay: std::time::Duration) -> Result<Self, String> {
+        let attempts = attempts.max(1);
+        let mut last_err = String::new();
+
+        for i in 0..attempts {
+            match Self::open(port, baud_rate) {
+                Ok(serial) => return Ok(serial),
+                Err(e) => last_err = e,
+            }
+            if i + 1 < attempts {
+                std::thread::sleep(delay);
+            }
+        }
+
+        Err(format!("{}（已尝试 {} 次）", last_err, attempts))
+    }
+
+    ///重新打开串口（设备拔插后恢复连接）
+    ///
+    ///使用相同的串口名称，并恢复波特率、数据位、停止位、校验位、流控制和超时设置。
+    ///重新打开失败时保留原连接对象，可稍后再次调用
+    pub fn reconnect(&mut self) -> Result<(), String> {
+        let mut inner = Serial2Port::open(&self.port_name, self.settings.get_baud_rate().unwrap_or(115200))
+            .map_err(|e| format!("重新打开串口失败: {}", e))?;
+
+        inner.set_configuration(&self.settings)
+            .map_err(|e| format!("应用配置失败: {}", e))?;
+
+        inner.set_read_timeout(self.timeout)
+            .map_err(|e| format!("设置超时失败: {}", e))?;
+
+        self.inner = inner;
+        Ok(())
+    }
+
+    ///获取串口名称
+    pub fn port_name(&self) -> &str {
+        &self.port_name
+    }
+
     ///获取 Builder
     pub fn builder() -> SerialPortBuilder {
         SerialPortBuilder::new()
@@ -257,8 +435,23 @@ impl SerialPort {
         Ok(buf)
     }
 
-    ///读取一行（直到 \n 或 \r\n）
+    ///读取一行（直到行结束符，默认 \n；结束符为 \n 时兼容 \r\n）
+    ///
+    ///返回内容不含行结束符，结束符可通过 set_line_terminator() 修改
     pub fn read_line(&mut self) -> Result<String, String> {
+        let mut result = self.read_until(self.line_terminator)?;
+        if self.line_terminator == b'\n' && result.last() == Some(&b'\r') {
+            result.pop();
+        }
+
+        String::from_utf8(result)
+            .map_err(|e| format!("UTF-8 解码失败: {}", e))
+    }
+
+    ///读取直到遇到指定分隔字节（如 \r 或 0x03）
+    ///
+    ///返回内容不含分隔字节，超过超时时间仍未遇到分隔字节时返回错误
+    pub fn read_until(&mut self, delimiter: u8) -> Result<Vec<u8>, String> {
         let mut result = Vec::new();
         let mut buf = [0u8; 1];
         let start = std::time::Instant::now();
@@ -270,20 +463,17 @@ impl SerialPort {
 
             match self.read(&mut buf) {
                 Ok(1) => {
-                    if buf[0] == b'\n' {
+                    if buf[0] == delimiter {
                         break;
                     }
-                    if buf[0] != b'\r' {
-                        result.push(buf[0]);
-                    }
+                    result.push(buf[0]);
                 }
                 Ok(_) => continue,
                 Err(e) => return Err(e),
             }
         }
 
-        String::from_utf8(result)
-            .map_err(|e| format!("UTF-8 解码失败: {}", e))
+        Ok(result)
     }
 
     //========================================
@@ -296,6 +486,11 @@ impl SerialPort {
         let _ = self.inner.set_read_timeout(timeout);
     }
 
+    ///设置行结束符（如仪器使用 \r 或 0x03 结尾）
+    pub fn set_line_terminator(&mut self, terminator: u8) {
+        self.line_terminator = terminator;
+    }
+
     ///设置波特率
     pub fn set_baud_rate(&mut self, baud_rate: u32) -> Result<(), String> {
         let settings = self.inner.get_configuration()
@@ -306,7 +501,24 @@ impl SerialPort {
             .map_err(|e| format!("设置波特率失败: {}", e))?;
 
         self.inner.set_configuration(&settings)
-            .map_err(|e| format!("应用配置失败: {}", e))
+            .map_err(|e| format!("应用配置失败: {}", e))?;
+
+        self.settings = settings;
+        Ok(())
+    }
+
+    ///设置流控制
+    pub fn set_flow_control(&mut self, flow_control: FlowControl) -> Result<(), String> {
+        let mut settings = self.inner.get_configuration()
+            .map_err(|e| format!("获取配置失败: {}", e))?;
+
+        settings.set_flow_control(flow_control.into());
+
+        self.inner.set_configuration(&settings)
+            .map_err(|e| format!("应用配置失败: {}", e))?;
+
+        self.settings = settings;
+        Ok(())
     }
 
     //========================================
@@ -337,6 +549,36 @@ impl SerialPort {
             .map_err(|e| format!("读取 DSR 失败: {}", e))
     }
 
+    //========================================
+    //后台读取
+    //========================================
+
+    ///启动后台读取线程，收到的原始数据块通过 channel 发送
+    ///
+    ///串口所有权转移到读取线程。读取出错（如设备被拔出）时线程退出、channel 关闭；
+    ///无法设置读取超时时不启动线程，直接返回 Err
+    pub fn into_reader(self) -> Result<(SerialReader, std::sync::mpsc::Receiver<Vec<u8>>), String> {
+        self.spawn_reader(false)
+    }
+
+    ///启动后台读取线程，按行发送（行结束符同 read_line），不含行结束符
+    ///
+    ///停止时缓冲区中不完整的最后一行也会发出
+    pub fn into_line_reader(self) -> Result<(SerialReader, std::sync::mpsc::Receiver<Vec<u8>>), String> {
+        self.spawn_reader(true)
+    }
+
+    ///启动读取线程
+    fn spawn_reader(mut self, lines: bool) -> Result<(SerialReader, std::sync::mpsc::Receiver<Vec<u8>>), String> {
+        //使用较短的读取超时，以便及时响应停止信号
+        self.inner
+            .set_read_timeout(std::time::Duration::from_millis(READER_POLL_INTERVAL_MS))
+            .map_err(|e| format!("设置读取超时失败: {}", e))?;
+
+        let terminator = if lines { Some(self.line_terminator) } else { None };
+        Ok(SerialReader::spawn(self.inner, terminator))
+    }
+
     ///获取内部引用
     pub fn inner(&self) -> &Serial2Port {
         &self.inner
@@ -348,6 +590,111 @@ impl SerialPort {
     }
 }
 
+//========================================
+//后台读取句柄
+//========================================
+
+///后台读取句柄，用于停止读取线程
+pub struct SerialReader {
+    stop_sender: std::sync::mpsc::Sender<()>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl SerialReader {
+    ///在后台线程中从 source 读取数据
+    ///
+    ///terminator 为 None 时按原始数据块发送，否则按行发送
+    fn spawn<R>(mut source: R, terminator: Option<u8>) -> (Self, std::sync::mpsc::Receiver<Vec<u8>>)
+    where
+        R: std::io::Read + Send + 'static,
+    {
+        let (data_tx, data_rx) = std::sync::mpsc::channel();
+        let (stop_tx, stop_rx) = std::sync::mpsc::channel::<()>();
+
+        let handle = std::thread::spawn(move || {
+            let mut buf = vec![0u8; DEFAULT_BUFFER_SIZE];
+            let mut pending: Vec<u8> = Vec::new();
+
+            while let Err(std::sync::mpsc::TryRecvError::Empty) = stop_rx.try_recv() {
+                let n = match source.read(&mut buf) {
+                    Ok(0) => break,
+                    Ok(n) => n,
+                    Err(e) if matches!(
+                        e.kind(),
+                        std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock | std::io::ErrorKind::Interrupted
+                    ) => continue,
+                    Err(_) => break,
+                };
+
+                let terminator = match terminator {
+                    Some(t) => t,
+                    None => {
+                        if data_tx.send(buf[..n].to_vec()).is_err() {
+                            return;
+                        }
+                        continue;
+                    }
+                };
+
+                pending.extend_from_slice(&buf[..n]);
+                for line in split_lines(&mut pending, terminator) {
+                    if data_tx.send(line).is_err() {
+                        return;
+                    }
+                }
+            }
+
+            //发出未以换行结尾的剩余数据
+            if !pending.is_empty() {
+                let _ = data_tx.send(pending);
+            }
+        });
+
+        let reader = SerialReader {
+            stop_sender: stop_tx,
+            thread: Some(handle),
+        };
+        (reader, data_rx)
+    }
+
+    ///停止读取并等待线程退出
+    ///
+    ///已读取的数据在线程退出前全部发送到 channel，接收端仍可继续取出
+    pub fn stop(mut self) {
+        let _ = self.stop_sender.send(());
+        if let Some(handle) = self.thread.take() {
+            let _ = handle.join();
+        }
+    }
+
+    ///检查是否仍在运行
+    pub fn is_running(&self) -> bool {
+        self.thread.as_ref().is_some_and(|h| !h.is_finished())
+    }
+}
+
+impl Drop for SerialReader {
+    fn drop(&mut self) {
+        let _ = self.stop_sender.send(());
+    }
+}
+
+///从缓冲区取出所有完整的行（不含行结束符），不完整的部分留在缓冲区
+///
+///结束符为 \n 时兼容 \r\n
+fn split_lines(pending: &mut Vec<u8>, terminator: u8) -> Vec<Vec<u8>> {
+    let mut lines = Vec::new();
+    while let Some(pos) = pending.iter().position(|&b| b == terminator) {
+        let mut line: Vec<u8> = pending.drain(..=pos).collect();
+        line.pop();
+        if terminator == b'\n' && line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        lines.push(line);
+    }
+    lines
+}
+
 //========================================
 //SerialPortBuilder
 //========================================
@@ -359,7 +706,9 @@ pub struct SerialPortBuilder {
     data_bits: DataBits,
     stop_bits: StopBits,
     parity: Parity,
+    flow_control: FlowControl,
     timeout: std::time::Duration,
+    line_terminator: u8,
 }
 
 impl SerialPortBuilder {
@@ -371,7 +720,9 @@ impl SerialPortBuilder {
             data_bits: DataBits::Eight,
             stop_bits: StopBits::One,
             parity: Parity::None,
+            flow_control: FlowControl::None,
             timeout: std::time::Duration::from_millis(DEFAULT_TIMEOUT_MS),
+            line_terminator: DEFAULT_LINE_TERMINATOR,
         }
     }
 
@@ -405,12 +756,24 @@ impl SerialPortBuilder {
         self
     }
 
+    ///设置流控制
+    pub fn flow_control(mut self, flow_control: FlowControl) -> Self {
+        self.flow_control = flow_control;
+        self
+    }
+
     ///设置超时
     pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
         self.timeout = timeout;
         self
     }
 
+    ///设置行结束符（默认 \n）
+    pub fn line_terminator(mut self, terminator: u8) -> Self {
+        self.line_terminator = terminator;
+        self
+    }
+
     ///打开串口
     pub fn open(self) -> Result<SerialPort, String> {
         let port_name = self.port.ok_or("未指定串口名称")?;
@@ -425,6 +788,7 @@ impl SerialPortBuilder {
         settings.set_char_size(self.data_bits.into());
         settings.set_stop_bits(self.stop_bits.into());
         settings.set_parity(self.parity.into());
+        settings.set_flow_control(self.flow_control.into());
 
         inner.set_configuration(&settings)
             .map_err(|e| format!("应用配置失败: {}", e))?;
@@ -435,6 +799,9 @@ impl SerialPortBuilder {
         Ok(SerialPort {
             inner,
             timeout: self.timeout,
+            port_name,
+            settings,
+            line_terminator: self.line_terminator,
         })
     }
 }
@@ -460,3 +827,112 @@ pub mod baud_rates {
     pub const B460800: u32 = 460800;
     pub const B921600: u32 = 921600;
 }
+
+//========================================
+//测试
+//========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///模拟串口：依次返回预设的数据块，之后一直读取超时
+    struct MockPort {
+        chunks: std::collections::VecDeque<Vec<u8>>,
+    }
+
+    impl MockPort {
+        fn new(chunks: &[&[u8]]) -> Self {
+            Self { chunks: chunks.iter().map(|c| c.to_vec()).collect() }
+        }
+    }
+
+    impl std::io::Read for MockPort {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            match self.chunks.pop_front() {
+                Some(chunk) => {
+                    buf[..chunk.len()].copy_from_slice(&chunk);
+                    Ok(chunk.len())
+                }
+                None => {
+                    std::thread::sleep(std::time::Duration::from_millis(5));
+                    Err(std::io::ErrorKind::TimedOut.into())
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn stop_flushes_pending_partial_line() {
+        let port = MockPort::new(&[b"abc\ndef"]);
+        let (reader, rx) = SerialReader::spawn(port, Some(b'\n'));
+
+        let timeout = std::time::Duration::from_secs(2);
+        assert_eq!(rx.recv_timeout(timeout).unwrap(), b"abc");
+        //"def" 没有行结束符，停止前一直留在缓冲区
+        assert!(rx.recv_timeout(std::time::Duration::from_millis(50)).is_err());
+
+        reader.stop();
+        assert_eq!(rx.recv_timeout(timeout).unwrap(), b"def");
+        //线程已退出，channel 关闭
+        assert!(rx.recv().is_err());
+    }
+
+    #[test]
+    fn split_lines_strips_crlf_and_keeps_partial_line() {
+        let mut pending = b"one\r\ntwo\n\nthr".to_vec();
+        let lines = split_lines(&mut pending, b'\n');
+        assert_eq!(lines, vec![b"one".to_vec(), b"two".to_vec(), Vec::new()]);
+        assert_eq!(pending, b"thr");
+    }
+
+    #[test]
+    fn split_lines_with_custom_terminator() {
+        //仪器常用 0x03（ETX）结尾；非 \n 结束符时 \r 属于内容
+        let mut pending = b"A1\r\x03B2\x03C".to_vec();
+        let lines = split_lines(&mut pending, 0x03);
+        assert_eq!(lines, vec![b"A1\r".to_vec(), b"B2".to_vec()]);
+        assert_eq!(pending, b"C");
+
+        assert!(split_lines(&mut pending, 0x03).is_empty());
+        assert_eq!(pending, b"C");
+    }
+
+    #[test]
+    fn list_ports_does_not_panic() {
+        //没有串口或平台不支持枚举时可能返回空列表或 Err，但不应 panic
+        if let Ok(ports) = list_ports() {
+            assert!(ports.iter().all(|p| !p.name.is_empty()));
+        }
+    }
+
+    #[test]
+    fn non_usb_port_has_no_usb_fields() {
+        let info = port_info(std::path::Path::new("/dev/ttyS_serial_test_missing"));
+        assert_eq!(info.name, "/dev/ttyS_serial_test_missing");
+        assert_eq!(info.vid, None);
+        assert_eq!(info.pid, None);
+        assert_eq!(info.manufacturer, None);
+        assert_eq!(info.product, None);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn usb_info_is_read_from_sysfs_dir() {
+        let dir = std::env::temp_dir().join(format!("serial_{}_usb", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("idVendor"), "1a86\n").unwrap();
+        std::fs::write(dir.join("idProduct"), "7523\n").unwrap();
+        std::fs::write(dir.join("product"), "USB Serial\n").unwrap();
+
+        let mut info = PortInfo::default();
+        read_usb_info(&dir, &mut info);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert_eq!(info.vid, Some(0x1a86));
+        assert_eq!(info.pid, Some(0x7523));
+        assert_eq!(info.product.as_deref(), Some("USB Serial"));
+        //缺失的文件保持 None
+        assert_eq!(info.manufacturer, None);
+    }
+}
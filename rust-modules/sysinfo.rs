@@ -1,13 +1,20 @@
 //!系统信息模块
 //!
-//!提供 CPU、内存、磁盘、网络等系统信息查询功能。
+//!提供 CPU、内存、磁盘、网络、温度传感器等系统信息查询功能。
 //!
-//!依赖：sysinfo（使用时查询最新版本：https://crates.io/crates/sysinfo）
+//!依赖：
+//!- sysinfo（使用时查询最新版本：https://crates.io/crates/sysinfo）
+//!- serde（可选，启用 serde feature 后信息结构体实现 Serialize；使用时查询最新版本：https://crates.io/crates/serde）
 //!
 //!# Cargo.toml 配置示例
 //!```toml
 //![dependencies]
 //!sysinfo = "0.37"  # https://crates.io/crates/sysinfo
+//!serde = { version = "1", features = ["derive"], optional = true }  # https://crates.io/crates/serde
+//!
+//!#需要将信息结构体序列化（如输出 JSON）时启用：cargo build --features serde
+//![features]
+//!serde = ["dep:serde"]
 //!```
 //!
 //!# 快速开始
@@ -24,7 +31,7 @@
 //!}
 //!```
 
-use sysinfo::{System, Disks, Networks, CpuRefreshKind, MemoryRefreshKind, RefreshKind};
+use sysinfo::{System, Disks, Networks, Components, CpuRefreshKind, MemoryRefreshKind, RefreshKind};
 
 //========================================
 //系统信息主结构
@@ -35,10 +42,11 @@ pub struct SystemInfo {
     sys: System,
     disks: Disks,
     networks: Networks,
+    components: Components,
 }
 
 impl SystemInfo {
-    ///创建并初始化系统信息（获取所有信息）
+    ///创建并初始化系统信息（获取所有信息，含进程列表）
     pub fn new() -> Self {
         let mut sys = System::new_all();
         //刷新 CPU 使用率需要两次采样
@@ -49,10 +57,11 @@ impl SystemInfo {
             sys,
             disks: Disks::new_with_refreshed_list(),
             networks: Networks::new_with_refreshed_list(),
+            components: Components::new_with_refreshed_list(),
         }
     }
 
-    ///创建轻量级实例（仅基础信息，不获取磁盘和网络）
+    ///创建轻量级实例（仅基础信息，不获取磁盘、网络、传感器和进程）
     pub fn new_light() -> Self {
         let refresh_kind = RefreshKind::new()
             .with_cpu(CpuRefreshKind::everything())
@@ -66,6 +75,7 @@ impl SystemInfo {
             sys,
             disks: Disks::new(),
             networks: Networks::new(),
+            components: Components::new(),
         }
     }
 
@@ -74,6 +84,7 @@ impl SystemInfo {
         self.sys.refresh_all();
         self.disks.refresh(true);
         self.networks.refresh(true);
+        self.components.refresh(true);
     }
 
     ///刷新 CPU 信息
@@ -95,6 +106,92 @@ impl SystemInfo {
     pub fn refresh_networks(&mut self) {
         self.networks.refresh(true);
     }
+
+    ///刷新传感器信息（new_light() 创建的实例查询传感器前需先调用）
+    pub fn refresh_components(&mut self) {
+        self.components.refresh(true);
+    }
+
+    ///刷新进程列表（new_light() 创建的实例查询进程前需先调用）
+    pub fn refresh_processes(&mut self) {
+        self.sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+    }
+}
+
+//========================================
+//定时监控
+//========================================
+
+impl SystemInfo {
+    ///按固定间隔刷新并调用回调（阻塞）
+    ///
+    ///# 参数
+    ///- interval: 刷新间隔（小于 sysinfo::MINIMUM_CPU_UPDATE_INTERVAL 时按最小间隔处理，保证 CPU 使用率准确）
+    ///- callback: 每次刷新后调用，返回 false 停止监控
+    pub fn watch<F>(mut self, interval: std::time::Duration, mut callback: F)
+    where
+        F: FnMut(&SystemInfo) -> bool,
+    {
+        let interval = interval.max(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        loop {
+            std::thread::sleep(interval);
+            self.refresh();
+            if !callback(&self) {
+                break;
+            }
+        }
+    }
+
+    ///按固定间隔刷新并调用回调（非阻塞，返回句柄）
+    pub fn watch_async<F>(mut self, interval: std::time::Duration, mut callback: F) -> MonitorHandle
+    where
+        F: FnMut(&SystemInfo) -> bool + Send + 'static,
+    {
+        let interval = interval.max(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        let (stop_tx, stop_rx) = std::sync::mpsc::channel();
+
+        let handle = std::thread::spawn(move || {
+            //等待间隔，期间收到停止信号立即退出
+            while let Err(std::sync::mpsc::RecvTimeoutError::Timeout) = stop_rx.recv_timeout(interval) {
+                self.refresh();
+                if !callback(&self) {
+                    break;
+                }
+            }
+        });
+
+        MonitorHandle {
+            stop_sender: stop_tx,
+            thread: Some(handle),
+        }
+    }
+}
+
+///监控句柄，用于控制异步监控
+pub struct MonitorHandle {
+    stop_sender: std::sync::mpsc::Sender<()>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl MonitorHandle {
+    ///停止监控
+    pub fn stop(mut self) {
+        let _ = self.stop_sender.send(());
+        if let Some(handle) = self.thread.take() {
+            let _ = handle.join();
+        }
+    }
+
+    ///检查是否仍在运行
+    pub fn is_running(&self) -> bool {
+        self.thread.as_ref().is_some_and(|h| !h.is_finished())
+    }
+}
+
+impl Drop for MonitorHandle {
+    fn drop(&mut self) {
+        let _ = self.stop_sender.send(());
+    }
 }
 
 //========================================
@@ -127,6 +224,8 @@ impl SystemInfo {
     }
 
     ///获取 CPU 品牌名称
+    ///
+    ///仅读取第 0 个核心；大小核等异构 CPU 请使用 cpu_per_core_info()
     pub fn cpu_brand(&self) -> String {
         self.sys.cpus()
             .first()
@@ -135,6 +234,8 @@ impl SystemInfo {
     }
 
     ///获取 CPU 频率（MHz）
+    ///
+    ///仅读取第 0 个核心；大小核等异构 CPU 请使用 cpu_per_core_info()
     pub fn cpu_frequency(&self) -> u64 {
         self.sys.cpus()
             .first()
@@ -152,10 +253,26 @@ impl SystemInfo {
             usage: self.cpu_usage(),
         }
     }
+
+    ///获取每个逻辑核心的信息（名称、品牌、频率、使用率）
+    ///
+    ///适用于大小核（big.LITTLE）等各核心频率或型号不同的 CPU
+    pub fn cpu_per_core_info(&self) -> Vec<CoreInfo> {
+        self.sys.cpus()
+            .iter()
+            .map(|c| CoreInfo {
+                name: c.name().to_string(),
+                brand: c.brand().to_string(),
+                frequency_mhz: c.frequency(),
+                usage: c.cpu_usage(),
+            })
+            .collect()
+    }
 }
 
 ///CPU 详细信息
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CpuInfo {
     ///品牌名称
     pub brand: String,
@@ -169,6 +286,20 @@ pub struct CpuInfo {
     pub usage: f32,
 }
 
+///单个 CPU 核心信息
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CoreInfo {
+    ///核心名称（如 cpu0）
+    pub name: String,
+    ///品牌名称
+    pub brand: String,
+    ///频率（MHz）
+    pub frequency_mhz: u64,
+    ///使用率（0.0-100.0）
+    pub usage: f32,
+}
+
 //========================================
 //内存信息
 //========================================
@@ -223,6 +354,7 @@ impl SystemInfo {
 
 ///内存详细信息
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct MemoryInfo {
     ///总内存（字节）
     pub total: u64,
@@ -296,6 +428,7 @@ impl SystemInfo {
 
 ///磁盘信息
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct DiskInfo {
     ///磁盘名称
     pub name: String,
@@ -374,10 +507,23 @@ impl SystemInfo {
                 packets_transmitted: data.total_packets_transmitted(),
             })
     }
+
+    ///采样计算各网络接口的实时速率（阻塞 interval 时长）
+    ///
+    ///先刷新一次记录计数，等待 interval 后再刷新一次，按两次差值计算每秒收发字节数
+    pub fn network_rates(&mut self, interval: std::time::Duration) -> Vec<NetworkRate> {
+        self.refresh_networks();
+        let before = self.networks();
+        let started = std::time::Instant::now();
+        std::thread::sleep(interval);
+        self.refresh_networks();
+        NetworkRate::between(&before, &self.networks(), started.elapsed())
+    }
 }
 
 ///网络接口信息
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct NetworkInfo {
     ///接口名称
     pub name: String,
@@ -403,6 +549,196 @@ impl NetworkInfo {
     }
 }
 
+///网络接口速率
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct NetworkRate {
+    ///接口名称
+    pub name: String,
+    ///每秒接收字节数
+    pub received_per_sec: u64,
+    ///每秒发送字节数
+    pub transmitted_per_sec: u64,
+}
+
+impl NetworkRate {
+    ///根据前后两次采样计算各接口速率
+    ///
+    ///只计算两次采样中都存在的接口；计数器回绕或重置时该次速率记为 0
+    pub fn between(before: &[NetworkInfo], after: &[NetworkInfo], elapsed: std::time::Duration) -> Vec<NetworkRate> {
+        let secs = elapsed.as_secs_f64();
+        let per_sec = |delta: u64| {
+            if secs > 0.0 { (delta as f64 / secs).round() as u64 } else { 0 }
+        };
+
+        after.iter().filter_map(|now| {
+            let prev = before.iter().find(|p| p.name == now.name)?;
+            Some(NetworkRate {
+                name: now.name.clone(),
+                received_per_sec: per_sec(now.received.saturating_sub(prev.received)),
+                transmitted_per_sec: per_sec(now.transmitted.saturating_sub(prev.transmitted)),
+            })
+        }).collect()
+    }
+
+    ///人性化显示接收速率（如 "1.20 MiB/s"）
+    pub fn received_per_sec_human(&self) -> String {
+        format!("{}/s", humanize_bytes(self.received_per_sec))
+    }
+
+    ///人性化显示发送速率（如 "1.20 MiB/s"）
+    pub fn transmitted_per_sec_human(&self) -> String {
+        format!("{}/s", humanize_bytes(self.transmitted_per_sec))
+    }
+}
+
+//========================================
+//传感器信息
+//========================================
+
+impl SystemInfo {
+    ///获取所有温度传感器信息
+    ///
+    ///平台不支持或无传感器（如虚拟机、容器）时返回空列表
+    pub fn components(&self) -> Vec<ComponentInfo> {
+        self.components.list().iter().map(|c| ComponentInfo {
+            label: c.label().to_string(),
+            temperature: c.temperature(),
+            max: c.max(),
+            critical: c.critical(),
+        }).collect()
+    }
+
+    ///获取 CPU 温度（摄氏度）
+    ///
+    ///按标签识别 CPU 传感器（如 Linux 的 coretemp Package、k10temp Tctl），
+    ///有多个时取最高值；找不到 CPU 传感器时返回 None
+    pub fn cpu_temperature(&self) -> Option<f32> {
+        self.components()
+            .into_iter()
+            .filter(|c| is_cpu_sensor(&c.label))
+            .filter_map(|c| c.temperature)
+            .fold(None, |max, t| Some(max.map_or(t, |m: f32| m.max(t))))
+    }
+}
+
+///按标签判断是否为 CPU 温度传感器
+fn is_cpu_sensor(label: &str) -> bool {
+    let label = label.to_lowercase();
+    ["cpu", "package", "coretemp", "k10temp", "tctl", "tdie"]
+        .iter()
+        .any(|key| label.contains(key))
+}
+
+///温度传感器信息
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ComponentInfo {
+    ///传感器标签
+    pub label: String,
+    ///当前温度（摄氏度，读取失败时为 None）
+    pub temperature: Option<f32>,
+    ///记录到的最高温度（摄氏度）
+    pub max: Option<f32>,
+    ///临界温度（摄氏度，硬件未提供时为 None）
+    pub critical: Option<f32>,
+}
+
+//========================================
+//进程信息
+//========================================
+
+impl SystemInfo {
+    ///获取所有进程（按 PID 升序）
+    ///
+    ///new() 创建时已获取进程列表；new_light() 创建的实例需先调用 refresh_processes()
+    pub fn processes(&self) -> Vec<ProcessInfo> {
+        let mut list = self.collect_processes(|_| true);
+        list.sort_by_key(|p| p.pid);
+        list
+    }
+
+    ///按名称查找进程（不区分大小写的子串匹配）
+    ///
+    ///如 "nginx" 可匹配 "nginx" 和 "nginx.exe"；需要精确匹配时使用 find_processes_by_exact_name()
+    pub fn find_processes_by_name(&self, name: &str) -> Vec<ProcessInfo> {
+        let name = name.to_lowercase();
+        self.collect_processes(|p| p.name.to_lowercase().contains(&name))
+    }
+
+    ///按名称查找进程（find_processes_by_name() 的简写）
+    pub fn processes_by_name(&self, name: &str) -> Vec<ProcessInfo> {
+        self.find_processes_by_name(name)
+    }
+
+    ///按名称精确查找进程（区分大小写）
+    pub fn find_processes_by_exact_name(&self, name: &str) -> Vec<ProcessInfo> {
+        self.collect_processes(|p| p.name == name)
+    }
+
+    ///判断是否有名称匹配的进程正在运行（匹配规则同 find_processes_by_name）
+    pub fn is_process_running(&self, name: &str) -> bool {
+        !self.find_processes_by_name(name).is_empty()
+    }
+
+    ///按 PID 获取进程信息
+    ///
+    ///# 注意
+    ///结果基于最近一次刷新，轮询进程状态时需先调用 refresh_processes()
+    pub fn process_by_pid(&self, pid: u32) -> Option<ProcessInfo> {
+        self.sys.process(sysinfo::Pid::from_u32(pid)).map(process_info)
+    }
+
+    ///收集满足条件的进程
+    fn collect_processes<F>(&self, filter: F) -> Vec<ProcessInfo>
+    where
+        F: Fn(&ProcessInfo) -> bool,
+    {
+        self.sys.processes()
+            .values()
+            .map(process_info)
+            .filter(|p| filter(p))
+            .collect()
+    }
+}
+
+///转换 sysinfo 进程为 ProcessInfo
+fn process_info(p: &sysinfo::Process) -> ProcessInfo {
+    ProcessInfo {
+        pid: p.pid().as_u32(),
+        name: p.name().to_string_lossy().to_string(),
+        cpu_usage: p.cpu_usage(),
+        memory: p.memory(),
+        parent_pid: p.parent().map(|pid| pid.as_u32()),
+        exe_path: p.exe().map(|path| path.to_path_buf()),
+    }
+}
+
+///进程信息
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ProcessInfo {
+    ///进程 ID
+    pub pid: u32,
+    ///进程名称
+    pub name: String,
+    ///CPU 使用率（单核为 100.0）
+    pub cpu_usage: f32,
+    ///内存占用（字节）
+    pub memory: u64,
+    ///父进程 ID
+    pub parent_pid: Option<u32>,
+    ///可执行文件路径（无权限访问时为 None）
+    pub exe_path: Option<std::path::PathBuf>,
+}
+
+impl ProcessInfo {
+    ///人性化显示内存占用
+    pub fn memory_human(&self) -> String {
+        humanize_bytes(self.memory)
+    }
+}
+
 //========================================
 //系统基本信息
 //========================================
@@ -458,6 +794,7 @@ impl SystemInfo {
 
 ///系统基本信息
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct BasicSystemInfo {
     ///操作系统名称
     pub os_name: String,
@@ -480,6 +817,44 @@ impl BasicSystemInfo {
     }
 }
 
+//========================================
+//系统快照
+//========================================
+
+impl SystemInfo {
+    ///获取系统快照（汇总当前各项信息，启用 serde feature 后可直接序列化为 JSON）
+    ///
+    ///数据基于最近一次刷新，需要最新数据时先调用 refresh()
+    pub fn snapshot(&self) -> SystemSnapshot {
+        SystemSnapshot {
+            system: self.system_info(),
+            cpu: self.cpu_info(),
+            memory: self.memory_info(),
+            disks: self.disks(),
+            networks: self.networks(),
+            components: self.components(),
+        }
+    }
+}
+
+///系统快照
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SystemSnapshot {
+    ///系统基本信息
+    pub system: BasicSystemInfo,
+    ///CPU 信息
+    pub cpu: CpuInfo,
+    ///内存信息
+    pub memory: MemoryInfo,
+    ///磁盘列表
+    pub disks: Vec<DiskInfo>,
+    ///网络接口列表
+    pub networks: Vec<NetworkInfo>,
+    ///温度传感器列表
+    pub components: Vec<ComponentInfo>,
+}
+
 //========================================
 //便捷函数
 //========================================
@@ -524,24 +899,31 @@ pub fn uptime() -> u64 {
 //工具函数
 //========================================
 
-///人性化显示字节数
+///人性化显示字节数（1024 进制，等同于 humanize_bytes_binary）
 pub fn humanize_bytes(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = KB * 1024;
-    const GB: u64 = MB * 1024;
-    const TB: u64 = GB * 1024;
-
-    if bytes >= TB {
-        format!("{:.2} TB", bytes as f64 / TB as f64)
-    } else if bytes >= GB {
-        format!("{:.2} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.2} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.2} KB", bytes as f64 / KB as f64)
-    } else {
-        format!("{} B", bytes)
+    humanize_bytes_binary(bytes)
+}
+
+///人性化显示字节数（1024 进制，KiB/MiB/GiB/TiB，与操作系统内存统计一致）
+pub fn humanize_bytes_binary(bytes: u64) -> String {
+    humanize_bytes_with(bytes, 1024, ["KiB", "MiB", "GiB", "TiB"])
+}
+
+///人性化显示字节数（1000 进制，KB/MB/GB/TB，与硬盘厂商标称一致）
+pub fn humanize_bytes_si(bytes: u64) -> String {
+    humanize_bytes_with(bytes, 1000, ["KB", "MB", "GB", "TB"])
+}
+
+///按指定进制和单位格式化字节数
+fn humanize_bytes_with(bytes: u64, base: u64, units: [&str; 4]) -> String {
+    let mut unit_size = base.pow(units.len() as u32);
+    for unit in units.iter().rev() {
+        if bytes >= unit_size {
+            return format!("{:.2} {}", bytes as f64 / unit_size as f64, unit);
+        }
+        unit_size /= base;
     }
+    format!("{} B", bytes)
 }
 
 ///人性化显示时间（秒转换为天时分秒）
@@ -567,3 +949,93 @@ impl Default for SystemInfo {
         Self::new()
     }
 }
+
+//========================================
+//测试
+//========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn components_without_sensors_do_not_panic() {
+        //虚拟机、容器中通常没有温度传感器，此时返回空列表和 None
+        let mut info = SystemInfo::new_light();
+        assert!(info.components().is_empty());
+        assert_eq!(info.cpu_temperature(), None);
+
+        info.refresh_components();
+        let components = info.components();
+        let temperature = info.cpu_temperature();
+        if !components.iter().any(|c| is_cpu_sensor(&c.label)) {
+            assert_eq!(temperature, None);
+        }
+    }
+
+    #[test]
+    fn cpu_sensor_labels() {
+        assert!(is_cpu_sensor("coretemp Package id 0"));
+        assert!(is_cpu_sensor("k10temp Tctl"));
+        assert!(!is_cpu_sensor("nvme Composite"));
+        assert!(!is_cpu_sensor("acpitz temp1"));
+    }
+
+    #[test]
+    fn current_process_is_found_by_pid_and_name() {
+        let mut info = SystemInfo::new_light();
+        info.refresh_processes();
+
+        let me = info.process_by_pid(std::process::id()).expect("找不到当前进程");
+        assert!(!me.name.is_empty());
+        assert!(info.processes_by_name(&me.name).iter().any(|p| p.pid == me.pid));
+        assert!(info.processes().windows(2).all(|w| w[0].pid < w[1].pid));
+    }
+
+    fn counters(name: &str, received: u64, transmitted: u64) -> NetworkInfo {
+        NetworkInfo {
+            name: name.to_string(),
+            received,
+            transmitted,
+            packets_received: 0,
+            packets_transmitted: 0,
+        }
+    }
+
+    #[test]
+    fn network_rate_between_samples() {
+        let before = [counters("eth0", 1_000, 500), counters("lo", 10, 10), counters("wlan0", 5_000, 0)];
+        //wlan0 计数器被重置，docker0 只在第二次采样中出现
+        let after = [
+            counters("eth0", 3_000, 1_500),
+            counters("wlan0", 100, 0),
+            counters("docker0", 9, 9),
+            counters("lo", 10, 10),
+        ];
+
+        let rates = NetworkRate::between(&before, &after, std::time::Duration::from_millis(500));
+        let rate = |name: &str| {
+            rates.iter().find(|r| r.name == name).map(|r| (r.received_per_sec, r.transmitted_per_sec))
+        };
+        assert_eq!(rates.len(), 3);
+        assert_eq!(rate("eth0"), Some((4_000, 2_000)));
+        assert_eq!(rate("wlan0"), Some((0, 0)));
+        assert_eq!(rate("lo"), Some((0, 0)));
+        assert_eq!(rate("docker0"), None);
+
+        //间隔为 0 时不做除法，速率记为 0
+        let zero = NetworkRate::between(&before, &after, std::time::Duration::ZERO);
+        assert!(zero.iter().all(|r| r.received_per_sec == 0 && r.transmitted_per_sec == 0));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn snapshot_serializes_to_json() {
+        let info = SystemInfo::new_light();
+        let json = serde_json::to_string(&info.snapshot()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["memory"]["total"].as_u64(), Some(info.memory_total()));
+        assert!(value["cpu"].is_object());
+        assert!(value["disks"].is_array());
+    }
+}
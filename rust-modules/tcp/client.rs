@@ -14,6 +14,8 @@ use super::protocol;
 pub struct TcpClient {
     ///底层 TCP 连接
     stream: std::net::TcpStream,
+    ///JSON-lines 模式的缓冲读取器（首次使用时创建）
+    line_reader: Option<std::io::BufReader<std::net::TcpStream>>,
 }
 
 impl TcpClient {
@@ -22,11 +24,21 @@ impl TcpClient {
     //========================================
 
     ///单次连接，失败返回错误
+    ///
+    ///连接超时使用 config::CONNECT_TIMEOUT_SECS
     pub fn connect_once(addr: &str, port: u16) -> std::io::Result<Self> {
-        let address = format!("{}:{}", addr, port);
-        let stream = std::net::TcpStream::connect(&address)?;
+        Self::connect_once_timeout(addr, port, std::time::Duration::from_secs(config::CONNECT_TIMEOUT_SECS))
+    }
+
+    ///单次连接，指定连接超时
+    ///
+    ///域名解析出多个地址时依次尝试，每个地址最多等待 timeout。
+    ///解析失败返回 NotFound，全部地址超时返回 TimedOut，其他情况返回最后一个地址的错误
+    pub fn connect_once_timeout(addr: &str, port: u16, timeout: std::time::Duration) -> std::io::Result<Self> {
+        let address = super::format_addr(addr, port);
+        let stream = Self::connect_stream(&address, timeout)?;
         Self::apply_timeouts(&stream)?;
-        Ok(Self { stream })
+        Ok(Self { stream, line_reader: None })
     }
 
     ///使用默认配置单次连接
@@ -48,11 +60,12 @@ impl TcpClient {
     where
         F: FnMut(&mut Self) -> bool,
     {
-        let address = format!("{}:{}", addr, port);
+        let address = super::format_addr(addr, port);
+        let connect_timeout = std::time::Duration::from_secs(config::CONNECT_TIMEOUT_SECS);
         let mut delay_ms = config::RECONNECT_INITIAL_MS;
 
         loop {
-            match std::net::TcpStream::connect(&address) {
+            match Self::connect_stream(&address, connect_timeout) {
                 Ok(stream) => {
                     println!("已连接到 {}", address);
                     delay_ms = config::RECONNECT_INITIAL_MS;
@@ -61,7 +74,7 @@ impl TcpClient {
                         eprintln!("设置超时失败: {}", e);
                     }
 
-                    let mut client = Self { stream };
+                    let mut client = Self { stream, line_reader: None };
                     if !on_connected(&mut client) {
                         println!("主动断开连接");
                         break;
@@ -93,17 +106,18 @@ impl TcpClient {
 
     ///重试连接直到成功
     pub fn connect_until_success(addr: &str, port: u16) -> Self {
-        let address = format!("{}:{}", addr, port);
+        let address = super::format_addr(addr, port);
+        let connect_timeout = std::time::Duration::from_secs(config::CONNECT_TIMEOUT_SECS);
         let mut delay_ms = config::RECONNECT_INITIAL_MS;
 
         loop {
-            match std::net::TcpStream::connect(&address) {
+            match Self::connect_stream(&address, connect_timeout) {
                 Ok(stream) => {
                     println!("已连接到 {}", address);
                     if let Err(e) = Self::apply_timeouts(&stream) {
                         eprintln!("设置超时失败: {}", e);
                     }
-                    return Self { stream };
+                    return Self { stream, line_reader: None };
                 }
                 Err(e) => {
                     eprintln!("连接失败: {}，{}ms 后重试", e, delay_ms);
@@ -120,6 +134,46 @@ impl TcpClient {
         Self::connect_until_success(config::CLIENT_DEFAULT_ADDR, config::CLIENT_DEFAULT_PORT)
     }
 
+    //========================================
+    //建立连接
+    //========================================
+
+    ///解析地址并带超时连接，依次尝试每个解析结果
+    fn connect_stream(address: &str, timeout: std::time::Duration) -> std::io::Result<std::net::TcpStream> {
+        use std::net::ToSocketAddrs;
+
+        let addrs: Vec<std::net::SocketAddr> = address
+            .to_socket_addrs()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::NotFound, format!("域名解析失败 {}: {}", address, e)))?
+            .collect();
+        if addrs.is_empty() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("域名解析失败 {}: 没有可用地址", address),
+            ));
+        }
+
+        let mut last_err = None;
+        let mut all_timed_out = true;
+        for socket_addr in &addrs {
+            match std::net::TcpStream::connect_timeout(socket_addr, timeout) {
+                Ok(stream) => return Ok(stream),
+                Err(e) => {
+                    all_timed_out &= e.kind() == std::io::ErrorKind::TimedOut;
+                    last_err = Some(e);
+                }
+            }
+        }
+
+        if all_timed_out {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("连接超时 {}（尝试 {} 个地址，每个 {:?}）", address, addrs.len(), timeout),
+            ));
+        }
+        Err(last_err.unwrap())
+    }
+
     //========================================
     //超时设置
     //========================================
@@ -146,6 +200,17 @@ impl TcpClient {
         self.stream.flush()
     }
 
+    ///发送已构造好的消息（如转发收到的消息）
+    pub fn send_message(&mut self, msg: &protocol::Message) -> std::io::Result<()> {
+        self.send_raw(&msg.to_bytes())
+    }
+
+    ///发送一行 JSON（JSON-lines 格式，不使用二进制协议头）
+    pub fn send_jsonline<T: serde::Serialize>(&mut self, value: &T) -> std::io::Result<()> {
+        let line = protocol::jsonlines::to_line(value)?;
+        self.send_raw(&line)
+    }
+
     ///发送字符串消息
     pub fn send_string(&mut self, content: &str) -> std::io::Result<()> {
         let msg = protocol::Message::string(content);
@@ -186,6 +251,23 @@ impl TcpClient {
         self.send_raw(&msg.to_bytes())
     }
 
+    ///发送 JSON 消息（使用二进制协议头，对端 parse_message_content 得到 ParsedContent::Json）
+    pub fn send_json<T: serde::Serialize>(&mut self, value: &T) -> std::io::Result<()> {
+        let msg = protocol::Message::json(value)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        self.send_raw(&msg.to_bytes())
+    }
+
+    ///发送心跳请求
+    pub fn send_ping(&mut self, payload: Vec<u8>) -> std::io::Result<()> {
+        self.send_raw(&protocol::Message::ping(payload).to_bytes())
+    }
+
+    ///发送心跳响应
+    pub fn send_pong(&mut self, payload: Vec<u8>) -> std::io::Result<()> {
+        self.send_raw(&protocol::Message::pong(payload).to_bytes())
+    }
+
     ///发送大文件（分块传输）
     pub fn send_file_chunked(&mut self, path: &std::path::Path) -> std::io::Result<()> {
         use std::io::Read;
@@ -203,15 +285,31 @@ impl TcpClient {
         let meta_bytes = meta.to_bytes();
 
         //构造消息头
-        let header = protocol::MessageHeader::new(
+        let mut header = protocol::MessageHeader::new(
             protocol::MessageType::File,
             meta_bytes.len() as u64 + file_size,
         );
+
+        let mut buffer = vec![0u8; config::CHUNK_SIZE];
+
+        //启用校验时先完整读一遍文件计算 CRC32（不把整个文件载入内存）
+        if config::ENABLE_CHECKSUM {
+            let mut crc = protocol::crc32(&meta_bytes);
+            loop {
+                let bytes_read = file.read(&mut buffer)?;
+                if bytes_read == 0 {
+                    break;
+                }
+                crc = protocol::crc32_update(crc, &buffer[..bytes_read]);
+            }
+            header = header.with_checksum(crc);
+            std::io::Seek::rewind(&mut file)?;
+        }
+
         self.send_raw(&header.to_bytes())?;
         self.send_raw(&meta_bytes)?;
 
         //分块发送文件内容
-        let mut buffer = vec![0u8; config::CHUNK_SIZE];
         loop {
             let bytes_read = file.read(&mut buffer)?;
             if bytes_read == 0 {
@@ -228,19 +326,63 @@ impl TcpClient {
     //========================================
 
     ///接收一条完整消息
-    pub fn recv_message(&mut self) -> std::io::Result<protocol::Message> {
-        use std::io::Read;
+    ///
+    ///对端关闭连接时返回 RecvError::ConnectionClosed，可据此正常结束接收循环；
+    ///收到 Ping 时自动回复 Pong 并继续等待下一条消息
+    pub fn recv_message(&mut self) -> Result<protocol::Message, protocol::RecvError> {
+        loop {
+            let msg = protocol::read_message(&mut self.stream)?;
+            if msg.header.msg_type == protocol::MessageType::Ping {
+                self.send_pong(msg.data)?;
+                continue;
+            }
+            return Ok(msg);
+        }
+    }
 
-        let mut header_buf = [0u8; protocol::HEADER_SIZE];
-        self.stream.read_exact(&mut header_buf)?;
+    ///接收一条消息，数据部分分块写入 writer（适合大文件，不整体载入内存）
+    ///
+    ///data_len 超过 max_len 时拒绝接收并返回错误（此后应关闭连接）；
+    ///File/Image 消息只写入文件内容，文件名见返回值的 filename。收到 Ping 时自动回复 Pong
+    pub fn recv_message_to_writer<W: std::io::Write>(
+        &mut self,
+        writer: &mut W,
+        max_len: u64,
+    ) -> Result<protocol::StreamedMessage, protocol::RecvError> {
+        loop {
+            let header = protocol::read_header(&mut self.stream)?;
+            if header.msg_type == protocol::MessageType::Ping {
+                let mut payload = Vec::new();
+                protocol::read_payload_to_writer(&mut self.stream, &mut payload, header, max_len)?;
+                self.send_pong(payload)?;
+                continue;
+            }
+            return protocol::read_payload_to_writer(&mut self.stream, writer, header, max_len);
+        }
+    }
 
-        let header = protocol::MessageHeader::from_bytes(&header_buf)
-            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "无效的消息头"))?;
+    ///接收一行 JSON（JSON-lines 格式）
+    ///
+    ///内部使用缓冲读取，同一连接上不要与 recv_message() 混用
+    pub fn recv_jsonline<T: serde::de::DeserializeOwned>(&mut self) -> std::io::Result<T> {
+        if self.line_reader.is_none() {
+            self.line_reader = Some(std::io::BufReader::new(self.stream.try_clone()?));
+        }
+        let reader = self.line_reader.as_mut().unwrap();
+        protocol::jsonlines::read_line(reader)
+    }
 
-        let mut data = vec![0u8; header.data_len as usize];
-        self.stream.read_exact(&mut data)?;
+    //========================================
+    //读写拆分
+    //========================================
 
-        Ok(protocol::Message { header, data })
+    ///拆分为读端和写端，可分别在不同线程中接收和发送
+    ///
+    ///内部通过 try_clone 复制底层连接，两端共享同一个 TCP 连接
+    pub fn split(self) -> std::io::Result<(TcpReader, TcpWriter)> {
+        let writer = TcpWriter { stream: self.stream.try_clone()? };
+        let reader = TcpReader { stream: self.stream, line_reader: self.line_reader };
+        Ok((reader, writer))
     }
 
     //========================================
@@ -257,3 +399,196 @@ impl TcpClient {
         &self.stream
     }
 }
+
+//========================================
+//读端/写端（split 后使用）
+//========================================
+
+///TCP 连接读端
+pub struct TcpReader {
+    ///底层 TCP 连接
+    stream: std::net::TcpStream,
+    ///JSON-lines 模式的缓冲读取器（首次使用时创建）
+    line_reader: Option<std::io::BufReader<std::net::TcpStream>>,
+}
+
+impl TcpReader {
+    ///接收一条完整消息
+    ///
+    ///读端无法发送数据，收到的 Ping 会原样返回，需由写端调用 send_pong() 回复
+    pub fn recv_message(&mut self) -> Result<protocol::Message, protocol::RecvError> {
+        protocol::read_message(&mut self.stream)
+    }
+
+    ///接收一行 JSON（JSON-lines 格式）
+    pub fn recv_jsonline<T: serde::de::DeserializeOwned>(&mut self) -> std::io::Result<T> {
+        if self.line_reader.is_none() {
+            self.line_reader = Some(std::io::BufReader::new(self.stream.try_clone()?));
+        }
+        let reader = self.line_reader.as_mut().unwrap();
+        protocol::jsonlines::read_line(reader)
+    }
+
+    ///获取底层流的只读引用
+    pub fn stream(&self) -> &std::net::TcpStream {
+        &self.stream
+    }
+}
+
+///TCP 连接写端
+pub struct TcpWriter {
+    ///底层 TCP 连接
+    stream: std::net::TcpStream,
+}
+
+impl TcpWriter {
+    ///发送已构造好的消息
+    pub fn send_message(&mut self, msg: &protocol::Message) -> std::io::Result<()> {
+        use std::io::Write;
+        self.stream.write_all(&msg.to_bytes())?;
+        self.stream.flush()
+    }
+
+    ///发送一行 JSON（JSON-lines 格式）
+    pub fn send_jsonline<T: serde::Serialize>(&mut self, value: &T) -> std::io::Result<()> {
+        use std::io::Write;
+        let line = protocol::jsonlines::to_line(value)?;
+        self.stream.write_all(&line)?;
+        self.stream.flush()
+    }
+
+    ///发送字符串消息
+    pub fn send_string(&mut self, content: &str) -> std::io::Result<()> {
+        self.send_message(&protocol::Message::string(content))
+    }
+
+    ///发送字节数据
+    pub fn send_bytes(&mut self, data: Vec<u8>) -> std::io::Result<()> {
+        self.send_message(&protocol::Message::bytes(data))
+    }
+
+    ///发送文件
+    pub fn send_file(&mut self, path: &std::path::Path) -> std::io::Result<()> {
+        let filename = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+        let content = std::fs::read(path)?;
+        self.send_message(&protocol::Message::file(filename, content))
+    }
+
+    ///发送图片
+    pub fn send_image(&mut self, path: &std::path::Path) -> std::io::Result<()> {
+        let filename = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+        let content = std::fs::read(path)?;
+        self.send_message(&protocol::Message::image(filename, content))
+    }
+
+    ///发送视频帧
+    pub fn send_video_frame(&mut self, frame_data: Vec<u8>) -> std::io::Result<()> {
+        self.send_message(&protocol::Message::video_frame(frame_data))
+    }
+
+    ///发送 JSON 消息
+    pub fn send_json<T: serde::Serialize>(&mut self, value: &T) -> std::io::Result<()> {
+        let msg = protocol::Message::json(value)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        self.send_message(&msg)
+    }
+
+    ///发送心跳请求
+    pub fn send_ping(&mut self, payload: Vec<u8>) -> std::io::Result<()> {
+        self.send_message(&protocol::Message::ping(payload))
+    }
+
+    ///发送心跳响应
+    pub fn send_pong(&mut self, payload: Vec<u8>) -> std::io::Result<()> {
+        self.send_message(&protocol::Message::pong(payload))
+    }
+
+    ///关闭写方向（通知对端不再发送数据，读端仍可继续接收）
+    pub fn shutdown(&self) -> std::io::Result<()> {
+        self.stream.shutdown(std::net::Shutdown::Write)
+    }
+}
+
+//========================================
+//测试
+//========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///测试文件内容：第 i 个字节为 i 的简单函数，便于不载入内存地生成和比较
+    fn pattern_byte(i: u64) -> u8 {
+        (i % 251) as u8 ^ (i >> 16) as u8
+    }
+
+    ///分块生成测试数据写入 writer
+    fn write_pattern<W: std::io::Write>(writer: &mut W, len: u64) {
+        let mut chunk = vec![0u8; 64 * 1024];
+        let mut offset = 0;
+        while offset < len {
+            let n = chunk.len().min((len - offset) as usize);
+            for (j, byte) in chunk[..n].iter_mut().enumerate() {
+                *byte = pattern_byte(offset + j as u64);
+            }
+            writer.write_all(&chunk[..n]).unwrap();
+            offset += n as u64;
+        }
+    }
+
+    #[test]
+    fn recv_message_to_writer_streams_large_file() {
+        use std::io::{Read, Write};
+
+        const LEN: u64 = 50 * 1024 * 1024;
+        let dir = std::env::temp_dir().join(format!("tcp_stream_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("source.bin");
+        let received = dir.join("received.bin");
+        write_pattern(&mut std::io::BufWriter::new(std::fs::File::create(&source).unwrap()), LEN);
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let sender = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let meta = protocol::FileMeta::new("source.bin").to_bytes();
+            let header = protocol::MessageHeader::new(protocol::MessageType::File, meta.len() as u64 + LEN);
+            stream.write_all(&header.to_bytes()).unwrap();
+            stream.write_all(&meta).unwrap();
+            std::io::copy(&mut std::fs::File::open(&source).unwrap(), &mut stream).unwrap();
+        });
+
+        let mut client = TcpClient::connect_once("127.0.0.1", port).unwrap();
+        let mut file = std::io::BufWriter::new(std::fs::File::create(&received).unwrap());
+        let streamed = client.recv_message_to_writer(&mut file, LEN + 1024).unwrap();
+        drop(file);
+        sender.join().unwrap();
+
+        assert_eq!(streamed.filename.as_deref(), Some("source.bin"));
+        assert_eq!(streamed.written, LEN);
+        assert_eq!(std::fs::metadata(&received).unwrap().len(), LEN);
+
+        //逐块比较接收到的文件
+        let mut reader = std::io::BufReader::new(std::fs::File::open(&received).unwrap());
+        let mut chunk = vec![0u8; 64 * 1024];
+        let mut offset = 0u64;
+        loop {
+            let n = reader.read(&mut chunk).unwrap();
+            if n == 0 {
+                break;
+            }
+            for (j, byte) in chunk[..n].iter().enumerate() {
+                assert_eq!(*byte, pattern_byte(offset + j as u64), "offset {}", offset + j as u64);
+            }
+            offset += n as u64;
+        }
+        assert_eq!(offset, LEN);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -13,9 +13,18 @@ pub const SERVER_DEFAULT_PORT: u16 = 8080;
 ///服务端默认绑定地址
 pub const SERVER_DEFAULT_ADDR: &str = "0.0.0.0";
 
+///服务端默认 IPv6 绑定地址（Linux 下通常同时接受 IPv4 连接）
+pub const SERVER_DEFAULT_ADDR_V6: &str = "::";
+
 ///接收缓冲区大小（字节）
 pub const RECV_BUFFER_SIZE: usize = 65536;
 
+///可停止的服务循环检查停止标志的间隔（毫秒）
+pub const SERVER_POLL_INTERVAL_MS: u64 = 50;
+
+///停止服务时等待处理线程结束的最长时间（秒）
+pub const SHUTDOWN_TIMEOUT_SECS: u64 = 5;
+
 //========================================
 //客户端配置
 //========================================
@@ -46,6 +55,15 @@ pub const RECONNECT_MULTIPLIER: f64 = 1.5;
 ///文件分块大小（字节）
 pub const CHUNK_SIZE: usize = 8192;
 
+///发送时是否附加 CRC32 校验和
+///
+///接收端总是能识别两种格式；默认关闭以兼容旧版本对端（不认识带校验和的消息头），
+///通信双方均已升级后可设为 true
+pub const ENABLE_CHECKSUM: bool = false;
+
+///JSON-lines 模式单行最大长度（字节，含换行符），超过时接收返回错误
+pub const JSON_LINE_MAX_LEN: u64 = 16 * 1024 * 1024;
+
 ///连接超时时间（秒）
 pub const CONNECT_TIMEOUT_SECS: u64 = 10;
 
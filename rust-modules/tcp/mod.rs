@@ -2,7 +2,7 @@
 //!
 //!提供完整的 TCP 客户端/服务端功能，支持多种连接模式和消息类型。
 //!
-//!依赖：无（纯标准库）
+//!依赖：无（纯标准库）；JSON 消息和 JSON-lines 模式需要 serde + serde_json
 //!
 //!# 模块结构
 //!- `config` - 配置项（端口、超时、缓冲区等）
@@ -54,6 +54,19 @@ pub mod server;
 //便捷重导出
 //========================================
 
-pub use client::TcpClient;
-pub use server::{TcpServer, ClientConnection};
-pub use protocol::{Message, MessageType, ParsedContent, parse_message_content};
+pub use client::{TcpClient, TcpReader, TcpWriter};
+pub use server::{TcpServer, ClientConnection, ServerHandle};
+pub use protocol::{Message, MessageType, ParsedContent, RecvError, StreamedMessage, parse_message_content};
+
+//========================================
+//地址工具
+//========================================
+
+///拼接 "地址:端口"，IPv6 地址自动加方括号（如 "::1" -> "[::1]:8080"）
+pub fn format_addr(addr: &str, port: u16) -> String {
+    if addr.contains(':') && !addr.starts_with('[') {
+        format!("[{}]:{}", addr, port)
+    } else {
+        format!("{}:{}", addr, port)
+    }
+}
@@ -1,7 +1,15 @@
 //!TCP 消息协议模块
 //!
 //!定义统一的消息类型和协议格式。
-//!协议格式：[类型:1字节][长度:8字节][数据:N字节]
+//!协议格式：[版本+类型:1字节][长度:8字节][CRC32:4字节][数据:N字节]
+//!
+//!首字节高 4 位为协议版本，低 4 位为消息类型：
+//!- 版本 0：旧格式，没有 CRC32 字段（消息头 9 字节）
+//!- 版本 1：带 CRC32 校验（消息头 13 字节）
+//!
+//!接收端按版本自动识别，发送端由 config::ENABLE_CHECKSUM 决定使用哪个版本
+
+use super::config;
 
 //========================================
 //消息类型定义
@@ -20,6 +28,12 @@ pub enum MessageType {
     Image = 4,
     ///视频流
     VideoStream = 5,
+    ///心跳请求（对端应回复相同数据的 Pong）
+    Ping = 6,
+    ///心跳响应
+    Pong = 7,
+    ///JSON 结构化消息（需 serde + serde_json）
+    Json = 8,
 }
 
 impl MessageType {
@@ -31,6 +45,9 @@ impl MessageType {
             3 => Some(Self::File),
             4 => Some(Self::Image),
             5 => Some(Self::VideoStream),
+            6 => Some(Self::Ping),
+            7 => Some(Self::Pong),
+            8 => Some(Self::Json),
             _ => None,
         }
     }
@@ -41,12 +58,59 @@ impl MessageType {
     }
 }
 
+//========================================
+//CRC32 校验
+//========================================
+
+///CRC32（IEEE 802.3）查找表
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+///增量计算 CRC32（初始值传 0，可分块连续调用）
+pub fn crc32_update(crc: u32, data: &[u8]) -> u32 {
+    let mut crc = !crc;
+    for &byte in data {
+        crc = CRC32_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+///计算 CRC32
+pub fn crc32(data: &[u8]) -> u32 {
+    crc32_update(0, data)
+}
+
 //========================================
 //消息头结构
 //========================================
 
-///消息头大小（字节）
-pub const HEADER_SIZE: usize = 9;
+///旧格式消息头大小（字节，不含校验和）
+pub const BASE_HEADER_SIZE: usize = 9;
+
+///校验和字段大小（字节）
+pub const CHECKSUM_SIZE: usize = 4;
+
+///消息头大小（字节，含校验和）
+pub const HEADER_SIZE: usize = BASE_HEADER_SIZE + CHECKSUM_SIZE;
+
+///旧格式协议版本（无校验和）
+pub const PROTOCOL_VERSION_LEGACY: u8 = 0;
+
+///当前协议版本（带 CRC32 校验和）
+pub const PROTOCOL_VERSION: u8 = 1;
 
 ///消息头结构
 #[derive(Debug, Clone)]
@@ -55,30 +119,63 @@ pub struct MessageHeader {
     pub msg_type: MessageType,
     ///数据长度
     pub data_len: u64,
+    ///数据的 CRC32（None 表示旧格式，不校验）
+    pub checksum: Option<u32>,
 }
 
 impl MessageHeader {
-    ///创建新的消息头
+    ///创建新的消息头（不带校验和）
     pub fn new(msg_type: MessageType, data_len: u64) -> Self {
-        Self { msg_type, data_len }
+        Self { msg_type, data_len, checksum: None }
     }
 
-    ///序列化为字节数组
-    pub fn to_bytes(&self) -> [u8; HEADER_SIZE] {
-        let mut bytes = [0u8; HEADER_SIZE];
-        bytes[0] = self.msg_type.to_u8();
-        bytes[1..9].copy_from_slice(&self.data_len.to_be_bytes());
+    ///设置校验和
+    pub fn with_checksum(mut self, checksum: u32) -> Self {
+        self.checksum = Some(checksum);
+        self
+    }
+
+    ///协议版本（有校验和为 PROTOCOL_VERSION，否则为 PROTOCOL_VERSION_LEGACY）
+    pub fn version(&self) -> u8 {
+        if self.checksum.is_some() {
+            PROTOCOL_VERSION
+        } else {
+            PROTOCOL_VERSION_LEGACY
+        }
+    }
+
+    ///序列化为字节（有校验和时 13 字节，否则 9 字节）
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_SIZE);
+        bytes.push((self.version() << 4) | self.msg_type.to_u8());
+        bytes.extend_from_slice(&self.data_len.to_be_bytes());
+        if let Some(checksum) = self.checksum {
+            bytes.extend_from_slice(&checksum.to_be_bytes());
+        }
         bytes
     }
 
-    ///从字节数组反序列化
-    pub fn from_bytes(bytes: &[u8; HEADER_SIZE]) -> Option<Self> {
-        let msg_type = MessageType::from_u8(bytes[0])?;
+    ///从字节反序列化（根据首字节中的版本决定是否读取校验和）
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < BASE_HEADER_SIZE {
+            return None;
+        }
+        let msg_type = MessageType::from_u8(bytes[0] & 0x0F)?;
         let data_len = u64::from_be_bytes(bytes[1..9].try_into().ok()?);
-        Some(Self { msg_type, data_len })
+        let checksum = match header_version(bytes[0]) {
+            PROTOCOL_VERSION_LEGACY => None,
+            PROTOCOL_VERSION => Some(u32::from_be_bytes(bytes.get(9..HEADER_SIZE)?.try_into().ok()?)),
+            _ => return None,
+        };
+        Some(Self { msg_type, data_len, checksum })
     }
 }
 
+///从消息头首字节取出协议版本
+pub fn header_version(first_byte: u8) -> u8 {
+    first_byte >> 4
+}
+
 //========================================
 //文件元信息
 //========================================
@@ -190,16 +287,258 @@ impl Message {
         }
     }
 
+    ///创建心跳请求（payload 用于匹配对应的 Pong，可为空）
+    pub fn ping(payload: Vec<u8>) -> Self {
+        Self {
+            header: MessageHeader::new(MessageType::Ping, payload.len() as u64),
+            data: payload,
+        }
+    }
+
+    ///创建心跳响应（payload 应与收到的 Ping 相同）
+    pub fn pong(payload: Vec<u8>) -> Self {
+        Self {
+            header: MessageHeader::new(MessageType::Pong, payload.len() as u64),
+            data: payload,
+        }
+    }
+
+    ///创建 JSON 消息（将任意可序列化的值编码为数据部分）
+    pub fn json<T: serde::Serialize>(value: &T) -> Result<Self, serde_json::Error> {
+        let data = serde_json::to_vec(value)?;
+        Ok(Self {
+            header: MessageHeader::new(MessageType::Json, data.len() as u64),
+            data,
+        })
+    }
+
+    ///将 JSON 消息的数据部分反序列化为指定类型
+    pub fn to_json<T: serde::de::DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_slice(&self.data)
+    }
+
     ///序列化完整消息
+    ///
+    ///config::ENABLE_CHECKSUM 为 true 时附加数据的 CRC32，否则使用旧格式
     pub fn to_bytes(&self) -> Vec<u8> {
-        let header_bytes = self.header.to_bytes();
-        let mut bytes = Vec::with_capacity(HEADER_SIZE + self.data.len());
+        let header = if config::ENABLE_CHECKSUM {
+            self.header.clone().with_checksum(crc32(&self.data))
+        } else {
+            MessageHeader { checksum: None, ..self.header.clone() }
+        };
+        let header_bytes = header.to_bytes();
+        let mut bytes = Vec::with_capacity(header_bytes.len() + self.data.len());
         bytes.extend_from_slice(&header_bytes);
         bytes.extend_from_slice(&self.data);
         bytes
     }
 }
 
+//========================================
+//消息接收
+//========================================
+
+///接收消息时的错误
+#[derive(Debug)]
+pub enum RecvError {
+    ///对端正常关闭连接（读取消息头前即遇到 EOF）
+    ConnectionClosed,
+    ///IO 错误（包括读取消息中途连接断开）
+    Io(std::io::Error),
+    ///协议错误（如无效的消息头）
+    Protocol(std::string::String),
+    ///数据校验失败（CRC32 不匹配，传输中数据损坏）
+    Checksum { expected: u32, actual: u32 },
+}
+
+impl std::fmt::Display for RecvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecvError::ConnectionClosed => write!(f, "连接已关闭"),
+            RecvError::Io(e) => write!(f, "IO 错误: {}", e),
+            RecvError::Protocol(msg) => write!(f, "协议错误: {}", msg),
+            RecvError::Checksum { expected, actual } => {
+                write!(f, "数据校验失败: 期望 CRC32 {:08x}，实际 {:08x}", expected, actual)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RecvError {}
+
+impl From<std::io::Error> for RecvError {
+    fn from(e: std::io::Error) -> Self {
+        RecvError::Io(e)
+    }
+}
+
+impl From<RecvError> for std::io::Error {
+    fn from(e: RecvError) -> Self {
+        match e {
+            RecvError::ConnectionClosed => {
+                std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "连接已关闭")
+            }
+            RecvError::Io(e) => e,
+            RecvError::Protocol(msg) => std::io::Error::new(std::io::ErrorKind::InvalidData, msg),
+            e @ RecvError::Checksum { .. } => {
+                std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string())
+            }
+        }
+    }
+}
+
+///从流中读取一条完整消息
+///
+///读取消息头前遇到 EOF 返回 ConnectionClosed；消息读到一半断开返回 Io(UnexpectedEof)；
+///消息头带校验和且与数据不符时返回 Checksum
+pub fn read_message<R: std::io::Read>(reader: &mut R) -> Result<Message, RecvError> {
+    let header = read_header(reader)?;
+    read_payload(reader, header)
+}
+
+///读取消息头（根据版本自动读取校验和字段）
+pub fn read_header<R: std::io::Read>(reader: &mut R) -> Result<MessageHeader, RecvError> {
+    let mut header_buf = [0u8; HEADER_SIZE];
+    let mut filled = 0;
+    while filled < BASE_HEADER_SIZE {
+        match reader.read(&mut header_buf[filled..BASE_HEADER_SIZE]) {
+            Ok(0) if filled == 0 => return Err(RecvError::ConnectionClosed),
+            Ok(0) => {
+                return Err(RecvError::Io(std::io::Error::new(
+                    std::io::ErrorKind::UnexpectedEof,
+                    "读取消息头时连接断开",
+                )));
+            }
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(RecvError::Io(e)),
+        }
+    }
+
+    let header_len = match header_version(header_buf[0]) {
+        PROTOCOL_VERSION_LEGACY => BASE_HEADER_SIZE,
+        PROTOCOL_VERSION => {
+            reader.read_exact(&mut header_buf[BASE_HEADER_SIZE..HEADER_SIZE])?;
+            HEADER_SIZE
+        }
+        v => return Err(RecvError::Protocol(format!("不支持的协议版本: {}", v))),
+    };
+
+    MessageHeader::from_bytes(&header_buf[..header_len])
+        .ok_or_else(|| RecvError::Protocol(format!("无效的消息类型: {}", header_buf[0] & 0x0F)))
+}
+
+///读取消息头之后的数据部分（整体载入内存）并校验
+///
+///# 注意
+///按消息头中的 data_len 一次性分配缓冲区，不限制长度：对端可以用一个很大的 data_len 耗尽内存。
+///recv_message() 走的就是这条路径，只适合可信对端；不可信对端请使用带 max_len 的
+///read_payload_to_writer() / recv_message_to_writer()
+pub fn read_payload<R: std::io::Read>(reader: &mut R, header: MessageHeader) -> Result<Message, RecvError> {
+    let mut data = vec![0u8; header.data_len as usize];
+    reader.read_exact(&mut data)?;
+
+    if let Some(expected) = header.checksum {
+        let actual = crc32(&data);
+        if actual != expected {
+            return Err(RecvError::Checksum { expected, actual });
+        }
+    }
+
+    Ok(Message { header, data })
+}
+
+//========================================
+//流式接收
+//========================================
+
+///流式接收的消息信息（数据已写入 writer）
+#[derive(Debug, Clone)]
+pub struct StreamedMessage {
+    ///消息头
+    pub header: MessageHeader,
+    ///文件名（File/Image 消息，文件元信息不会写入 writer）
+    pub filename: Option<std::string::String>,
+    ///写入 writer 的字节数
+    pub written: u64,
+}
+
+///从流中读取一条消息，数据部分按 config::CHUNK_SIZE 分块写入 writer（不整体载入内存）
+///
+///data_len 超过 max_len 时在读取数据前返回 Protocol 错误，此时流中仍残留该消息的数据，应关闭连接
+pub fn read_message_to_writer<R, W>(reader: &mut R, writer: &mut W, max_len: u64) -> Result<StreamedMessage, RecvError>
+where
+    R: std::io::Read,
+    W: std::io::Write,
+{
+    let header = read_header(reader)?;
+    read_payload_to_writer(reader, writer, header, max_len)
+}
+
+///读取消息头之后的数据部分，分块写入 writer
+///
+///File/Image 消息的文件元信息会被解析为文件名，只把文件内容写入 writer。
+///校验和在全部写入后才能确认，返回 Checksum 错误时 writer 中已是损坏的数据，需由调用方丢弃
+pub fn read_payload_to_writer<R, W>(
+    reader: &mut R,
+    writer: &mut W,
+    header: MessageHeader,
+    max_len: u64,
+) -> Result<StreamedMessage, RecvError>
+where
+    R: std::io::Read,
+    W: std::io::Write,
+{
+    if header.data_len > max_len {
+        return Err(RecvError::Protocol(format!("消息长度 {} 超过上限 {}", header.data_len, max_len)));
+    }
+
+    let mut remaining = header.data_len;
+    let mut crc = 0u32;
+    let mut written = 0u64;
+    let mut filename = None;
+
+    //文件元信息：[文件名长度:2字节][文件名]
+    if matches!(header.msg_type, MessageType::File | MessageType::Image) && remaining >= FILE_META_SIZE as u64 {
+        let mut len_buf = [0u8; FILE_META_SIZE];
+        reader.read_exact(&mut len_buf)?;
+        crc = crc32_update(crc, &len_buf);
+        remaining -= FILE_META_SIZE as u64;
+
+        let name_len = u16::from_be_bytes(len_buf) as u64;
+        if name_len <= remaining {
+            let mut name_buf = vec![0u8; name_len as usize];
+            reader.read_exact(&mut name_buf)?;
+            crc = crc32_update(crc, &name_buf);
+            remaining -= name_len;
+            filename = Some(std::string::String::from_utf8_lossy(&name_buf).to_string());
+        } else {
+            //元信息无效，按原始数据写出（与 parse_message_content 的回退行为一致）
+            writer.write_all(&len_buf)?;
+            written += FILE_META_SIZE as u64;
+        }
+    }
+
+    let mut buffer = vec![0u8; config::CHUNK_SIZE.min(remaining as usize)];
+    while remaining > 0 {
+        let n = buffer.len().min(remaining as usize);
+        reader.read_exact(&mut buffer[..n])?;
+        crc = crc32_update(crc, &buffer[..n]);
+        writer.write_all(&buffer[..n])?;
+        remaining -= n as u64;
+        written += n as u64;
+    }
+    writer.flush()?;
+
+    if let Some(expected) = header.checksum {
+        if crc != expected {
+            return Err(RecvError::Checksum { expected, actual: crc });
+        }
+    }
+
+    Ok(StreamedMessage { header, filename, written })
+}
+
 //========================================
 //解析后的消息内容
 //========================================
@@ -217,6 +556,12 @@ pub enum ParsedContent {
     Image { filename: std::string::String, data: Vec<u8> },
     ///视频帧
     VideoFrame(Vec<u8>),
+    ///心跳请求
+    Ping(Vec<u8>),
+    ///心跳响应
+    Pong(Vec<u8>),
+    ///JSON 消息
+    Json(serde_json::Value),
 }
 
 ///解析接收到的消息内容
@@ -244,5 +589,168 @@ pub fn parse_message_content(msg: &Message) -> ParsedContent {
         MessageType::VideoStream => {
             ParsedContent::VideoFrame(msg.data.clone())
         }
+        MessageType::Ping => ParsedContent::Ping(msg.data.clone()),
+        MessageType::Pong => ParsedContent::Pong(msg.data.clone()),
+        MessageType::Json => match serde_json::from_slice(&msg.data) {
+            Ok(value) => ParsedContent::Json(value),
+            Err(_) => ParsedContent::Bytes(msg.data.clone()),
+        },
+    }
+}
+
+//========================================
+//JSON-lines 格式
+//========================================
+
+///JSON-lines（每行一个 JSON 对象）编解码
+///
+///用于与使用换行分隔 JSON 的工具互通，与 `[类型][长度][数据]` 二进制协议相互独立，
+///同一连接上不要混用两种格式
+///
+///需要额外依赖：serde = { version = "1", features = ["derive"] }、serde_json = "1"
+pub mod jsonlines {
+    ///序列化为一行 JSON（末尾带换行符）
+    pub fn to_line<T: serde::Serialize>(value: &T) -> std::io::Result<Vec<u8>> {
+        let mut line = serde_json::to_vec(value)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        line.push(b'\n');
+        Ok(line)
+    }
+
+    ///从 BufRead 读取一行并反序列化
+    ///
+    ///单行（含换行符）最长 config::JSON_LINE_MAX_LEN 字节，超过时返回 InvalidData 错误，
+    ///此时流中仍残留该行的剩余数据，应关闭连接；
+    ///连接关闭（读到 EOF）时返回 UnexpectedEof 错误
+    pub fn read_line<R, T>(reader: &mut R) -> std::io::Result<T>
+    where
+        R: std::io::BufRead,
+        T: serde::de::DeserializeOwned,
+    {
+        read_line_limited(reader, super::config::JSON_LINE_MAX_LEN)
+    }
+
+    ///从 BufRead 读取一行并反序列化，单行（含换行符）最长 max_len 字节
+    pub fn read_line_limited<R, T>(reader: &mut R, max_len: u64) -> std::io::Result<T>
+    where
+        R: std::io::BufRead,
+        T: serde::de::DeserializeOwned,
+    {
+        use std::io::{BufRead, Read};
+
+        let mut line = Vec::new();
+        let n = reader.by_ref().take(max_len).read_until(b'\n', &mut line)?;
+        if n == 0 {
+            return Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "连接已关闭"));
+        }
+        if line.last() != Some(&b'\n') && n as u64 >= max_len {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("JSON 行长度超过上限 {} 字节", max_len),
+            ));
+        }
+        while matches!(line.last(), Some(b'\n' | b'\r')) {
+            line.pop();
+        }
+        serde_json::from_slice(&line)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+//========================================
+//测试
+//========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jsonlines_reads_consecutive_lines() {
+        let mut reader = std::io::Cursor::new(b"{\"a\":1}\r\n[2,3]\n".to_vec());
+        let first: serde_json::Value = jsonlines::read_line(&mut reader).unwrap();
+        let second: Vec<u8> = jsonlines::read_line(&mut reader).unwrap();
+        assert_eq!(first, serde_json::json!({"a": 1}));
+        assert_eq!(second, vec![2, 3]);
+        let eof = jsonlines::read_line::<_, serde_json::Value>(&mut reader).unwrap_err();
+        assert_eq!(eof.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn jsonlines_rejects_line_over_limit() {
+        let mut reader = std::io::Cursor::new(b"\"0123456789\"\n".to_vec());
+        let err = jsonlines::read_line_limited::<_, String>(&mut reader, 8).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+        //恰好等于上限（含换行符）时正常读取
+        let mut reader = std::io::Cursor::new(b"\"0123456789\"\n".to_vec());
+        let value: String = jsonlines::read_line_limited(&mut reader, 13).unwrap();
+        assert_eq!(value, "0123456789");
+    }
+
+    ///按 v1 格式（带校验和）编码消息，不受 config::ENABLE_CHECKSUM 影响
+    fn encode_v1(msg: &Message) -> Vec<u8> {
+        let mut bytes = msg.header.clone().with_checksum(crc32(&msg.data)).to_bytes();
+        bytes.extend_from_slice(&msg.data);
+        bytes
+    }
+
+    #[test]
+    fn v1_message_round_trips() {
+        let bytes = encode_v1(&Message::string("hello"));
+        assert_eq!(header_version(bytes[0]), PROTOCOL_VERSION);
+        assert_eq!(bytes.len(), HEADER_SIZE + 5);
+
+        let msg = read_message(&mut std::io::Cursor::new(bytes)).unwrap();
+        assert_eq!(msg.data, b"hello");
+        assert!(msg.header.checksum.is_some());
+    }
+
+    #[test]
+    fn corrupted_payload_fails_checksum() {
+        let mut bytes = encode_v1(&Message::bytes(b"payload bytes".to_vec()));
+        bytes[HEADER_SIZE + 3] ^= 0x01;
+
+        let err = read_message(&mut std::io::Cursor::new(bytes.clone())).unwrap_err();
+        assert!(matches!(err, RecvError::Checksum { .. }), "{:?}", err);
+        assert_eq!(std::io::Error::from(err).kind(), std::io::ErrorKind::InvalidData);
+
+        //流式接收同样校验
+        let err = read_message_to_writer(&mut std::io::Cursor::new(bytes), &mut Vec::new(), u64::MAX).unwrap_err();
+        assert!(matches!(err, RecvError::Checksum { .. }), "{:?}", err);
+    }
+
+    #[test]
+    fn v0_message_still_decodes() {
+        let mut bytes = MessageHeader::new(MessageType::String, 2).to_bytes();
+        assert_eq!(bytes.len(), BASE_HEADER_SIZE);
+        assert_eq!(header_version(bytes[0]), PROTOCOL_VERSION_LEGACY);
+        bytes.extend_from_slice(b"hi");
+
+        let msg = read_message(&mut std::io::Cursor::new(bytes)).unwrap();
+        assert_eq!(msg.data, b"hi");
+        assert!(msg.header.checksum.is_none());
+    }
+
+    #[test]
+    fn default_sender_uses_v0_headers() {
+        let bytes = Message::string("x").to_bytes();
+        let expected = if config::ENABLE_CHECKSUM { PROTOCOL_VERSION } else { PROTOCOL_VERSION_LEGACY };
+        assert_eq!(header_version(bytes[0]), expected);
+    }
+
+    #[test]
+    fn oversized_message_is_rejected_before_reading_payload() {
+        let mut bytes = MessageHeader::new(MessageType::Bytes, 1000).to_bytes();
+        let header_len = bytes.len() as u64;
+        bytes.extend_from_slice(&[0u8; 1000]);
+        let mut reader = std::io::Cursor::new(bytes);
+        let mut writer = Vec::new();
+
+        let err = read_message_to_writer(&mut reader, &mut writer, 10).unwrap_err();
+        assert!(matches!(err, RecvError::Protocol(_)), "{:?}", err);
+        //只读取了消息头，数据部分未被读取也未写入
+        assert_eq!(reader.position(), header_len);
+        assert!(writer.is_empty());
     }
 }
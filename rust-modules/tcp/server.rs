@@ -15,28 +15,74 @@ pub struct ClientConnection {
     stream: std::net::TcpStream,
     ///客户端地址
     addr: std::net::SocketAddr,
+    ///JSON-lines 模式的缓冲读取器（首次使用时创建）
+    line_reader: Option<std::io::BufReader<std::net::TcpStream>>,
+    ///check_alive() 等待 Pong 期间收到的其他消息，由 recv_message() 优先返回
+    pending: std::collections::VecDeque<protocol::Message>,
 }
 
 impl ClientConnection {
+    ///包装已接受的连接
+    fn new(stream: std::net::TcpStream, addr: std::net::SocketAddr) -> Self {
+        Self { stream, addr, line_reader: None, pending: std::collections::VecDeque::new() }
+    }
+
     ///获取客户端地址
     pub fn addr(&self) -> &std::net::SocketAddr {
         &self.addr
     }
 
     ///读取一条完整消息
-    pub fn recv_message(&mut self) -> std::io::Result<protocol::Message> {
-        use std::io::Read;
-
-        let mut header_buf = [0u8; protocol::HEADER_SIZE];
-        self.stream.read_exact(&mut header_buf)?;
-
-        let header = protocol::MessageHeader::from_bytes(&header_buf)
-            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "无效的消息头"))?;
+    ///
+    ///对端关闭连接时返回 RecvError::ConnectionClosed，可据此正常结束接收循环；
+    ///收到 Ping 时自动回复 Pong 并继续等待下一条消息
+    pub fn recv_message(&mut self) -> Result<protocol::Message, protocol::RecvError> {
+        if let Some(msg) = self.pending.pop_front() {
+            return Ok(msg);
+        }
+        loop {
+            let msg = protocol::read_message(&mut self.stream)?;
+            if msg.header.msg_type == protocol::MessageType::Ping {
+                self.send_pong(msg.data)?;
+                continue;
+            }
+            return Ok(msg);
+        }
+    }
 
-        let mut data = vec![0u8; header.data_len as usize];
-        self.stream.read_exact(&mut data)?;
+    ///接收一条消息，数据部分分块写入 writer（适合大文件，不整体载入内存）
+    ///
+    ///data_len 超过 max_len 时拒绝接收并返回错误（此后应关闭连接）；
+    ///File/Image 消息只写入文件内容，文件名见返回值的 filename。收到 Ping 时自动回复 Pong
+    pub fn recv_message_to_writer<W: std::io::Write>(
+        &mut self,
+        writer: &mut W,
+        max_len: u64,
+    ) -> Result<protocol::StreamedMessage, protocol::RecvError> {
+        if let Some(msg) = self.pending.pop_front() {
+            return protocol::read_payload_to_writer(&mut msg.data.as_slice(), writer, msg.header, max_len);
+        }
+        loop {
+            let header = protocol::read_header(&mut self.stream)?;
+            if header.msg_type == protocol::MessageType::Ping {
+                let mut payload = Vec::new();
+                protocol::read_payload_to_writer(&mut self.stream, &mut payload, header, max_len)?;
+                self.send_pong(payload)?;
+                continue;
+            }
+            return protocol::read_payload_to_writer(&mut self.stream, writer, header, max_len);
+        }
+    }
 
-        Ok(protocol::Message { header, data })
+    ///读取一行 JSON（JSON-lines 格式）
+    ///
+    ///内部使用缓冲读取，同一连接上不要与 recv_message() 混用
+    pub fn recv_jsonline<T: serde::de::DeserializeOwned>(&mut self) -> std::io::Result<T> {
+        if self.line_reader.is_none() {
+            self.line_reader = Some(std::io::BufReader::new(self.stream.try_clone()?));
+        }
+        let reader = self.line_reader.as_mut().unwrap();
+        protocol::jsonlines::read_line(reader)
     }
 
     ///发送原始字节
@@ -46,6 +92,17 @@ impl ClientConnection {
         self.stream.flush()
     }
 
+    ///发送已构造好的消息（如转发收到的消息）
+    pub fn send_message(&mut self, msg: &protocol::Message) -> std::io::Result<()> {
+        self.send_raw(&msg.to_bytes())
+    }
+
+    ///发送一行 JSON（JSON-lines 格式，不使用二进制协议头）
+    pub fn send_jsonline<T: serde::Serialize>(&mut self, value: &T) -> std::io::Result<()> {
+        let line = protocol::jsonlines::to_line(value)?;
+        self.send_raw(&line)
+    }
+
     ///发送字符串消息
     pub fn send_string(&mut self, content: &str) -> std::io::Result<()> {
         let msg = protocol::Message::string(content);
@@ -86,6 +143,81 @@ impl ClientConnection {
         self.send_raw(&msg.to_bytes())
     }
 
+    ///发送 JSON 消息（使用二进制协议头，对端 parse_message_content 得到 ParsedContent::Json）
+    pub fn send_json<T: serde::Serialize>(&mut self, value: &T) -> std::io::Result<()> {
+        let msg = protocol::Message::json(value)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        self.send_raw(&msg.to_bytes())
+    }
+
+    //========================================
+    //心跳检测
+    //========================================
+
+    ///发送心跳请求
+    pub fn send_ping(&mut self, payload: Vec<u8>) -> std::io::Result<()> {
+        self.send_raw(&protocol::Message::ping(payload).to_bytes())
+    }
+
+    ///发送心跳响应
+    pub fn send_pong(&mut self, payload: Vec<u8>) -> std::io::Result<()> {
+        self.send_raw(&protocol::Message::pong(payload).to_bytes())
+    }
+
+    ///发送 Ping 并等待对应的 Pong，用于检测半开连接（对端已失联但未关闭）
+    ///
+    ///返回 Ok(true) 表示连接存活，超时或对端已关闭返回 Ok(false)。
+    ///等待期间收到的其他消息会暂存，之后由 recv_message() 依次返回
+    ///
+    ///# 注意
+    ///超时可能发生在读取消息中途，返回 false 后应关闭该连接
+    pub fn check_alive(&mut self, timeout: std::time::Duration) -> std::io::Result<bool> {
+        let nonce = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0)
+            .to_be_bytes()
+            .to_vec();
+        self.send_ping(nonce.clone())?;
+
+        let old_timeout = self.stream.read_timeout()?;
+        let result = self.wait_pong(&nonce, std::time::Instant::now() + timeout);
+        self.stream.set_read_timeout(old_timeout)?;
+        result
+    }
+
+    ///等待与 nonce 匹配的 Pong 直到 deadline
+    fn wait_pong(&mut self, nonce: &[u8], deadline: std::time::Instant) -> std::io::Result<bool> {
+        loop {
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            if remaining.is_zero() {
+                return Ok(false);
+            }
+            self.stream.set_read_timeout(Some(remaining))?;
+
+            match protocol::read_message(&mut self.stream) {
+                Ok(msg) => match msg.header.msg_type {
+                    protocol::MessageType::Pong if msg.data == nonce => return Ok(true),
+                    //之前的 Pong（如上次超时后才到达），忽略
+                    protocol::MessageType::Pong => {}
+                    protocol::MessageType::Ping => self.send_pong(msg.data)?,
+                    _ => self.pending.push_back(msg),
+                },
+                Err(protocol::RecvError::ConnectionClosed) => return Ok(false),
+                Err(protocol::RecvError::Io(e))
+                    if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) =>
+                {
+                    return Ok(false);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    //========================================
+    //底层访问
+    //========================================
+
     ///获取底层流的可变引用
     pub fn stream_mut(&mut self) -> &mut std::net::TcpStream {
         &mut self.stream
@@ -114,7 +246,7 @@ impl TcpServer {
 
     ///绑定端口并启动监听
     pub fn bind(port: u16) -> std::io::Result<Self> {
-        let addr = format!("{}:{}", config::SERVER_DEFAULT_ADDR, port);
+        let addr = super::format_addr(config::SERVER_DEFAULT_ADDR, port);
         let listener = std::net::TcpListener::bind(&addr)?;
         println!("服务端已启动，监听 {}", addr);
         Ok(Self { listener })
@@ -125,9 +257,17 @@ impl TcpServer {
         Self::bind(config::SERVER_DEFAULT_PORT)
     }
 
-    ///绑定指定地址和端口
+    ///绑定 IPv6 任意地址（[::]:port）
+    ///
+    ///Linux 默认双栈，同时接受 IPv4 连接（此时不要再用 bind() 绑定同一端口）；
+    ///Windows 等仅 IPv6 的系统需另外用 bind() 监听 IPv4
+    pub fn bind_v6(port: u16) -> std::io::Result<Self> {
+        Self::bind_addr(config::SERVER_DEFAULT_ADDR_V6, port)
+    }
+
+    ///绑定指定地址和端口（支持 IPv6 地址，如 "::1"）
     pub fn bind_addr(addr: &str, port: u16) -> std::io::Result<Self> {
-        let address = format!("{}:{}", addr, port);
+        let address = super::format_addr(addr, port);
         let listener = std::net::TcpListener::bind(&address)?;
         println!("服务端已启动，监听 {}", address);
         Ok(Self { listener })
@@ -141,7 +281,28 @@ impl TcpServer {
     pub fn accept(&self) -> std::io::Result<ClientConnection> {
         let (stream, addr) = self.listener.accept()?;
         println!("客户端连接: {}", addr);
-        Ok(ClientConnection { stream, addr })
+        Ok(ClientConnection::new(stream, addr))
+    }
+
+    ///设置监听 socket 是否为非阻塞模式（配合 try_accept 使用）
+    pub fn set_nonblocking(&self, nonblocking: bool) -> std::io::Result<()> {
+        self.listener.set_nonblocking(nonblocking)
+    }
+
+    ///尝试接受一个客户端连接（非阻塞）
+    ///
+    ///需先调用 set_nonblocking(true)，当前无新连接时返回 Ok(None)
+    ///返回的连接已恢复为阻塞模式
+    pub fn try_accept(&self) -> std::io::Result<Option<ClientConnection>> {
+        match self.listener.accept() {
+            Ok((stream, addr)) => {
+                stream.set_nonblocking(false)?;
+                println!("客户端连接: {}", addr);
+                Ok(Some(ClientConnection::new(stream, addr)))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => Ok(None),
+            Err(e) => Err(e),
+        }
     }
 
     ///阻塞式运行，为每个连接调用回调函数
@@ -159,7 +320,7 @@ impl TcpServer {
                         std::net::SocketAddr::from(([0, 0, 0, 0], 0))
                     });
                     println!("客户端连接: {}", addr);
-                    let conn = ClientConnection { stream, addr };
+                    let conn = ClientConnection::new(stream, addr);
                     if !on_client(conn) {
                         println!("服务端停止");
                         break;
@@ -189,7 +350,7 @@ impl TcpServer {
                         std::net::SocketAddr::from(([0, 0, 0, 0], 0))
                     });
                     println!("客户端连接: {}", addr);
-                    let conn = ClientConnection { stream, addr };
+                    let conn = ClientConnection::new(stream, addr);
                     let handler = std::sync::Arc::clone(&handler);
 
                     std::thread::spawn(move || {
@@ -203,8 +364,180 @@ impl TcpServer {
         }
     }
 
+    //========================================
+    //可停止的运行方式
+    //========================================
+
+    ///阻塞式运行，直到 stop 被置为 true 或回调返回 false
+    ///
+    ///内部将监听 socket 切换为非阻塞模式，每隔 config::SERVER_POLL_INTERVAL_MS 检查一次停止标志
+    pub fn run_until<F>(&self, stop: &std::sync::atomic::AtomicBool, mut on_client: F) -> std::io::Result<()>
+    where
+        F: FnMut(ClientConnection) -> bool,
+    {
+        let poll_interval = std::time::Duration::from_millis(config::SERVER_POLL_INTERVAL_MS);
+        self.set_nonblocking(true)?;
+
+        while !stop.load(std::sync::atomic::Ordering::SeqCst) {
+            match self.try_accept() {
+                Ok(Some(conn)) => {
+                    if !on_client(conn) {
+                        break;
+                    }
+                }
+                Ok(None) => std::thread::sleep(poll_interval),
+                Err(e) => {
+                    //持续出错（如文件描述符耗尽）时避免空转
+                    eprintln!("接受连接失败: {}", e);
+                    std::thread::sleep(poll_interval);
+                }
+            }
+        }
+
+        println!("服务端停止");
+        self.set_nonblocking(false)
+    }
+
+    ///多线程运行，直到 stop 被置为 true
+    ///
+    ///停止后最多等待 config::SHUTDOWN_TIMEOUT_SECS 让处理线程结束，超时仍未结束的线程不再等待
+    pub fn run_threaded_until<F>(&self, stop: &std::sync::atomic::AtomicBool, handler: F) -> std::io::Result<()>
+    where
+        F: Fn(ClientConnection) + Send + Sync + 'static,
+    {
+        let handler = std::sync::Arc::new(handler);
+        let poll_interval = std::time::Duration::from_millis(config::SERVER_POLL_INTERVAL_MS);
+        let mut workers: Vec<std::thread::JoinHandle<()>> = Vec::new();
+        self.set_nonblocking(true)?;
+
+        while !stop.load(std::sync::atomic::Ordering::SeqCst) {
+            match self.try_accept() {
+                Ok(Some(conn)) => {
+                    let handler = std::sync::Arc::clone(&handler);
+                    workers.push(std::thread::spawn(move || {
+                        handler(conn);
+                    }));
+                }
+                Ok(None) => std::thread::sleep(poll_interval),
+                Err(e) => {
+                    //持续出错（如文件描述符耗尽）时避免空转
+                    eprintln!("接受连接失败: {}", e);
+                    std::thread::sleep(poll_interval);
+                }
+            }
+            workers.retain(|w| !w.is_finished());
+        }
+
+        println!("服务端停止，等待 {} 个处理线程结束", workers.len());
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(config::SHUTDOWN_TIMEOUT_SECS);
+        while workers.iter().any(|w| !w.is_finished()) && std::time::Instant::now() < deadline {
+            std::thread::sleep(poll_interval);
+        }
+        let (finished, unfinished): (Vec<_>, Vec<_>) = workers.into_iter().partition(|w| w.is_finished());
+        for worker in finished {
+            let _ = worker.join();
+        }
+        if !unfinished.is_empty() {
+            eprintln!("{} 个处理线程未在超时内结束", unfinished.len());
+        }
+
+        self.set_nonblocking(false)
+    }
+
+    ///在后台线程中多线程运行，返回可用于停止服务的句柄
+    ///
+    ///句柄 shutdown() 后监听 socket 随服务端一起释放，端口可立即重新绑定
+    pub fn spawn_threaded<F>(self, handler: F) -> std::io::Result<ServerHandle>
+    where
+        F: Fn(ClientConnection) + Send + Sync + 'static,
+    {
+        let local_addr = self.local_addr()?;
+        let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let thread_stop = std::sync::Arc::clone(&stop);
+
+        let thread = std::thread::spawn(move || {
+            if let Err(e) = self.run_threaded_until(&thread_stop, handler) {
+                eprintln!("服务端运行失败: {}", e);
+            }
+        });
+
+        Ok(ServerHandle {
+            stop,
+            thread: Some(thread),
+            local_addr,
+        })
+    }
+
     ///获取本地绑定地址
     pub fn local_addr(&self) -> std::io::Result<std::net::SocketAddr> {
         self.listener.local_addr()
     }
 }
+
+//========================================
+//服务端句柄
+//========================================
+
+///后台运行的服务端句柄（spawn_threaded 返回）
+///
+///丢弃句柄也会通知服务端停止，但不等待其结束
+pub struct ServerHandle {
+    stop: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    thread: Option<std::thread::JoinHandle<()>>,
+    local_addr: std::net::SocketAddr,
+}
+
+impl ServerHandle {
+    ///停止服务并等待监听线程退出（端口随之释放）
+    pub fn shutdown(mut self) {
+        self.stop.store(true, std::sync::atomic::Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+
+    ///检查服务是否仍在运行
+    pub fn is_running(&self) -> bool {
+        self.thread.as_ref().is_some_and(|t| !t.is_finished())
+    }
+
+    ///获取服务端绑定地址
+    pub fn local_addr(&self) -> std::net::SocketAddr {
+        self.local_addr
+    }
+}
+
+impl Drop for ServerHandle {
+    fn drop(&mut self) {
+        self.stop.store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+}
+
+//========================================
+//测试
+//========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shutdown_stops_server_and_releases_port() {
+        let server = TcpServer::bind_addr("127.0.0.1", 0).unwrap();
+        let addr = server.local_addr().unwrap();
+        let handle = server
+            .spawn_threaded(|mut conn| {
+                let _ = conn.send_string("hello");
+            })
+            .unwrap();
+        assert!(handle.is_running());
+
+        let mut client = std::net::TcpStream::connect(addr).unwrap();
+        let msg = protocol::read_message(&mut client).unwrap();
+        assert_eq!(msg.data, b"hello");
+
+        handle.shutdown();
+        //监听 socket 已释放，端口可以重新绑定
+        std::net::TcpListener::bind(addr).unwrap();
+    }
+}
@@ -1,6 +1,6 @@
 //!TOML 配置模块
 //!
-//!提供 TOML 配置文件的读取、写入功能。
+//!提供 TOML 配置文件的读取、写入、修改功能。保存时先写临时文件再重命名，写入中途崩溃不会损坏原文件。
 //!TOML 是 Rust 生态系统的标准配置格式。
 //!
 //!依赖：toml（使用时查询最新版本：https://crates.io/crates/toml）
@@ -10,6 +10,7 @@
 //![dependencies]
 //!serde = { version = "1", features = ["derive"] }
 //!toml = "0.8"
+//!chrono = "0.4"  # get_datetime_parsed() 使用，不需要可删除该方法
 //!```
 //!
 //!# 快速开始
@@ -76,15 +77,22 @@ impl TomlConfig {
         &self.data
     }
 
+    ///获取内部值的可变引用
+    pub fn inner_mut(&mut self) -> &mut toml::Value {
+        &mut self.data
+    }
+
     //========================================
     //获取值
     //========================================
 
     ///获取指定路径的值（支持点分隔路径）
+    ///
+    ///数组节点下的数字段作为索引，如 "servers.0.port"；索引越界返回 None
     pub fn get(&self, path: &str) -> Option<&toml::Value> {
         let mut current = &self.data;
         for key in path.split('.') {
-            current = current.get(key)?;
+            current = child(current, key)?;
         }
         Some(current)
     }
@@ -114,15 +122,179 @@ impl TomlConfig {
         self.get(path)?.as_array()
     }
 
+    ///获取表（可用于遍历键值对）
+    pub fn get_table(&self, path: &str) -> Option<&toml::map::Map<String, toml::Value>> {
+        self.get(path)?.as_table()
+    }
+
+    ///获取 TOML 原生日期时间值
+    ///
+    ///可能只含日期（2024-01-15）、只含时间（08:30:00）或不带时区偏移
+    pub fn get_datetime(&self, path: &str) -> Option<&toml::value::Datetime> {
+        self.get(path)?.as_datetime()
+    }
+
+    ///获取日期时间并转换为 chrono 类型
+    ///
+    ///- 带偏移（1979-05-27T07:32:00+08:00）：保留原偏移
+    ///- 不带偏移（1979-05-27T07:32:00）：按本地时区解释
+    ///- 只含日期（1979-05-27）：按本地时区当天 00:00:00
+    ///- 只含时间，或本地时间不存在（夏令时跳过）时返回 None
+    pub fn get_datetime_parsed(&self, path: &str) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+        use chrono::TimeZone;
+
+        let dt = self.get_datetime(path)?;
+        let date = dt.date?;
+        let date = chrono::NaiveDate::from_ymd_opt(date.year as i32, date.month as u32, date.day as u32)?;
+        let naive = match dt.time {
+            Some(t) => date.and_hms_nano_opt(t.hour as u32, t.minute as u32, t.second as u32, t.nanosecond)?,
+            None => date.and_hms_opt(0, 0, 0)?,
+        };
+
+        match dt.offset {
+            Some(toml::value::Offset::Z) => Some(chrono::Utc.from_utc_datetime(&naive).fixed_offset()),
+            Some(toml::value::Offset::Custom { minutes }) => {
+                let offset = chrono::FixedOffset::east_opt(minutes as i32 * 60)?;
+                offset.from_local_datetime(&naive).single()
+            }
+            None => chrono::Local.from_local_datetime(&naive).single().map(|t| t.fixed_offset()),
+        }
+    }
+
+    //========================================
+    //修改值
+    //========================================
+
+    ///获取指定路径值的可变引用（支持点分隔路径，路径规则同 get）
+    pub fn get_mut(&mut self, path: &str) -> Option<&mut toml::Value> {
+        let mut current = &mut self.data;
+        for key in path.split('.') {
+            current = child_mut(current, key)?;
+        }
+        Some(current)
+    }
+
+    ///获取数组的可变引用（可直接 push/remove 元素）
+    pub fn get_array_mut(&mut self, path: &str) -> Option<&mut Vec<toml::Value>> {
+        self.get_mut(path)?.as_array_mut()
+    }
+
+    ///获取表的可变引用
+    pub fn get_table_mut(&mut self, path: &str) -> Option<&mut toml::map::Map<String, toml::Value>> {
+        self.get_mut(path)?.as_table_mut()
+    }
+
+    ///设置指定路径的值（支持点分隔路径）
+    ///
+    ///中间路径不存在时自动创建表；中间路径已存在但不是表（如字符串）时会被替换为新表，
+    ///需要报错而不是覆盖时使用 set_strict()
+    ///
+    ///数组节点下的数字段作为索引：索引小于长度时替换该元素，等于长度时追加，大于长度时返回错误
+    ///
+    ///# 注意
+    ///TOML 没有空值，值为 None 时序列化失败
+    pub fn set<T: serde::Serialize>(&mut self, path: &str, value: T) -> Result<(), String> {
+        let toml_value = toml::Value::try_from(value).map_err(|e| format!("序列化失败: {}", e))?;
+        let keys: Vec<&str> = path.split('.').collect();
+        self.set_nested(&keys, toml_value, false)
+    }
+
+    ///设置指定路径的值（严格模式）
+    ///
+    ///中间路径已存在但不是表时返回错误，不会覆盖原有值
+    pub fn set_strict<T: serde::Serialize>(&mut self, path: &str, value: T) -> Result<(), String> {
+        let toml_value = toml::Value::try_from(value).map_err(|e| format!("序列化失败: {}", e))?;
+        let keys: Vec<&str> = path.split('.').collect();
+        self.set_nested(&keys, toml_value, true)
+    }
+
+    ///设置嵌套值
+    fn set_nested(&mut self, keys: &[&str], value: toml::Value, strict: bool) -> Result<(), String> {
+        if keys.is_empty() {
+            return Err("路径不能为空".to_string());
+        }
+
+        let mut current = &mut self.data;
+        for (i, key) in keys.iter().enumerate() {
+            let last = i == keys.len() - 1;
+
+            //数组节点：数字段作为索引
+            let index = if current.is_array() { key.parse::<usize>().ok() } else { None };
+            if let Some(index) = index {
+                let arr = current.as_array_mut().ok_or("路径无效".to_string())?;
+                if index > arr.len() {
+                    return Err(format!("数组索引越界: \"{}\"（长度 {}）", keys[..=i].join("."), arr.len()));
+                }
+                if last {
+                    if index == arr.len() {
+                        arr.push(value);
+                    } else {
+                        arr[index] = value;
+                    }
+                    return Ok(());
+                }
+                if index == arr.len() {
+                    arr.push(toml::Value::Table(toml::map::Map::new()));
+                }
+                current = &mut arr[index];
+                continue;
+            }
+
+            //当前节点不是表：严格模式报错，否则替换为空表
+            if !current.is_table() {
+                if strict {
+                    return Err(match i {
+                        0 => "根节点不是表".to_string(),
+                        _ => format!("路径 \"{}\" 不是表", keys[..i].join(".")),
+                    });
+                }
+                *current = toml::Value::Table(toml::map::Map::new());
+            }
+            let table = current.as_table_mut().ok_or("路径无效".to_string())?;
+
+            if last {
+                table.insert(key.to_string(), value);
+                return Ok(());
+            }
+            current = table.entry(key.to_string())
+                .or_insert_with(|| toml::Value::Table(toml::map::Map::new()));
+        }
+        Ok(())
+    }
+
+    ///删除指定路径的值（数组元素删除后，后续元素前移）
+    pub fn remove(&mut self, path: &str) -> Option<toml::Value> {
+        let keys: Vec<&str> = path.split('.').collect();
+        if keys.is_empty() {
+            return None;
+        }
+
+        let mut current = &mut self.data;
+        for (i, key) in keys.iter().enumerate() {
+            if i == keys.len() - 1 {
+                if let Some(arr) = current.as_array_mut() {
+                    let index = key.parse::<usize>().ok().filter(|&index| index < arr.len())?;
+                    return Some(arr.remove(index));
+                }
+                return current.as_table_mut()?.remove(*key);
+            }
+            current = child_mut(current, key)?;
+        }
+        None
+    }
+
     //========================================
     //文件操作
     //========================================
 
-    ///保存到文件
+    ///保存到文件（美化格式，数组逐行展开）
     pub fn save(&self, path: &str) -> std::io::Result<()> {
-        let content = toml::to_string_pretty(&self.data)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
-        std::fs::write(path, content)
+        save(path, &self.data)
+    }
+
+    ///保存到文件，同 save()（TOML 始终以美化格式保存，此方法与 JsonConfig 保持一致）
+    pub fn save_pretty(&self, path: &str) -> std::io::Result<()> {
+        self.save(path)
     }
 }
 
@@ -145,11 +317,16 @@ pub fn load_as<T: serde::de::DeserializeOwned>(path: &str) -> std::io::Result<T>
         .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
 }
 
-///保存数据到 TOML 文件
+///保存数据到 TOML 文件（美化格式，数组逐行展开）
 pub fn save<T: serde::Serialize>(path: &str, data: &T) -> std::io::Result<()> {
     let content = toml::to_string_pretty(data)
         .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
-    std::fs::write(path, content)
+    write_atomic(path, content.as_bytes())
+}
+
+///保存数据到 TOML 文件，同 save()（与 json_config 保持一致）
+pub fn save_pretty<T: serde::Serialize>(path: &str, data: &T) -> std::io::Result<()> {
+    save(path, data)
 }
 
 ///从字符串解析 TOML 配置
@@ -162,3 +339,172 @@ pub fn from_str(toml_str: &str) -> Result<TomlConfig, toml::de::Error> {
 pub fn new() -> TomlConfig {
     TomlConfig::empty()
 }
+
+//========================================
+//辅助函数
+//========================================
+
+///获取子节点（数组节点按数字索引，其余按键名）
+fn child<'a>(value: &'a toml::Value, key: &str) -> Option<&'a toml::Value> {
+    match value {
+        toml::Value::Array(arr) => arr.get(key.parse::<usize>().ok()?),
+        _ => value.get(key),
+    }
+}
+
+///获取子节点的可变引用（规则同 child）
+fn child_mut<'a>(value: &'a mut toml::Value, key: &str) -> Option<&'a mut toml::Value> {
+    match value {
+        toml::Value::Array(arr) => arr.get_mut(key.parse::<usize>().ok()?),
+        _ => value.get_mut(key),
+    }
+}
+
+///原子写入文件：先写入同目录临时文件再重命名覆盖目标，写入中途崩溃不会留下空文件或半截文件
+///
+///目标已存在时沿用其权限；重命名失败时删除临时文件并返回错误，原文件保持不变
+fn write_atomic(path: &str, content: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    ///临时文件序号，与进程号一起保证同一文件并发保存时临时文件名不冲突
+    static TMP_SEQ: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+    //目标为符号链接时替换其指向的文件，保留链接本身
+    let target = std::fs::canonicalize(path).unwrap_or_else(|_| std::path::PathBuf::from(path));
+    let dir = match target.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+        _ => std::path::PathBuf::from("."),
+    };
+    let file_name = target.file_name()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "无效的文件路径"))?;
+    let seq = TMP_SEQ.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let tmp = dir.join(format!(".{}.{}.{}.tmp", file_name.to_string_lossy(), std::process::id(), seq));
+
+    //写入并刷盘，确保重命名后的文件内容完整
+    fn write_synced(tmp: &std::path::Path, content: &[u8], target: &std::path::Path) -> std::io::Result<()> {
+        let mut file = std::fs::OpenOptions::new().write(true).create_new(true).open(tmp)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+        if let Ok(meta) = std::fs::metadata(target) {
+            std::fs::set_permissions(tmp, meta.permissions())?;
+        }
+        Ok(())
+    }
+
+    if let Err(e) = write_synced(&tmp, content, &target) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e);
+    }
+
+    if let Err(e) = std::fs::rename(&tmp, &target) {
+        let _ = std::fs::remove_file(&tmp);
+        return Err(e);
+    }
+    Ok(())
+}
+
+//========================================
+//测试
+//========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    ///创建空的临时测试目录
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("toml_config_{}_{}", std::process::id(), name));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    ///目录中除 keep 以外的文件名（用于检查临时文件是否残留）
+    fn leftover_files(dir: &std::path::Path, keep: &str) -> Vec<String> {
+        std::fs::read_dir(dir).unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name != keep)
+            .collect()
+    }
+
+    #[test]
+    fn failed_serialization_leaves_original_file() {
+        let dir = temp_dir("bad_value");
+        let path = dir.join("config.toml");
+        std::fs::write(&path, "keep = 1\n").unwrap();
+
+        //TOML 顶层必须是表，整数无法序列化
+        assert!(save(path.to_str().unwrap(), &5).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "keep = 1\n");
+        assert!(leftover_files(&dir, "config.toml").is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn failed_rename_returns_error_and_cleans_up() {
+        let dir = temp_dir("rename_fail");
+        //目标是非空目录，重命名必然失败
+        let target = dir.join("config.toml");
+        std::fs::create_dir(&target).unwrap();
+        std::fs::write(target.join("inner"), "x").unwrap();
+
+        let data = toml::Value::Table(toml::map::Map::new());
+        assert!(save(target.to_str().unwrap(), &data).is_err());
+        assert!(target.join("inner").exists());
+        assert!(leftover_files(&dir, "config.toml").is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn set_save_reload_round_trip() {
+        let dir = temp_dir("round_trip");
+        let path = dir.join("config.toml");
+        std::fs::write(&path, "name = \"app\"\n\n[server]\nport = 80\n").unwrap();
+        let path = path.to_str().unwrap();
+
+        let mut config = load(path).unwrap();
+        config.set("server.port", 9000).unwrap();
+        config.set("server.tls.enabled", true).unwrap();
+        config.set("server.hosts", vec!["10.0.0.1"]).unwrap();
+        config.set("server.hosts.1", "10.0.0.2").unwrap();
+        assert!(config.remove("name").is_some());
+        config.save(path).unwrap();
+
+        let reloaded = load(path).unwrap();
+        assert_eq!(reloaded.get_i64("server.port"), Some(9000));
+        assert_eq!(reloaded.get_bool("server.tls.enabled"), Some(true));
+        let hosts: Vec<_> = reloaded.get_array("server.hosts").unwrap().iter().filter_map(|v| v.as_str()).collect();
+        assert_eq!(hosts, ["10.0.0.1", "10.0.0.2"]);
+        assert!(reloaded.get("name").is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn save_preserves_untouched_keys_and_stays_pretty() {
+        let dir = temp_dir("preserve");
+        let path = dir.join("config.toml");
+        std::fs::write(&path, "title = \"demo\"\nports = [1, 2, 3]\n\n[db]\nurl = \"sqlite://a.db\"\n").unwrap();
+        let path = path.to_str().unwrap();
+
+        let mut config = load(path).unwrap();
+        config.set("db.pool", 4).unwrap();
+        config.save(path).unwrap();
+
+        let content = std::fs::read_to_string(path).unwrap();
+        //美化格式下数组逐行展开
+        assert!(content.contains("ports = [\n"), "{}", content);
+        let reloaded = load(path).unwrap();
+        assert_eq!(reloaded.get_str("title"), Some("demo"));
+        assert_eq!(reloaded.get_array("ports").map(|a| a.len()), Some(3));
+        assert_eq!(reloaded.get_str("db.url"), Some("sqlite://a.db"));
+        assert_eq!(reloaded.get_i64("db.pool"), Some(4));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn set_rejects_invalid_paths() {
+        let mut config = from_str("list = [1]\nscalar = 1\n").unwrap();
+        assert!(config.set("list.5", 2).is_err());
+        assert!(config.remove("missing.key").is_none());
+    }
+}
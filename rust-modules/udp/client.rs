@@ -3,6 +3,8 @@
 //!提供 UDP 客户端功能：单播发送、广播发送、数据接收。
 
 use super::config;
+use super::reliable;
+use super::fragment;
 
 //========================================
 //UDP 客户端结构
@@ -12,6 +14,12 @@ use super::config;
 pub struct UdpClient {
     ///底层 UDP socket
     socket: std::net::UdpSocket,
+    ///单次接收的缓冲区大小（字节）
+    recv_buffer_size: usize,
+    ///可靠发送的下一个序号（分片发送的消息编号也从这里分配）
+    next_seq: std::sync::atomic::AtomicU32,
+    ///分片重组缓存（recv_large 使用）
+    reassembler: std::sync::Mutex<fragment::Reassembler>,
 }
 
 impl UdpClient {
@@ -19,24 +27,42 @@ impl UdpClient {
     //客户端创建方法
     //========================================
 
+    ///包装已绑定的 socket
+    fn from_socket(socket: std::net::UdpSocket) -> Self {
+        Self {
+            socket,
+            recv_buffer_size: config::RECV_BUFFER_SIZE,
+            next_seq: std::sync::atomic::AtomicU32::new(super::time_seed()),
+            reassembler: std::sync::Mutex::new(fragment::Reassembler::new(
+                std::time::Duration::from_millis(config::FRAGMENT_TIMEOUT_MS),
+            )),
+        }
+    }
+
     ///创建客户端（绑定系统自动分配的端口）
     pub fn new() -> std::io::Result<Self> {
         let socket = std::net::UdpSocket::bind(config::CLIENT_BIND_ADDR)?;
-        Ok(Self { socket })
+        Ok(Self::from_socket(socket))
     }
 
     ///创建客户端并绑定指定端口
     pub fn bind(port: u16) -> std::io::Result<Self> {
         let addr = format!("0.0.0.0:{}", port);
         let socket = std::net::UdpSocket::bind(&addr)?;
-        Ok(Self { socket })
+        Ok(Self::from_socket(socket))
+    }
+
+    ///创建 IPv6 客户端（向 IPv6 地址发送时使用）
+    pub fn new_v6() -> std::io::Result<Self> {
+        let socket = std::net::UdpSocket::bind(config::CLIENT_BIND_ADDR_V6)?;
+        Ok(Self::from_socket(socket))
     }
 
     ///创建支持广播的客户端
     pub fn new_broadcast() -> std::io::Result<Self> {
         let socket = std::net::UdpSocket::bind(config::CLIENT_BIND_ADDR)?;
         socket.set_broadcast(true)?;
-        Ok(Self { socket })
+        Ok(Self::from_socket(socket))
     }
 
     //========================================
@@ -45,7 +71,7 @@ impl UdpClient {
 
     ///向指定地址发送数据
     pub fn send_to(&self, addr: &str, port: u16, data: &[u8]) -> std::io::Result<usize> {
-        let target = format!("{}:{}", addr, port);
+        let target = super::format_addr(addr, port);
         self.socket.send_to(data, &target)
     }
 
@@ -64,6 +90,96 @@ impl UdpClient {
         self.send(content.as_bytes())
     }
 
+    //========================================
+    //可靠发送（确认 + 重传）
+    //========================================
+
+    ///可靠发送：附加序号，等待对端确认，超时则重传
+    ///
+    ///对端需使用 UdpServer::run_reliable() 接收。
+    ///共发送最多 retries + 1 次，全部超时返回 ErrorKind::TimedOut。
+    ///
+    ///# 注意
+    ///等待确认期间收到的其他数据报会被丢弃；
+    ///确认包丢失时对端可能已收到数据，属于"至少一次"语义
+    pub fn send_reliable(
+        &self,
+        addr: &str,
+        port: u16,
+        data: &[u8],
+        retries: u32,
+        timeout: std::time::Duration,
+    ) -> std::io::Result<()> {
+        use std::net::ToSocketAddrs;
+
+        let target = super::format_addr(addr, port)
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "无法解析目标地址"))?;
+        let seq = self.next_seq.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let packet = reliable::encode_data(seq, data);
+
+        let old_timeout = self.socket.read_timeout()?;
+        let mut result = Err(std::io::Error::new(std::io::ErrorKind::TimedOut, "等待确认超时"));
+        for _ in 0..=retries {
+            self.socket.send_to(&packet, target)?;
+            match self.wait_ack(target, seq, timeout) {
+                Ok(true) => {
+                    result = Ok(());
+                    break;
+                }
+                Ok(false) => continue,
+                Err(e) => {
+                    result = Err(e);
+                    break;
+                }
+            }
+        }
+        self.socket.set_read_timeout(old_timeout)?;
+        result
+    }
+
+    ///在 timeout 内等待来自 target 的指定序号确认包
+    fn wait_ack(&self, target: std::net::SocketAddr, seq: u32, timeout: std::time::Duration) -> std::io::Result<bool> {
+        let deadline = std::time::Instant::now() + timeout;
+        let mut buf = [0u8; 64];
+        loop {
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            if remaining.is_zero() {
+                return Ok(false);
+            }
+            self.socket.set_read_timeout(Some(remaining))?;
+            match self.socket.recv_from(&mut buf) {
+                Ok((size, from)) => {
+                    if from == target && reliable::decode(&buf[..size]) == Some(reliable::Packet::Ack { seq }) {
+                        return Ok(true);
+                    }
+                }
+                Err(e) if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => {
+                    return Ok(false);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    //========================================
+    //分片发送方法
+    //========================================
+
+    ///分片发送大数据（可超过单个数据报上限），对端使用 recv_large() 接收
+    ///
+    ///每个分片数据部分为 config::FRAGMENT_PAYLOAD_SIZE 字节。
+    ///尽力而为：不确认、不重传，任一分片丢失则对端丢弃整条消息
+    pub fn send_large(&self, addr: &str, port: u16, data: &[u8]) -> std::io::Result<()> {
+        let target = super::format_addr(addr, port);
+        let message_id = self.next_seq.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        for packet in fragment::split(message_id, data, config::FRAGMENT_PAYLOAD_SIZE)? {
+            self.socket.send_to(&packet, &target)?;
+        }
+        Ok(())
+    }
+
     //========================================
     //广播发送方法
     //========================================
@@ -79,13 +195,42 @@ impl UdpClient {
         self.broadcast(port, content.as_bytes())
     }
 
+    //========================================
+    //组播方法
+    //========================================
+
+    ///加入组播组（之后可接收发往该组的数据报）
+    ///
+    ///参数：
+    ///- group: 组播地址（224.0.0.0 ~ 239.255.255.255，局域网内建议 239.x.x.x）
+    ///- interface: 本地网卡地址，Ipv4Addr::UNSPECIFIED 表示由系统选择
+    pub fn join_multicast(&self, group: std::net::Ipv4Addr, interface: std::net::Ipv4Addr) -> std::io::Result<()> {
+        self.socket.join_multicast_v4(&group, &interface)
+    }
+
+    ///离开组播组
+    pub fn leave_multicast(&self, group: std::net::Ipv4Addr, interface: std::net::Ipv4Addr) -> std::io::Result<()> {
+        self.socket.leave_multicast_v4(&group, &interface)
+    }
+
+    ///向组播组发送数据（TTL 为 config::MULTICAST_TTL，不需要先加入组）
+    pub fn send_multicast(&self, group: std::net::Ipv4Addr, port: u16, data: &[u8]) -> std::io::Result<usize> {
+        self.socket.set_multicast_ttl_v4(config::MULTICAST_TTL)?;
+        self.socket.send_to(data, (group, port))
+    }
+
+    ///设置是否接收本机发出的组播数据报（默认接收）
+    pub fn set_multicast_loop(&self, enable: bool) -> std::io::Result<()> {
+        self.socket.set_multicast_loop_v4(enable)
+    }
+
     //========================================
     //数据接收方法
     //========================================
 
     ///接收数据报，返回数据和发送方地址
     pub fn recv(&self) -> std::io::Result<(Vec<u8>, std::net::SocketAddr)> {
-        let mut buf = vec![0u8; config::RECV_BUFFER_SIZE];
+        let mut buf = vec![0u8; self.recv_buffer_size];
         let (size, src_addr) = self.socket.recv_from(&mut buf)?;
         buf.truncate(size);
         Ok((buf, src_addr))
@@ -98,13 +243,29 @@ impl UdpClient {
         Ok((s, addr))
     }
 
+    ///接收分片发送的大数据，所有分片到齐后返回重组的数据和发送方地址
+    ///
+    ///分片可乱序到达；非分片格式的数据报会被忽略；
+    ///超过 config::FRAGMENT_TIMEOUT_MS 仍未收齐的消息被丢弃
+    pub fn recv_large(&self) -> std::io::Result<(Vec<u8>, std::net::SocketAddr)> {
+        loop {
+            let (data, addr) = self.recv()?;
+            if let Some(frag) = fragment::decode(&data) {
+                let mut reassembler = self.reassembler.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some(message) = reassembler.insert(addr, frag) {
+                    return Ok((message, addr));
+                }
+            }
+        }
+    }
+
     //========================================
     //连接模式（伪连接）
     //========================================
 
     ///连接到指定地址（设置默认目标，之后可用 send_connected）
     pub fn connect(&self, addr: &str, port: u16) -> std::io::Result<()> {
-        let target = format!("{}:{}", addr, port);
+        let target = super::format_addr(addr, port);
         self.socket.connect(&target)
     }
 
@@ -137,6 +298,20 @@ impl UdpClient {
         self.socket.local_addr()
     }
 
+    ///设置单次接收的缓冲区大小（字节），超出部分的数据报内容会被截断
+    ///
+    ///默认为 config::RECV_BUFFER_SIZE
+    ///注：此处仅影响 recv()/recv_string() 使用的用户态缓冲区，
+    ///如需调整系统 socket 接收缓冲区（SO_RCVBUF），可借助 socket2 库
+    pub fn set_recv_buffer_size(&mut self, size: usize) {
+        self.recv_buffer_size = size;
+    }
+
+    ///获取单次接收的缓冲区大小（字节）
+    pub fn recv_buffer_size(&self) -> usize {
+        self.recv_buffer_size
+    }
+
     ///设置是否启用广播
     pub fn set_broadcast(&self, enable: bool) -> std::io::Result<()> {
         self.socket.set_broadcast(enable)
@@ -148,3 +323,39 @@ impl Default for UdpClient {
         Self::new().expect("创建 UDP 客户端失败")
     }
 }
+
+//========================================
+//测试
+//========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multicast_join_send_recv_loopback() {
+        let group = std::net::Ipv4Addr::new(239, 255, 77, 1);
+        let receiver = UdpClient::bind(0).unwrap();
+        let port = receiver.local_addr().unwrap().port();
+        //没有组播路由的环境（如部分容器/CI）无法加入或发送，跳过测试
+        if let Err(e) = receiver.join_multicast(group, std::net::Ipv4Addr::UNSPECIFIED) {
+            eprintln!("跳过组播测试：无法加入组播组: {}", e);
+            return;
+        }
+
+        let sender = UdpClient::new().unwrap();
+        sender.set_multicast_loop(true).unwrap();
+        if let Err(e) = sender.send_multicast(group, port, b"discover") {
+            eprintln!("跳过组播测试：无法发送组播: {}", e);
+            return;
+        }
+
+        receiver
+            .socket()
+            .set_read_timeout(Some(std::time::Duration::from_secs(2)))
+            .unwrap();
+        let (data, _) = receiver.recv().unwrap();
+        assert_eq!(data, b"discover");
+        receiver.leave_multicast(group, std::net::Ipv4Addr::UNSPECIFIED).unwrap();
+    }
+}
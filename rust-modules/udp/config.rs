@@ -13,6 +13,9 @@ pub const SERVER_DEFAULT_PORT: u16 = 8081;
 ///服务端默认绑定地址
 pub const SERVER_DEFAULT_ADDR: &str = "0.0.0.0";
 
+///服务端默认 IPv6 绑定地址（Linux 下通常同时接收 IPv4 数据报）
+pub const SERVER_DEFAULT_ADDR_V6: &str = "::";
+
 //========================================
 //客户端配置
 //========================================
@@ -26,6 +29,9 @@ pub const CLIENT_DEFAULT_ADDR: &str = "127.0.0.1";
 ///客户端默认本地绑定地址（0表示系统自动分配）
 pub const CLIENT_BIND_ADDR: &str = "0.0.0.0:0";
 
+///客户端默认 IPv6 本地绑定地址
+pub const CLIENT_BIND_ADDR_V6: &str = "[::]:0";
+
 //========================================
 //广播配置
 //========================================
@@ -33,6 +39,9 @@ pub const CLIENT_BIND_ADDR: &str = "0.0.0.0:0";
 ///广播地址
 pub const BROADCAST_ADDR: &str = "255.255.255.255";
 
+///组播默认 TTL（1 表示只在本网段内传播）
+pub const MULTICAST_TTL: u32 = 1;
+
 //========================================
 //缓冲区配置
 //========================================
@@ -43,3 +52,29 @@ pub const RECV_BUFFER_SIZE: usize = 65535;
 
 ///发送缓冲区大小（字节）
 pub const SEND_BUFFER_SIZE: usize = 65535;
+
+//========================================
+//可靠传输配置
+//========================================
+
+///可靠接收模式的去重缓存容量（记录最近收到的序号条数）
+pub const RELIABLE_DEDUP_CAPACITY: usize = 4096;
+
+//========================================
+//分片传输配置
+//========================================
+
+///分片数据部分的大小（字节），加上分片头后不超过常见 MTU
+pub const FRAGMENT_PAYLOAD_SIZE: usize = 1400;
+
+///未收齐分片的消息最长保留时间（毫秒），超时整条丢弃
+pub const FRAGMENT_TIMEOUT_MS: u64 = 5000;
+
+///每个发送方最多同时缓存的未收齐消息数，超出时丢弃该发送方最早的一条
+pub const FRAGMENT_MAX_PENDING_PER_PEER: usize = 16;
+
+///所有发送方合计最多缓存的未收齐消息数，超出时丢弃最早的一条
+pub const FRAGMENT_MAX_PENDING: usize = 256;
+
+///未收齐消息合计最多缓存的分片数据字节数，超出时从最早的消息开始丢弃
+pub const FRAGMENT_MAX_BUFFERED_BYTES: usize = 16 * 1024 * 1024;
@@ -0,0 +1,350 @@
+//!UDP 分片重组模块
+//!
+//!将超过单个数据报大小的数据拆分为带编号的分片发送，接收端按消息编号缓存分片，
+//!全部到齐后重组为完整数据。
+//!
+//!尽力而为：不确认、不重传，任一分片丢失则整条消息在超时后被丢弃。
+//!接收端按发送方和总量限制缓存的消息数与字节数（见 config::FRAGMENT_MAX_*），
+//!超出时丢弃最早的未收齐消息，避免伪造的分片头耗尽内存。
+//!
+//!分片格式：[类型:1字节][消息编号:4字节][分片序号:2字节][分片总数:2字节][数据:N字节]
+
+use super::config;
+
+//========================================
+//分片定义
+//========================================
+
+///分片头大小（字节）
+pub const HEADER_SIZE: usize = 9;
+
+///分片类型标识
+pub const FRAGMENT_TYPE: u8 = 0xF1;
+
+///解析后的分片
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fragment<'a> {
+    ///消息编号（同一条消息的所有分片相同）
+    pub message_id: u32,
+    ///分片序号（从 0 开始）
+    pub frag_index: u16,
+    ///分片总数
+    pub frag_count: u16,
+    ///分片数据
+    pub payload: &'a [u8],
+}
+
+///将数据拆分为分片，每个分片数据部分不超过 payload_size 字节
+///
+///分片数超过 u16::MAX 时返回 InvalidInput 错误
+pub fn split(message_id: u32, data: &[u8], payload_size: usize) -> std::io::Result<Vec<Vec<u8>>> {
+    let payload_size = payload_size.max(1);
+    let frag_count = data.len().div_ceil(payload_size).max(1);
+    if frag_count > u16::MAX as usize {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("数据过大: {} 字节需要 {} 个分片，最多 {}", data.len(), frag_count, u16::MAX),
+        ));
+    }
+
+    let chunks: Vec<&[u8]> = if data.is_empty() {
+        vec![data]
+    } else {
+        data.chunks(payload_size).collect()
+    };
+
+    Ok(chunks
+        .into_iter()
+        .enumerate()
+        .map(|(index, chunk)| {
+            let mut bytes = Vec::with_capacity(HEADER_SIZE + chunk.len());
+            bytes.push(FRAGMENT_TYPE);
+            bytes.extend_from_slice(&message_id.to_be_bytes());
+            bytes.extend_from_slice(&(index as u16).to_be_bytes());
+            bytes.extend_from_slice(&(frag_count as u16).to_be_bytes());
+            bytes.extend_from_slice(chunk);
+            bytes
+        })
+        .collect())
+}
+
+///解析分片，不是分片格式的数据报返回 None
+pub fn decode(bytes: &[u8]) -> Option<Fragment<'_>> {
+    if bytes.len() < HEADER_SIZE || bytes[0] != FRAGMENT_TYPE {
+        return None;
+    }
+    let message_id = u32::from_be_bytes(bytes[1..5].try_into().ok()?);
+    let frag_index = u16::from_be_bytes(bytes[5..7].try_into().ok()?);
+    let frag_count = u16::from_be_bytes(bytes[7..9].try_into().ok()?);
+    if frag_count == 0 || frag_index >= frag_count {
+        return None;
+    }
+    Some(Fragment { message_id, frag_index, frag_count, payload: &bytes[HEADER_SIZE..] })
+}
+
+//========================================
+//接收端重组
+//========================================
+
+///未收齐的消息
+struct Partial {
+    ///已收到的分片（按序号稀疏存放，只占用已到达分片的内存）
+    frags: std::collections::BTreeMap<u16, Vec<u8>>,
+    ///分片总数
+    frag_count: u16,
+    ///已缓存的分片数据字节数
+    bytes: usize,
+    ///收到第一个分片的时间
+    first_seen: std::time::Instant,
+}
+
+///分片重组缓存
+pub struct Reassembler {
+    ///未收齐的消息，按 (发送方地址, 消息编号) 区分
+    pending: std::collections::HashMap<(std::net::SocketAddr, u32), Partial>,
+    ///未收齐消息的最长保留时间
+    timeout: std::time::Duration,
+    ///每个发送方最多缓存的未收齐消息数
+    max_per_peer: usize,
+    ///合计最多缓存的未收齐消息数
+    max_pending: usize,
+    ///合计最多缓存的分片数据字节数
+    max_bytes: usize,
+    ///当前缓存的分片数据字节数
+    buffered_bytes: usize,
+}
+
+impl Reassembler {
+    ///创建重组缓存（缓存上限使用 config::FRAGMENT_MAX_* 默认值）
+    pub fn new(timeout: std::time::Duration) -> Self {
+        Self::with_limits(
+            timeout,
+            config::FRAGMENT_MAX_PENDING_PER_PEER,
+            config::FRAGMENT_MAX_PENDING,
+            config::FRAGMENT_MAX_BUFFERED_BYTES,
+        )
+    }
+
+    ///创建重组缓存并指定缓存上限
+    ///
+    ///参数：
+    ///- max_per_peer: 每个发送方最多缓存的未收齐消息数
+    ///- max_pending: 合计最多缓存的未收齐消息数
+    ///- max_bytes: 合计最多缓存的分片数据字节数
+    pub fn with_limits(timeout: std::time::Duration, max_per_peer: usize, max_pending: usize, max_bytes: usize) -> Self {
+        Self {
+            pending: std::collections::HashMap::new(),
+            timeout,
+            max_per_peer: max_per_peer.max(1),
+            max_pending: max_pending.max(1),
+            max_bytes,
+            buffered_bytes: 0,
+        }
+    }
+
+    ///加入一个分片，消息全部到齐时返回重组后的数据
+    ///
+    ///分片可以乱序到达，重复的分片会被忽略；
+    ///超出缓存上限时丢弃最早的未收齐消息（可能是本条消息本身）
+    pub fn insert(&mut self, addr: std::net::SocketAddr, fragment: Fragment<'_>) -> Option<Vec<u8>> {
+        self.purge_expired();
+
+        let key = (addr, fragment.message_id);
+        if !self.pending.contains_key(&key) {
+            self.make_room_for(addr);
+            self.pending.insert(key, Partial {
+                frags: std::collections::BTreeMap::new(),
+                frag_count: fragment.frag_count,
+                bytes: 0,
+                first_seen: std::time::Instant::now(),
+            });
+        }
+
+        let partial = self.pending.get_mut(&key)?;
+        //分片总数与之前不一致，视为无效分片
+        if partial.frag_count != fragment.frag_count {
+            return None;
+        }
+        if let std::collections::btree_map::Entry::Vacant(slot) = partial.frags.entry(fragment.frag_index) {
+            slot.insert(fragment.payload.to_vec());
+            partial.bytes += fragment.payload.len();
+            self.buffered_bytes += fragment.payload.len();
+        }
+
+        if partial.frags.len() < partial.frag_count as usize {
+            self.enforce_byte_limit();
+            return None;
+        }
+        let partial = self.remove(&key)?;
+        Some(partial.frags.into_values().flatten().collect())
+    }
+
+    ///丢弃超时仍未收齐的消息，返回丢弃的条数
+    pub fn purge_expired(&mut self) -> usize {
+        let timeout = self.timeout;
+        let expired: Vec<_> = self
+            .pending
+            .iter()
+            .filter(|(_, partial)| partial.first_seen.elapsed() >= timeout)
+            .map(|(key, _)| *key)
+            .collect();
+        for key in &expired {
+            self.remove(key);
+        }
+        expired.len()
+    }
+
+    ///当前未收齐的消息数
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    ///当前缓存的分片数据字节数
+    pub fn buffered_bytes(&self) -> usize {
+        self.buffered_bytes
+    }
+
+    ///为 addr 的新消息腾出位置：超出每个发送方或总数上限时丢弃最早的消息
+    fn make_room_for(&mut self, addr: std::net::SocketAddr) {
+        while self.pending.keys().filter(|(a, _)| *a == addr).count() >= self.max_per_peer {
+            match self.oldest(|(a, _)| *a == addr) {
+                Some(key) => self.remove(&key),
+                None => break,
+            };
+        }
+        while self.pending.len() >= self.max_pending {
+            match self.oldest(|_| true) {
+                Some(key) => self.remove(&key),
+                None => break,
+            };
+        }
+    }
+
+    ///缓存字节数超出上限时从最早的消息开始丢弃
+    fn enforce_byte_limit(&mut self) {
+        while self.buffered_bytes > self.max_bytes {
+            match self.oldest(|_| true) {
+                Some(key) => self.remove(&key),
+                None => break,
+            };
+        }
+    }
+
+    ///满足条件的最早一条未收齐消息
+    fn oldest<F>(&self, filter: F) -> Option<(std::net::SocketAddr, u32)>
+    where
+        F: Fn(&(std::net::SocketAddr, u32)) -> bool,
+    {
+        self.pending
+            .iter()
+            .filter(|(key, _)| filter(key))
+            .min_by_key(|(_, partial)| partial.first_seen)
+            .map(|(key, _)| *key)
+    }
+
+    ///移除一条消息并更新字节计数
+    fn remove(&mut self, key: &(std::net::SocketAddr, u32)) -> Option<Partial> {
+        let partial = self.pending.remove(key)?;
+        self.buffered_bytes -= partial.bytes;
+        Some(partial)
+    }
+}
+
+//========================================
+//测试
+//========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer(port: u16) -> std::net::SocketAddr {
+        std::net::SocketAddr::from(([127, 0, 0, 1], port))
+    }
+
+    fn data(len: usize) -> Vec<u8> {
+        (0..len).map(|i| i as u8).collect()
+    }
+
+    #[test]
+    fn reassembles_out_of_order_and_duplicate_fragments() {
+        let payload = data(1000);
+        let packets = split(7, &payload, 100).unwrap();
+        assert_eq!(packets.len(), 10);
+
+        let mut reassembler = Reassembler::new(std::time::Duration::from_secs(5));
+        let mut order: Vec<usize> = (0..packets.len()).rev().collect();
+        order.insert(3, 9);
+        let mut result = None;
+        for i in order {
+            assert!(result.is_none());
+            result = reassembler.insert(peer(1), decode(&packets[i]).unwrap());
+        }
+        assert_eq!(result, Some(payload));
+        assert_eq!(reassembler.pending_count(), 0);
+        assert_eq!(reassembler.buffered_bytes(), 0);
+    }
+
+    #[test]
+    fn lost_fragment_never_completes_and_expires() {
+        let packets = split(1, &data(500), 100).unwrap();
+        let mut reassembler = Reassembler::new(std::time::Duration::from_millis(30));
+        for packet in packets.iter().skip(1) {
+            assert_eq!(reassembler.insert(peer(1), decode(packet).unwrap()), None);
+        }
+        assert_eq!(reassembler.pending_count(), 1);
+        assert_eq!(reassembler.buffered_bytes(), 400);
+
+        std::thread::sleep(std::time::Duration::from_millis(40));
+        assert_eq!(reassembler.purge_expired(), 1);
+        assert_eq!(reassembler.pending_count(), 0);
+        assert_eq!(reassembler.buffered_bytes(), 0);
+
+        //超时后迟到的分片开始一条新消息，不会拼出残缺数据
+        assert_eq!(reassembler.insert(peer(1), decode(&packets[0]).unwrap()), None);
+        assert_eq!(reassembler.pending_count(), 1);
+    }
+
+    #[test]
+    fn huge_fragment_count_does_not_preallocate() {
+        let mut reassembler = Reassembler::new(std::time::Duration::from_secs(5));
+        let fragment = Fragment { message_id: 1, frag_index: 0, frag_count: u16::MAX, payload: b"x" };
+        assert_eq!(reassembler.insert(peer(1), fragment), None);
+        assert_eq!(reassembler.buffered_bytes(), 1);
+    }
+
+    #[test]
+    fn pending_messages_are_capped_per_peer_and_in_total() {
+        let timeout = std::time::Duration::from_secs(5);
+        let mut reassembler = Reassembler::with_limits(timeout, 2, 3, usize::MAX);
+        let first = |id| Fragment { message_id: id, frag_index: 0, frag_count: 2, payload: b"a" };
+
+        for id in 0..5 {
+            reassembler.insert(peer(1), first(id));
+        }
+        assert_eq!(reassembler.pending_count(), 2);
+
+        reassembler.insert(peer(2), first(0));
+        reassembler.insert(peer(3), first(0));
+        assert_eq!(reassembler.pending_count(), 3);
+
+        //peer(1) 最早的消息 3 已被淘汰，最新的消息 4 仍可完成
+        let second = |id| Fragment { message_id: id, frag_index: 1, frag_count: 2, payload: b"b" };
+        assert_eq!(reassembler.insert(peer(1), second(4)), Some(b"ab".to_vec()));
+    }
+
+    #[test]
+    fn buffered_bytes_are_capped() {
+        let timeout = std::time::Duration::from_secs(5);
+        let mut reassembler = Reassembler::with_limits(timeout, 16, 16, 250);
+        let packets = split(1, &data(400), 100).unwrap();
+        reassembler.insert(peer(1), decode(&packets[0]).unwrap());
+        reassembler.insert(peer(1), decode(&packets[1]).unwrap());
+        assert_eq!(reassembler.buffered_bytes(), 200);
+
+        //第三个分片使缓存超过上限，最早（也是唯一）的消息被丢弃
+        assert_eq!(reassembler.insert(peer(1), decode(&packets[2]).unwrap()), None);
+        assert_eq!(reassembler.pending_count(), 0);
+        assert_eq!(reassembler.buffered_bytes(), 0);
+    }
+}
@@ -8,6 +8,8 @@
 //!- `config` - 配置项（端口、缓冲区大小等）
 //!- `client` - UDP 客户端（单播、广播发送）
 //!- `server` - UDP 服务端（数据报接收）
+//!- `reliable` - 可靠传输协议（序号、确认、去重）
+//!- `fragment` - 大数据分片与重组（尽力而为，不重传）
 //!
 //!# 快速开始
 //!
@@ -42,6 +44,8 @@
 pub mod config;
 pub mod client;
 pub mod server;
+pub mod reliable;
+pub mod fragment;
 
 //========================================
 //便捷重导出
@@ -49,3 +53,24 @@ pub mod server;
 
 pub use client::UdpClient;
 pub use server::UdpServer;
+
+//========================================
+//地址工具
+//========================================
+
+///拼接 "地址:端口"，IPv6 地址自动加方括号（如 "::1" -> "[::1]:8080"）
+pub fn format_addr(addr: &str, port: u16) -> String {
+    if addr.contains(':') && !addr.starts_with('[') {
+        format!("[{}]:{}", addr, port)
+    } else {
+        format!("{}:{}", addr, port)
+    }
+}
+
+///以当前时间生成序号/消息编号的起点，避免重启后与对端缓存中的旧编号冲突
+fn time_seed() -> u32 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() ^ d.as_secs() as u32)
+        .unwrap_or(0)
+}
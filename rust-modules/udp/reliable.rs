@@ -0,0 +1,101 @@
+//!UDP 可靠传输协议模块
+//!
+//!为数据报附加序号并要求对端确认（ACK），配合超时重传实现"至少一次"送达。
+//!接收端按 (发送方地址, 序号) 去重，重传的数据报不会重复交给业务处理。
+//!
+//!数据包格式：[类型:1字节][序号:4字节][数据:N字节]
+//!确认包格式：[类型:1字节][序号:4字节]
+
+//========================================
+//数据包定义
+//========================================
+
+///包头大小（字节）
+pub const HEADER_SIZE: usize = 5;
+
+///数据包类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PacketType {
+    ///携带业务数据，需要确认
+    Data = 0xD1,
+    ///确认包
+    Ack = 0xA1,
+}
+
+///解析后的数据包
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Packet<'a> {
+    ///数据包
+    Data { seq: u32, payload: &'a [u8] },
+    ///确认包
+    Ack { seq: u32 },
+}
+
+///编码数据包
+pub fn encode_data(seq: u32, payload: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_SIZE + payload.len());
+    bytes.push(PacketType::Data as u8);
+    bytes.extend_from_slice(&seq.to_be_bytes());
+    bytes.extend_from_slice(payload);
+    bytes
+}
+
+///编码确认包
+pub fn encode_ack(seq: u32) -> [u8; HEADER_SIZE] {
+    let mut bytes = [0u8; HEADER_SIZE];
+    bytes[0] = PacketType::Ack as u8;
+    bytes[1..5].copy_from_slice(&seq.to_be_bytes());
+    bytes
+}
+
+///解析数据包，不是可靠协议的数据报返回 None
+pub fn decode(bytes: &[u8]) -> Option<Packet<'_>> {
+    if bytes.len() < HEADER_SIZE {
+        return None;
+    }
+    let seq = u32::from_be_bytes(bytes[1..5].try_into().ok()?);
+    match bytes[0] {
+        b if b == PacketType::Data as u8 => Some(Packet::Data { seq, payload: &bytes[HEADER_SIZE..] }),
+        b if b == PacketType::Ack as u8 && bytes.len() == HEADER_SIZE => Some(Packet::Ack { seq }),
+        _ => None,
+    }
+}
+
+//========================================
+//接收端去重
+//========================================
+
+///已接收序号缓存（按插入顺序淘汰最旧的记录）
+pub struct DedupCache {
+    ///已接收的 (发送方地址, 序号)
+    seen: std::collections::HashSet<(std::net::SocketAddr, u32)>,
+    ///插入顺序，用于淘汰
+    order: std::collections::VecDeque<(std::net::SocketAddr, u32)>,
+    ///最多记录的条数
+    capacity: usize,
+}
+
+impl DedupCache {
+    ///创建缓存
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            seen: std::collections::HashSet::new(),
+            order: std::collections::VecDeque::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    ///记录一次接收，首次出现返回 true，重复返回 false
+    pub fn insert(&mut self, addr: std::net::SocketAddr, seq: u32) -> bool {
+        if !self.seen.insert((addr, seq)) {
+            return false;
+        }
+        self.order.push_back((addr, seq));
+        if self.order.len() > self.capacity {
+            if let Some(old) = self.order.pop_front() {
+                self.seen.remove(&old);
+            }
+        }
+        true
+    }
+}
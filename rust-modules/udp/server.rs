@@ -3,6 +3,8 @@
 //!提供 UDP 服务端功能：端口监听、数据报接收、回复发送。
 
 use super::config;
+use super::reliable;
+use super::fragment;
 
 //========================================
 //UDP 服务端结构
@@ -12,6 +14,12 @@ use super::config;
 pub struct UdpServer {
     ///底层 UDP socket
     socket: std::net::UdpSocket,
+    ///单次接收的缓冲区大小（字节）
+    recv_buffer_size: usize,
+    ///分片发送的下一个消息编号
+    next_message_id: std::sync::atomic::AtomicU32,
+    ///分片重组缓存（recv_large 使用）
+    reassembler: std::sync::Mutex<fragment::Reassembler>,
 }
 
 impl UdpServer {
@@ -19,12 +27,24 @@ impl UdpServer {
     //服务端启动方法
     //========================================
 
+    ///包装已绑定的 socket
+    fn from_socket(socket: std::net::UdpSocket) -> Self {
+        Self {
+            socket,
+            recv_buffer_size: config::RECV_BUFFER_SIZE,
+            next_message_id: std::sync::atomic::AtomicU32::new(super::time_seed()),
+            reassembler: std::sync::Mutex::new(fragment::Reassembler::new(
+                std::time::Duration::from_millis(config::FRAGMENT_TIMEOUT_MS),
+            )),
+        }
+    }
+
     ///绑定端口并启动监听
     pub fn bind(port: u16) -> std::io::Result<Self> {
-        let addr = format!("{}:{}", config::SERVER_DEFAULT_ADDR, port);
+        let addr = super::format_addr(config::SERVER_DEFAULT_ADDR, port);
         let socket = std::net::UdpSocket::bind(&addr)?;
         println!("UDP 服务端已启动，监听 {}", addr);
-        Ok(Self { socket })
+        Ok(Self::from_socket(socket))
     }
 
     ///使用默认配置启动
@@ -32,12 +52,53 @@ impl UdpServer {
         Self::bind(config::SERVER_DEFAULT_PORT)
     }
 
-    ///绑定指定地址和端口
+    ///绑定 IPv6 任意地址（[::]:port）
+    ///
+    ///Linux 默认双栈，同时接收 IPv4 数据报（此时不要再用 bind() 绑定同一端口）；
+    ///Windows 等仅 IPv6 的系统需另外用 bind() 监听 IPv4
+    pub fn bind_v6(port: u16) -> std::io::Result<Self> {
+        Self::bind_addr(config::SERVER_DEFAULT_ADDR_V6, port)
+    }
+
+    ///绑定指定地址和端口（支持 IPv6 地址，如 "::1"）
     pub fn bind_addr(addr: &str, port: u16) -> std::io::Result<Self> {
-        let address = format!("{}:{}", addr, port);
+        let address = super::format_addr(addr, port);
         let socket = std::net::UdpSocket::bind(&address)?;
         println!("UDP 服务端已启动，监听 {}", address);
-        Ok(Self { socket })
+        Ok(Self::from_socket(socket))
+    }
+
+    ///绑定端口并加入组播组（由系统选择网卡）
+    ///
+    ///# 注意
+    ///标准库无法设置 SO_REUSEADDR，同一端口只能由一个进程绑定；
+    ///需要多个进程同时监听同一组播端口时，可借助 socket2 库创建 socket
+    pub fn bind_multicast(group: std::net::Ipv4Addr, port: u16) -> std::io::Result<Self> {
+        Self::bind_multicast_on(group, std::net::Ipv4Addr::UNSPECIFIED, port)
+    }
+
+    ///绑定端口并在指定网卡上加入组播组
+    pub fn bind_multicast_on(
+        group: std::net::Ipv4Addr,
+        interface: std::net::Ipv4Addr,
+        port: u16,
+    ) -> std::io::Result<Self> {
+        if !group.is_multicast() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("不是组播地址: {}", group),
+            ));
+        }
+        let addr = super::format_addr(config::SERVER_DEFAULT_ADDR, port);
+        let socket = std::net::UdpSocket::bind(&addr)?;
+        socket.join_multicast_v4(&group, &interface)?;
+        println!("UDP 服务端已启动，监听 {}，加入组播组 {}", addr, group);
+        Ok(Self::from_socket(socket))
+    }
+
+    ///离开组播组
+    pub fn leave_multicast(&self, group: std::net::Ipv4Addr, interface: std::net::Ipv4Addr) -> std::io::Result<()> {
+        self.socket.leave_multicast_v4(&group, &interface)
     }
 
     //========================================
@@ -46,7 +107,7 @@ impl UdpServer {
 
     ///接收一个数据报，返回数据和发送方地址
     pub fn recv(&self) -> std::io::Result<(Vec<u8>, std::net::SocketAddr)> {
-        let mut buf = vec![0u8; config::RECV_BUFFER_SIZE];
+        let mut buf = vec![0u8; self.recv_buffer_size];
         let (size, src_addr) = self.socket.recv_from(&mut buf)?;
         buf.truncate(size);
         Ok((buf, src_addr))
@@ -59,6 +120,43 @@ impl UdpServer {
         Ok((s, addr))
     }
 
+    ///接收分片发送的大数据，所有分片到齐后返回重组的数据和发送方地址
+    ///
+    ///分片可乱序到达；非分片格式的数据报会被忽略；
+    ///超过 config::FRAGMENT_TIMEOUT_MS 仍未收齐的消息被丢弃
+    pub fn recv_large(&self) -> std::io::Result<(Vec<u8>, std::net::SocketAddr)> {
+        loop {
+            let (data, addr) = self.recv()?;
+            if let Some(frag) = fragment::decode(&data) {
+                let mut reassembler = self.reassembler.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some(message) = reassembler.insert(addr, frag) {
+                    return Ok((message, addr));
+                }
+            }
+        }
+    }
+
+    ///带超时接收一个数据报，超时内没有数据返回 Ok(None)
+    ///
+    ///临时设置 socket 读超时，返回前恢复原来的设置
+    pub fn recv_timeout(&self, timeout: std::time::Duration) -> std::io::Result<Option<(Vec<u8>, std::net::SocketAddr)>> {
+        let old_timeout = self.socket.read_timeout()?;
+        //读超时不能为 0
+        self.socket.set_read_timeout(Some(timeout.max(std::time::Duration::from_millis(1))))?;
+        let result = self.recv();
+        self.socket.set_read_timeout(old_timeout)?;
+        Self::timeout_to_none(result)
+    }
+
+    ///将读超时错误（WouldBlock/TimedOut，因平台而异）转换为 Ok(None)
+    fn timeout_to_none<T>(result: std::io::Result<T>) -> std::io::Result<Option<T>> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
     ///阻塞式运行，为每个数据报调用回调函数
     ///
     ///参数：
@@ -82,6 +180,79 @@ impl UdpServer {
         }
     }
 
+    ///阻塞式运行，超过 interval 没有收到数据报时调用空闲回调
+    ///
+    ///适合需要定期检查停止标志或做清理工作的服务，运行期间 socket 读超时为 interval，退出后恢复
+    ///
+    ///参数：
+    ///- interval: 空闲检测间隔
+    ///- handler: 数据报处理回调，参数为(数据, 发送方地址, 服务端引用)，返回 false 停止服务
+    ///- on_idle: 空闲回调，返回 false 停止服务
+    pub fn run_with_interval<F, I>(&self, interval: std::time::Duration, mut handler: F, mut on_idle: I) -> std::io::Result<()>
+    where
+        F: FnMut(Vec<u8>, std::net::SocketAddr, &Self) -> bool,
+        I: FnMut(&Self) -> bool,
+    {
+        let old_timeout = self.socket.read_timeout()?;
+        self.socket.set_read_timeout(Some(interval.max(std::time::Duration::from_millis(1))))?;
+
+        loop {
+            let keep_running = match Self::timeout_to_none(self.recv()) {
+                Ok(Some((data, addr))) => handler(data, addr, self),
+                Ok(None) => on_idle(self),
+                Err(e) => {
+                    eprintln!("接收数据报失败: {}", e);
+                    true
+                }
+            };
+            if !keep_running {
+                println!("UDP 服务端停止");
+                break;
+            }
+        }
+
+        self.socket.set_read_timeout(old_timeout)
+    }
+
+    ///阻塞式运行可靠接收模式（配合 UdpClient::send_reliable() 使用）
+    ///
+    ///自动回复确认包，并按 (发送方地址, 序号) 去重：重传的数据报只确认、不重复调用 handler。
+    ///非可靠协议格式的数据报会被忽略
+    ///
+    ///参数：
+    ///- handler: 数据处理回调，参数为(数据, 发送方地址, 服务端引用)，返回 false 停止服务
+    pub fn run_reliable<F>(&self, mut handler: F)
+    where
+        F: FnMut(Vec<u8>, std::net::SocketAddr, &Self) -> bool,
+    {
+        let mut seen = reliable::DedupCache::new(config::RELIABLE_DEDUP_CAPACITY);
+        loop {
+            let (data, addr) = match self.recv() {
+                Ok(received) => received,
+                Err(e) => {
+                    eprintln!("接收数据报失败: {}", e);
+                    continue;
+                }
+            };
+            let (seq, payload) = match reliable::decode(&data) {
+                Some(reliable::Packet::Data { seq, payload }) => (seq, payload),
+                _ => continue,
+            };
+
+            //重复包也要确认（上次的确认可能丢失）
+            if let Err(e) = self.send_to(&addr, &reliable::encode_ack(seq)) {
+                eprintln!("发送确认失败: {}", e);
+            }
+            if !seen.insert(addr, seq) {
+                continue;
+            }
+            if !handler(payload.to_vec(), addr, self) {
+                println!("UDP 服务端停止");
+                break;
+            }
+        }
+    }
+
     //========================================
     //数据发送方法
     //========================================
@@ -96,6 +267,15 @@ impl UdpServer {
         self.send_to(addr, content.as_bytes())
     }
 
+    ///向指定地址分片发送大数据（对端使用 recv_large() 接收，尽力而为，不重传）
+    pub fn send_large_to(&self, addr: &std::net::SocketAddr, data: &[u8]) -> std::io::Result<()> {
+        let message_id = self.next_message_id.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        for packet in fragment::split(message_id, data, config::FRAGMENT_PAYLOAD_SIZE)? {
+            self.socket.send_to(&packet, addr)?;
+        }
+        Ok(())
+    }
+
     //========================================
     //底层访问
     //========================================
@@ -109,4 +289,39 @@ impl UdpServer {
     pub fn local_addr(&self) -> std::io::Result<std::net::SocketAddr> {
         self.socket.local_addr()
     }
+
+    ///设置单次接收的缓冲区大小（字节），超出部分的数据报内容会被截断
+    ///
+    ///默认为 config::RECV_BUFFER_SIZE
+    ///注：此处仅影响 recv()/recv_string() 使用的用户态缓冲区，
+    ///如需调整系统 socket 接收缓冲区（SO_RCVBUF），可借助 socket2 库
+    pub fn set_recv_buffer_size(&mut self, size: usize) {
+        self.recv_buffer_size = size;
+    }
+
+    ///获取单次接收的缓冲区大小（字节）
+    pub fn recv_buffer_size(&self) -> usize {
+        self.recv_buffer_size
+    }
+}
+
+//========================================
+//测试
+//========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recv_timeout_on_silent_socket_returns_none() {
+        let server = UdpServer::bind(0).unwrap();
+        let timeout = std::time::Duration::from_millis(150);
+
+        let started = std::time::Instant::now();
+        assert!(server.recv_timeout(timeout).unwrap().is_none());
+        assert!(started.elapsed() >= timeout);
+        //返回前恢复原来的读超时（默认一直阻塞）
+        assert_eq!(server.socket().read_timeout().unwrap(), None);
+    }
 }
@@ -9,7 +9,17 @@
 //![dependencies]
 //!tungstenite = "0.21"
 //!url = "2"
+//!
+//!#wss:// 连接需启用 TLS，并声明同名 feature 以便 set_read_timeout() 等能访问底层 TcpStream
+//![features]
+//!native-tls = ["tungstenite/native-tls"]
+//!rustls-tls = ["tungstenite/rustls-tls-webpki-roots"]
 //!```
+//!
+//!# 心跳保活
+//!enable_keepalive() 不会启动后台线程：Ping 的发送和对端存活检查只在
+//!recv()/try_recv()/messages() 等待数据期间进行。
+//!长时间不调用接收方法时既不会发送心跳，也不会发现连接已失效。
 
 use super::config;
 
@@ -69,9 +79,30 @@ impl WsMessage {
 ///WebSocket 客户端
 pub struct WsClient {
     ///底层 WebSocket 连接
-    socket: tungstenite::WebSocket<std::net::TcpStream>,
+    socket: tungstenite::WebSocket<tungstenite::stream::MaybeTlsStream<std::net::TcpStream>>,
+    ///握手协商的子协议
+    protocol: Option<String>,
+    ///用户设置的读取超时（None 表示一直阻塞）
+    read_timeout: Option<std::time::Duration>,
+    ///心跳保活状态（None 表示未启用）
+    keepalive: Option<Keepalive>,
+}
+
+///心跳保活状态
+struct Keepalive {
+    ///Ping 间隔
+    interval: std::time::Duration,
+    ///上次发送 Ping 的时间
+    last_ping: std::time::Instant,
+    ///上次收到对端任意数据的时间
+    last_seen: std::time::Instant,
+    ///是否已判定连接失效
+    dead: bool,
 }
 
+///心跳 Ping 的负载，对应的 Pong 不会返回给调用方
+const KEEPALIVE_PAYLOAD: &[u8] = b"keepalive";
+
 impl WsClient {
     ///连接到 WebSocket 服务端
     ///
@@ -80,7 +111,55 @@ impl WsClient {
     pub fn connect(url: &str) -> Result<Self, String> {
         let (socket, _response) = tungstenite::connect(url)
             .map_err(|e| format!("连接失败: {}", e))?;
-        Ok(Self { socket })
+        Ok(Self::from_socket(socket, None))
+    }
+
+    ///连接到 WebSocket 服务端（自定义握手请求头和子协议）
+    ///
+    ///参数：
+    ///- url: WebSocket URL
+    ///- headers: 额外请求头（如 Authorization）
+    ///- protocols: 请求的子协议列表（Sec-WebSocket-Protocol），为空表示不指定
+    pub fn connect_with(url: &str, headers: &[(&str, &str)], protocols: &[&str]) -> Result<Self, String> {
+        use tungstenite::client::IntoClientRequest;
+        use tungstenite::http::{HeaderName, HeaderValue};
+
+        let mut request = url
+            .into_client_request()
+            .map_err(|e| format!("URL 无效: {}", e))?;
+
+        for (key, value) in headers {
+            let name = HeaderName::from_bytes(key.as_bytes())
+                .map_err(|e| format!("请求头名称无效 {}: {}", key, e))?;
+            let value = HeaderValue::from_str(value)
+                .map_err(|e| format!("请求头值无效 {}: {}", key, e))?;
+            request.headers_mut().insert(name, value);
+        }
+
+        if !protocols.is_empty() {
+            let value = HeaderValue::from_str(&protocols.join(", "))
+                .map_err(|e| format!("子协议无效: {}", e))?;
+            request.headers_mut().insert("Sec-WebSocket-Protocol", value);
+        }
+
+        let (socket, response) = tungstenite::connect(request)
+            .map_err(|e| format!("连接失败: {}", e))?;
+
+        let protocol = response
+            .headers()
+            .get("Sec-WebSocket-Protocol")
+            .and_then(|v| v.to_str().ok())
+            .map(|s| s.to_string());
+
+        Ok(Self::from_socket(socket, protocol))
+    }
+
+    ///包装已握手的连接
+    fn from_socket(
+        socket: tungstenite::WebSocket<tungstenite::stream::MaybeTlsStream<std::net::TcpStream>>,
+        protocol: Option<String>,
+    ) -> Self {
+        Self { socket, protocol, read_timeout: None, keepalive: None }
     }
 
     ///连接到指定地址和端口
@@ -105,6 +184,29 @@ impl WsClient {
             .map_err(|e| format!("发送失败: {}", e))
     }
 
+    ///分片发送文本消息（每帧负载不超过 max_frame 字节）
+    ///
+    ///使用 WebSocket 协议层分片：首帧为 Text、后续为 Continuation 帧，
+    ///仅最后一帧置 FIN。对端收到的仍是一条完整文本消息，无需额外解析。
+    ///
+    ///按 UTF-8 字符边界切分，每帧都是合法文本；
+    ///单个字符超过 max_frame 时该帧会略大于 max_frame
+    pub fn send_text_chunked(&mut self, text: &str, max_frame: usize) -> Result<(), String> {
+        use tungstenite::protocol::frame::coding::{Data, OpCode};
+        use tungstenite::protocol::frame::Frame;
+
+        let chunks = split_utf8(text, max_frame.max(1));
+        let last = chunks.len() - 1;
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            let opcode = if i == 0 { Data::Text } else { Data::Continue };
+            let frame = Frame::message(chunk.as_bytes().to_vec(), OpCode::Data(opcode), i == last);
+            self.socket
+                .write(tungstenite::Message::Frame(frame))
+                .map_err(|e| format!("发送失败: {}", e))?;
+        }
+        self.socket.flush().map_err(|e| format!("发送失败: {}", e))
+    }
+
     ///发送二进制消息
     pub fn send_binary(&mut self, data: &[u8]) -> Result<(), String> {
         self.socket
@@ -124,9 +226,87 @@ impl WsClient {
     //========================================
 
     ///接收消息（阻塞）
+    ///
+    ///设置了读取超时时，超时返回 Err；启用心跳保活后，对端超过 2 个间隔无响应返回 Err
     pub fn recv(&mut self) -> Result<WsMessage, String> {
+        match self.recv_until_timeout()? {
+            Some(msg) => Ok(msg),
+            None => Err("接收失败: 读取超时".to_string()),
+        }
+    }
+
+    ///尝试接收消息，超时返回 Ok(None)
+    ///
+    ///需先调用 set_read_timeout() 设置超时，否则与 recv() 一样一直阻塞；
+    ///返回 Err 表示连接已断开或出错
+    ///
+    ///# 示例
+    ///```rust
+    ///client.set_read_timeout(Some(Duration::from_millis(100)))?;
+    ///match client.try_recv()? {
+    ///    Some(msg) => println!("{:?}", msg),
+    ///    None => { /*暂无消息，处理其他事情*/ }
+    ///}
+    ///```
+    pub fn try_recv(&mut self) -> Result<Option<WsMessage>, String> {
+        self.recv_until_timeout()
+    }
+
+    ///接收消息直到用户设置的读取超时，期间按需发送心跳
+    fn recv_until_timeout(&mut self) -> Result<Option<WsMessage>, String> {
+        let deadline = self.read_timeout.map(|t| std::time::Instant::now() + t);
+        loop {
+            if self.keepalive.as_ref().is_some_and(|k| k.dead) {
+                return Err("接收失败: 心跳超时，连接已失效".to_string());
+            }
+
+            match self.read_message() {
+                Ok(msg) => {
+                    if let Some(ref mut keepalive) = self.keepalive {
+                        keepalive.last_seen = std::time::Instant::now();
+                        if matches!(msg, WsMessage::Pong(ref p) if p.as_slice() == KEEPALIVE_PAYLOAD) {
+                            continue;
+                        }
+                    }
+                    return Ok(Some(msg));
+                }
+                Err(e)
+                    if matches!(*e, tungstenite::Error::Io(ref io)
+                        if matches!(io.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut)) =>
+                {
+                    self.keepalive_tick()?;
+                    if deadline.is_some_and(|d| std::time::Instant::now() >= d) {
+                        return Ok(None);
+                    }
+                }
+                Err(e) => return Err(format!("接收失败: {}", e)),
+            }
+        }
+    }
+
+    ///心跳检查：对端超过 2 个间隔无数据则判定失效，到达间隔则发送 Ping
+    fn keepalive_tick(&mut self) -> Result<(), String> {
+        let keepalive = match self.keepalive {
+            Some(ref mut k) => k,
+            None => return Ok(()),
+        };
+        if keepalive.last_seen.elapsed() > keepalive.interval * 2 {
+            keepalive.dead = true;
+            return Err("接收失败: 心跳超时，连接已失效".to_string());
+        }
+        if keepalive.last_ping.elapsed() >= keepalive.interval {
+            keepalive.last_ping = std::time::Instant::now();
+            self.socket
+                .send(tungstenite::Message::Ping(KEEPALIVE_PAYLOAD.to_vec()))
+                .map_err(|e| format!("发送心跳失败: {}", e))?;
+        }
+        Ok(())
+    }
+
+    ///读取一条消息（收到 Ping 时自动回复 Pong）
+    fn read_message(&mut self) -> Result<WsMessage, Box<tungstenite::Error>> {
         loop {
-            let msg = self.socket.read().map_err(|e| format!("接收失败: {}", e))?;
+            let msg = self.socket.read().map_err(Box::new)?;
             match msg {
                 tungstenite::Message::Text(s) => return Ok(WsMessage::Text(s)),
                 tungstenite::Message::Binary(b) => return Ok(WsMessage::Binary(b)),
@@ -142,9 +322,16 @@ impl WsClient {
         }
     }
 
-    ///尝试接收消息（非阻塞，需要设置超时）
-    pub fn try_recv(&mut self) -> Option<WsMessage> {
-        self.recv().ok()
+    ///消息迭代器，收到 Close 或出错时结束
+    ///
+    ///# 示例
+    ///```rust
+    ///for msg in client.messages() {
+    ///    println!("{:?}", msg);
+    ///}
+    ///```
+    pub fn messages(&mut self) -> WsMessages<'_> {
+        WsMessages { client: self, done: false }
     }
 
     //========================================
@@ -158,10 +345,124 @@ impl WsClient {
             .map_err(|e| format!("关闭失败: {}", e))
     }
 
+    ///设置读取超时（None 表示一直阻塞），配合 try_recv() 使用
+    pub fn set_read_timeout(&mut self, timeout: Option<std::time::Duration>) -> Result<(), String> {
+        self.read_timeout = timeout;
+        self.apply_read_timeout()
+    }
+
+    ///启用心跳保活：每隔 interval 发送 Ping，对端超过 2 个间隔没有任何数据（包括 Pong）时判定连接失效
+    ///
+    ///心跳在 recv()/try_recv()/messages() 等待数据期间发送和检查，无需额外线程；
+    ///判定失效后接收方法返回 Err。心跳对应的 Pong 不会返回给调用方。
+    ///默认间隔可参考 config::PING_INTERVAL_SECS
+    pub fn enable_keepalive(&mut self, interval: std::time::Duration) -> Result<(), String> {
+        let interval = interval.max(std::time::Duration::from_millis(10));
+        let now = std::time::Instant::now();
+        let previous = self.keepalive.replace(Keepalive { interval, last_ping: now, last_seen: now, dead: false });
+        //先设置读超时，失败时恢复原状态，避免已发出 Ping 却未启用心跳
+        if let Err(e) = self.apply_read_timeout() {
+            self.keepalive = previous;
+            return Err(e);
+        }
+        self.send_ping(KEEPALIVE_PAYLOAD)
+    }
+
+    ///关闭心跳保活
+    pub fn disable_keepalive(&mut self) -> Result<(), String> {
+        self.keepalive = None;
+        self.apply_read_timeout()
+    }
+
+    ///设置底层读超时：取用户超时与半个心跳间隔中较小的一个
+    ///
+    ///按半个间隔检查心跳，对端失效后最迟 2.5 个间隔即可发现
+    fn apply_read_timeout(&mut self) -> Result<(), String> {
+        let keepalive_interval = self.keepalive.as_ref().map(|k| k.interval / 2);
+        let timeout = match (self.read_timeout, keepalive_interval) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.tcp_stream()?
+            .set_read_timeout(timeout)
+            .map_err(|e| format!("设置读取超时失败: {}", e))
+    }
+
+    ///获取底层 TcpStream（TLS 连接取其内部的 TcpStream）
+    fn tcp_stream(&self) -> Result<&std::net::TcpStream, String> {
+        match self.socket.get_ref() {
+            tungstenite::stream::MaybeTlsStream::Plain(stream) => Ok(stream),
+            #[cfg(feature = "native-tls")]
+            tungstenite::stream::MaybeTlsStream::NativeTls(stream) => Ok(stream.get_ref()),
+            #[cfg(feature = "rustls-tls")]
+            tungstenite::stream::MaybeTlsStream::Rustls(stream) => Ok(stream.get_ref()),
+            _ => Err("当前连接类型不支持设置读取超时，请启用对应的 TLS feature".to_string()),
+        }
+    }
+
     ///检查连接是否可写
     pub fn can_write(&self) -> bool {
         self.socket.can_write()
     }
+
+    ///获取握手时服务端选定的子协议
+    pub fn protocol(&self) -> Option<&str> {
+        self.protocol.as_deref()
+    }
+}
+
+//========================================
+//分片工具
+//========================================
+
+///按 UTF-8 字符边界切分文本，每段不超过 max_bytes（单字符超长时除外）
+///
+///空文本返回一个空段，保证至少发送一帧
+fn split_utf8(text: &str, max_bytes: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = text;
+    while rest.len() > max_bytes {
+        let mut end = max_bytes;
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        if end == 0 {
+            //单个字符超过 max_bytes，整字符作为一段
+            end = rest.chars().next().map(|c| c.len_utf8()).unwrap_or(rest.len());
+        }
+        chunks.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    chunks.push(rest);
+    chunks
+}
+
+//========================================
+//消息迭代器
+//========================================
+
+///WsClient::messages() 返回的消息迭代器
+pub struct WsMessages<'a> {
+    client: &'a mut WsClient,
+    ///是否已结束（收到 Close 或出错）
+    done: bool,
+}
+
+impl Iterator for WsMessages<'_> {
+    type Item = WsMessage;
+
+    fn next(&mut self) -> Option<WsMessage> {
+        if self.done {
+            return None;
+        }
+        match self.client.recv() {
+            Ok(WsMessage::Close) | Err(_) => {
+                self.done = true;
+                None
+            }
+            Ok(msg) => Some(msg),
+        }
+    }
 }
 
 //========================================
@@ -190,3 +491,140 @@ where
     let _ = client.close();
     Ok(())
 }
+
+//========================================
+//测试
+//========================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+
+    ///启动只接受一个连接的本地服务端：要求 Bearer 令牌，并回应请求的第一个子协议
+    //回调签名由 tungstenite 决定，ErrorResponse 无法装箱
+    #[allow(clippy::result_large_err)]
+    fn spawn_auth_server() -> (u16, std::thread::JoinHandle<bool>) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let callback = |req: &Request, mut resp: Response| -> Result<Response, ErrorResponse> {
+                let authorized = req
+                    .headers()
+                    .get("Authorization")
+                    .is_some_and(|v| v == "Bearer secret");
+                if !authorized {
+                    let mut err = ErrorResponse::new(Some("unauthorized".to_string()));
+                    *err.status_mut() = tungstenite::http::StatusCode::UNAUTHORIZED;
+                    return Err(err);
+                }
+                if let Some(first) = req
+                    .headers()
+                    .get("Sec-WebSocket-Protocol")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.split(',').next())
+                {
+                    let value = first.trim().parse().unwrap();
+                    resp.headers_mut().insert("Sec-WebSocket-Protocol", value);
+                }
+                Ok(resp)
+            };
+            match tungstenite::accept_hdr(stream, callback) {
+                Ok(mut ws) => {
+                    let _ = ws.close(None);
+                    let _ = ws.read();
+                    true
+                }
+                Err(_) => false,
+            }
+        });
+        (port, handle)
+    }
+
+    #[test]
+    fn connect_with_sends_headers_and_negotiates_protocol() {
+        let (port, server) = spawn_auth_server();
+        let url = format!("ws://127.0.0.1:{}", port);
+        let client = WsClient::connect_with(
+            &url,
+            &[("Authorization", "Bearer secret")],
+            &["chat.v2", "chat.v1"],
+        )
+        .unwrap();
+        assert_eq!(client.protocol(), Some("chat.v2"));
+        drop(client);
+        assert!(server.join().unwrap());
+    }
+
+    #[test]
+    fn connect_with_without_token_is_rejected() {
+        let (port, server) = spawn_auth_server();
+        let url = format!("ws://127.0.0.1:{}", port);
+        let err = WsClient::connect_with(&url, &[], &["chat.v2"]).err().unwrap();
+        assert!(err.contains("401"), "{}", err);
+        assert!(!server.join().unwrap());
+    }
+
+    #[test]
+    fn connect_with_without_protocols_has_none() {
+        let (port, server) = spawn_auth_server();
+        let url = format!("ws://127.0.0.1:{}", port);
+        let client = WsClient::connect_with(&url, &[("Authorization", "Bearer secret")], &[]).unwrap();
+        assert_eq!(client.protocol(), None);
+        drop(client);
+        assert!(server.join().unwrap());
+    }
+
+    ///启动完成握手后不再读写的本地服务端，发送端被丢弃时关闭连接
+    ///
+    ///服务端从不读取，因此也不会回复客户端的 Ping
+    fn spawn_silent_server() -> (u16, std::sync::mpsc::Sender<()>, std::thread::JoinHandle<()>) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (tx, rx) = std::sync::mpsc::channel::<()>();
+        let handle = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let _ws = tungstenite::accept(stream).unwrap();
+            let _ = rx.recv();
+        });
+        (port, tx, handle)
+    }
+
+    #[test]
+    fn try_recv_on_silent_server_returns_none_after_timeout() {
+        let (port, stop, server) = spawn_silent_server();
+        let mut client = WsClient::connect(&format!("ws://127.0.0.1:{}", port)).unwrap();
+        let timeout = std::time::Duration::from_millis(200);
+        client.set_read_timeout(Some(timeout)).unwrap();
+
+        let started = std::time::Instant::now();
+        assert!(client.try_recv().unwrap().is_none());
+        let elapsed = started.elapsed();
+        assert!(elapsed >= timeout, "{:?}", elapsed);
+        assert!(elapsed < timeout * 5, "{:?}", elapsed);
+
+        drop(stop);
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn keepalive_detects_peer_that_stops_answering_pings() {
+        let (port, stop, server) = spawn_silent_server();
+        let mut client = WsClient::connect(&format!("ws://127.0.0.1:{}", port)).unwrap();
+        let interval = std::time::Duration::from_millis(200);
+        client.enable_keepalive(interval).unwrap();
+
+        let started = std::time::Instant::now();
+        let err = client.recv().unwrap_err();
+        let elapsed = started.elapsed();
+        assert!(err.contains("心跳超时"), "{}", err);
+        assert!(elapsed >= interval * 2, "{:?}", elapsed);
+        assert!(elapsed < interval * 3, "{:?}", elapsed);
+        //判定失效后再次接收仍返回错误
+        assert!(client.recv().is_err());
+
+        drop(stop);
+        server.join().unwrap();
+    }
+}
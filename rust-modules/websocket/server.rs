@@ -64,6 +64,30 @@ impl WsConnection {
             .close(None)
             .map_err(|e| format!("关闭失败: {}", e))
     }
+    ///循环接收消息直到连接关闭
+    ///
+    ///参数：
+    ///- handler: 消息处理函数，返回 false 主动断开
+    pub fn for_each<F>(mut self, mut handler: F)
+    where
+        F: FnMut(&mut Self, WsMessage) -> bool,
+    {
+        loop {
+            match self.recv() {
+                Ok(WsMessage::Close) => break,
+                Ok(msg) => {
+                    if !handler(&mut self, msg) {
+                        let _ = self.close();
+                        break;
+                    }
+                }
+                Err(e) => {
+                    eprintln!("接收错误: {}", e);
+                    break;
+                }
+            }
+        }
+    }
 }
 
 //========================================
@@ -181,3 +205,14 @@ impl WsServer {
         self.listener.local_addr()
     }
 }
+
+//========================================
+//消息压缩（permessage-deflate）
+//========================================
+
+//tungstenite 目前不支持 permessage-deflate 扩展（见其 README），
+//收到 RSV1 置位的压缩帧会直接报协议错误，因此无法在握手中协商压缩。
+//
+//如需降低带宽，可选方案：
+//- 在应用层压缩后通过 send_binary() 发送（需客户端配合解压）
+//- 在服务端前放置支持压缩的反向代理（如 nginx）